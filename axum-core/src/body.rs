@@ -1,6 +1,6 @@
 //! HTTP body utilities.
 
-use crate::{BoxError, Error};
+use crate::{ext_traits::request::BodyLimit, BoxError, Error};
 use bytes::Bytes;
 use futures_util::stream::Stream;
 use futures_util::TryStream;
@@ -8,6 +8,7 @@ use http_body::{Body as _, Frame};
 use http_body_util::BodyExt;
 use pin_project_lite::pin_project;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use sync_wrapper::SyncWrapper;
 
@@ -36,7 +37,11 @@ where
 
 /// The body type used in axum requests and responses.
 #[derive(Debug)]
-pub struct Body(BoxBody);
+pub struct Body {
+    inner: BoxBody,
+    // set if this body was created by `RequestExt::with_limited_body`
+    pub(crate) limit: Option<Arc<BodyLimit>>,
+}
 
 impl Body {
     /// Create a new `Body` that wraps another [`http_body::Body`].
@@ -45,7 +50,10 @@ impl Body {
         B: http_body::Body<Data = Bytes> + Send + 'static,
         B::Error: Into<BoxError>,
     {
-        try_downcast(body).unwrap_or_else(|body| Self(boxed(body)))
+        try_downcast(body).unwrap_or_else(|body| Self {
+            inner: boxed(body),
+            limit: None,
+        })
     }
 
     /// Create an empty body.
@@ -76,6 +84,19 @@ impl Body {
     pub fn into_data_stream(self) -> BodyDataStream {
         BodyDataStream { inner: self }
     }
+
+    /// Limit the body to `limit` bytes.
+    ///
+    /// The limit is applied lazily, as the body is read. Reading past it yields an error that can
+    /// be downcast to [`http_body_util::LengthLimitError`].
+    ///
+    /// Use [`RequestExt::into_limited_body`](crate::RequestExt::into_limited_body) to apply the
+    /// [default body limit](crate::extract::DefaultBodyLimit) instead.
+    ///
+    /// [`http_body_util::LengthLimitError`]: https://docs.rs/http-body-util/latest/http_body_util/struct.LengthLimitError.html
+    pub fn limited(self, limit: usize) -> Self {
+        Self::new(http_body_util::Limited::new(self, limit))
+    }
 }
 
 impl Default for Body {
@@ -119,17 +140,17 @@ impl http_body::Body for Body {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
-        Pin::new(&mut self.0).poll_frame(cx)
+        Pin::new(&mut self.inner).poll_frame(cx)
     }
 
     #[inline]
     fn size_hint(&self) -> http_body::SizeHint {
-        self.0.size_hint()
+        self.inner.size_hint()
     }
 
     #[inline]
     fn is_end_stream(&self) -> bool {
-        self.0.is_end_stream()
+        self.inner.is_end_stream()
     }
 }
 
@@ -1,6 +1,20 @@
 use crate::body::Body;
-use crate::extract::{DefaultBodyLimitKind, FromRequest, FromRequestParts, Request};
+use crate::extract::{
+    rejection::BytesRejection, DefaultBodyLimitKind, FromRequest, FromRequestParts, Request,
+};
+use crate::response::{IntoResponse, Response};
+use crate::BoxError;
+use bytes::Bytes;
 use futures_util::future::BoxFuture;
+use http_body::Body as _;
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
 
 mod sealed {
     pub trait Sealed {}
@@ -129,6 +143,40 @@ pub trait RequestExt: sealed::Sealed + Sized {
         E: FromRequest<S, M> + 'static,
         S: Send + Sync;
 
+    /// Apply an extractor to this `Request`, converting the rejection into a [`Response`].
+    ///
+    /// This is just a convenience for
+    /// `req.extract::<E, M>().await.map_err(IntoResponse::into_response)`, for when the typed
+    /// rejection isn't needed, for example in middleware.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axum::{
+    ///     body::{Body, Bytes},
+    ///     extract::Request,
+    ///     middleware::Next,
+    ///     response::Response,
+    ///     RequestExt,
+    /// };
+    ///
+    /// async fn log_body(req: Request, next: Next) -> Result<Response, Response> {
+    ///     let (parts, body) = req.into_parts();
+    ///
+    ///     // responds with the rejection if buffering the body fails
+    ///     let bytes = Request::from_parts(parts.clone(), body)
+    ///         .extract_response::<Bytes, _>()
+    ///         .await?;
+    ///     println!("request body: {bytes:?}");
+    ///
+    ///     Ok(next.run(Request::from_parts(parts, Body::from(bytes))).await)
+    /// }
+    /// ```
+    fn extract_response<E, M>(self) -> BoxFuture<'static, Result<E, Response>>
+    where
+        E: FromRequest<(), M> + 'static,
+        M: 'static;
+
     /// Apply a parts extractor to this `Request`.
     ///
     /// This is just a convenience for `E::from_request_parts(parts, state)`.
@@ -255,9 +303,49 @@ pub trait RequestExt: sealed::Sealed + Sized {
         E: FromRequestParts<S> + 'static,
         S: Send + Sync;
 
+    /// Apply a body extractor to this `Request` without consuming the body.
+    ///
+    /// The body is buffered into [`Bytes`], with the [default body
+    /// limit](crate::extract::DefaultBodyLimit) applied, and `E` runs against a copy of the
+    /// request. The buffered body is then put back into `self` so it can be read again.
+    ///
+    /// If buffering the body fails, the error is returned and the body is left empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axum::{
+    ///     extract::Request,
+    ///     middleware::Next,
+    ///     response::{IntoResponse, Response},
+    ///     Json, RequestExt,
+    /// };
+    /// use serde_json::Value;
+    ///
+    /// async fn log_json_body(mut req: Request, next: Next) -> Response {
+    ///     let Json(payload) = match req.try_extract_peek::<Json<Value>, _>().await {
+    ///         Ok(payload) => payload,
+    ///         Err(rejection) => return rejection.into_response(),
+    ///     };
+    ///     println!("request body: {payload}");
+    ///
+    ///     // the body can still be read by the handler
+    ///     next.run(req).await
+    /// }
+    /// ```
+    fn try_extract_peek<E, M>(&mut self) -> BoxFuture<'_, Result<E, E::Rejection>>
+    where
+        E: FromRequest<(), M> + 'static,
+        E::Rejection: From<BytesRejection>,
+        M: 'static;
+
     /// Apply the [default body limit](crate::extract::DefaultBodyLimit).
     ///
     /// If it is disabled, the request is returned as-is.
+    ///
+    /// Calling this on a request whose body has already been limited, and not been read from yet,
+    /// doesn't wrap the body again. Instead the existing limit is lowered if the new one is
+    /// smaller. If the body has been replaced in the meantime, the new body is limited.
     fn with_limited_body(self) -> Request;
 
     /// Consumes the request, returning the body wrapped in [`http_body_util::Limited`] if a
@@ -266,6 +354,10 @@ pub trait RequestExt: sealed::Sealed + Sized {
     fn into_limited_body(self) -> Body;
 }
 
+// update docs in `axum-core/src/extract/default_body_limit.rs` and
+// `axum/src/docs/extract.md` if this changes
+const DEFAULT_LIMIT: usize = 2_097_152; // 2 mb
+
 impl RequestExt for Request {
     fn extract<E, M>(self) -> BoxFuture<'static, Result<E, E::Rejection>>
     where
@@ -283,6 +375,15 @@ impl RequestExt for Request {
         E::from_request(self, state)
     }
 
+    fn extract_response<E, M>(self) -> BoxFuture<'static, Result<E, Response>>
+    where
+        E: FromRequest<(), M> + 'static,
+        M: 'static,
+    {
+        let future = self.extract::<E, M>();
+        Box::pin(async move { future.await.map_err(IntoResponse::into_response) })
+    }
+
     fn extract_parts<E>(&mut self) -> BoxFuture<'_, Result<E, E::Rejection>>
     where
         E: FromRequestParts<()> + 'static,
@@ -319,18 +420,52 @@ impl RequestExt for Request {
         })
     }
 
+    fn try_extract_peek<E, M>(&mut self) -> BoxFuture<'_, Result<E, E::Rejection>>
+    where
+        E: FromRequest<(), M> + 'static,
+        E::Rejection: From<BytesRejection>,
+        M: 'static,
+    {
+        Box::pin(async move {
+            let body = std::mem::take(self.body_mut());
+            let bytes = Bytes::from_request(copy_with_body(self, body), &()).await?;
+
+            *self.body_mut() = Body::from(bytes.clone());
+
+            E::from_request(copy_with_body(self, Body::from(bytes)), &()).await
+        })
+    }
+
     fn with_limited_body(self) -> Request {
-        // update docs in `axum-core/src/extract/default_body_limit.rs` and
-        // `axum/src/docs/extract.md` if this changes
-        const DEFAULT_LIMIT: usize = 2_097_152; // 2 mb
-
-        match self.extensions().get::<DefaultBodyLimitKind>().copied() {
-            Some(DefaultBodyLimitKind::Disable) => self,
-            Some(DefaultBodyLimitKind::Limit(limit)) => {
-                self.map(|b| Body::new(http_body_util::Limited::new(b, limit)))
+        let limit = match self.extensions().get::<DefaultBodyLimitKind>().copied() {
+            Some(DefaultBodyLimitKind::Disable) => return self,
+            Some(DefaultBodyLimitKind::Limit(limit)) => limit,
+            None => DEFAULT_LIMIT,
+        };
+
+        // the body is still the one we limited and hasn't been read from, so tighten that limit
+        // instead of wrapping it again
+        if let Some(current) = self.body().limit.as_ref() {
+            if !current.polled.load(Ordering::Acquire) {
+                current.limit.fetch_min(limit, Ordering::AcqRel);
+                return self;
             }
-            None => self.map(|b| Body::new(http_body_util::Limited::new(b, DEFAULT_LIMIT))),
         }
+
+        self.map(|body| {
+            let current = Arc::new(BodyLimit {
+                limit: AtomicUsize::new(limit),
+                polled: AtomicBool::new(false),
+            });
+            let mut limited = Body::new(LimitedBody {
+                state: LimitedBodyState::Pending {
+                    body,
+                    limit: Arc::clone(&current),
+                },
+            });
+            limited.limit = Some(current);
+            limited
+        })
     }
 
     fn into_limited_body(self) -> Body {
@@ -338,6 +473,120 @@ impl RequestExt for Request {
     }
 }
 
+/// Get the limit that [`RequestExt::into_limited_body`] would apply to the body of `req`, if any.
+pub(crate) fn effective_body_limit(req: &Request) -> Option<usize> {
+    let current = req
+        .body()
+        .limit
+        .as_ref()
+        .map(|current| current.limit.load(Ordering::Acquire));
+
+    let limit = match req.extensions().get::<DefaultBodyLimitKind>().copied() {
+        Some(DefaultBodyLimitKind::Disable) => return current,
+        Some(DefaultBodyLimitKind::Limit(limit)) => limit,
+        None => DEFAULT_LIMIT,
+    };
+
+    Some(current.map_or(limit, |current| current.min(limit)))
+}
+
+/// Create a new request with the same parts as `req` but a different body.
+fn copy_with_body(req: &Request, body: Body) -> Request {
+    let mut copy = Request::new(body);
+    *copy.version_mut() = req.version();
+    *copy.method_mut() = req.method().clone();
+    *copy.uri_mut() = req.uri().clone();
+    *copy.headers_mut() = req.headers().clone();
+    *copy.extensions_mut() = req.extensions().clone();
+    copy
+}
+
+/// The limit of a body created by [`RequestExt::with_limited_body`].
+///
+/// This is shared between the [`LimitedBody`] and the [`Body`] wrapping it, rather than stored in
+/// the request extensions, so that replacing the body also drops the limit and the new body gets
+/// limited again.
+#[derive(Debug)]
+pub(crate) struct BodyLimit {
+    // the limit that'll be applied once the body is polled
+    limit: AtomicUsize,
+    // set once the body has been polled, after which changing `limit` has no effect
+    polled: AtomicBool,
+}
+
+/// Body that applies [`http_body_util::Limited`] the first time it's polled.
+///
+/// Deferring the wrapping allows calling `with_limited_body` multiple times and having the
+/// smallest limit win, without nesting multiple `Limited` bodies and their error types.
+struct LimitedBody {
+    state: LimitedBodyState,
+}
+
+enum LimitedBodyState {
+    Pending { body: Body, limit: Arc<BodyLimit> },
+    Polled(http_body_util::Limited<Body>),
+    Empty,
+}
+
+impl LimitedBody {
+    fn limited(&mut self) -> &mut http_body_util::Limited<Body> {
+        self.state = match std::mem::replace(&mut self.state, LimitedBodyState::Empty) {
+            LimitedBodyState::Pending { body, limit } => {
+                limit.polled.store(true, Ordering::Release);
+                LimitedBodyState::Polled(http_body_util::Limited::new(
+                    body,
+                    limit.limit.load(Ordering::Acquire),
+                ))
+            }
+            state => state,
+        };
+
+        match &mut self.state {
+            LimitedBodyState::Polled(limited) => limited,
+            LimitedBodyState::Pending { .. } | LimitedBodyState::Empty => unreachable!(),
+        }
+    }
+}
+
+impl http_body::Body for LimitedBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        Pin::new(self.limited()).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.state {
+            LimitedBodyState::Pending { body, .. } => body.is_end_stream(),
+            LimitedBodyState::Polled(limited) => limited.is_end_stream(),
+            LimitedBodyState::Empty => true,
+        }
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        match &self.state {
+            LimitedBodyState::Pending { body, limit } => {
+                let limit = limit.limit.load(Ordering::Acquire) as u64;
+                let mut hint = body.size_hint();
+                if hint.lower() >= limit {
+                    hint.set_exact(limit);
+                } else if let Some(max) = hint.upper() {
+                    hint.set_upper(limit.min(max));
+                } else {
+                    hint.set_upper(limit);
+                }
+                hint
+            }
+            LimitedBodyState::Polled(limited) => limited.size_hint(),
+            LimitedBodyState::Empty => http_body::SizeHint::with_exact(0),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -366,6 +615,17 @@ mod tests {
         assert_eq!(body, "foobar");
     }
 
+    #[tokio::test]
+    async fn extract_response() {
+        let req = Request::new(Body::from("foobar"));
+        let body: String = req.extract_response().await.unwrap();
+        assert_eq!(body, "foobar");
+
+        let req = Request::new(Body::from(vec![0xfa, 0xfb]));
+        let res = req.extract_response::<String, _>().await.unwrap_err();
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn extract_with_state() {
         let req = Request::new(Body::empty());
@@ -406,6 +666,83 @@ mod tests {
         assert_eq!(req.headers()["x-foo"], "foo");
     }
 
+    #[tokio::test]
+    async fn try_extract_peek_keeps_body() {
+        let mut req = Request::new(Body::from(r#"{"foo":"bar"}"#));
+
+        let peeked: Bytes = req.try_extract_peek().await.unwrap();
+        let json = serde_json::from_slice::<serde_json::Value>(&peeked).unwrap();
+        assert_eq!(json["foo"], "bar");
+
+        let body: String = req.extract().await.unwrap();
+        assert_eq!(body, r#"{"foo":"bar"}"#);
+    }
+
+    #[tokio::test]
+    async fn with_limited_body_twice_keeps_smallest_limit() {
+        use crate::extract::rejection::{BytesRejection, FailedToBufferBody};
+
+        for (first, second) in [(10, 5), (5, 10)] {
+            let mut req = Request::new(Body::from("too long"));
+            req.extensions_mut()
+                .insert(DefaultBodyLimitKind::Limit(first));
+            let mut req = req.with_limited_body();
+            req.extensions_mut()
+                .insert(DefaultBodyLimitKind::Limit(second));
+            let req = req.with_limited_body();
+
+            // `Bytes` applies the limit a third time
+            let rejection = Bytes::from_request(req, &()).await.unwrap_err();
+            assert!(matches!(
+                rejection,
+                BytesRejection::FailedToBufferBody(FailedToBufferBody::LengthLimitError(_))
+            ));
+        }
+
+        let mut req = Request::new(Body::from("short"));
+        req.extensions_mut().insert(DefaultBodyLimitKind::Limit(5));
+        let req = req.with_limited_body().with_limited_body();
+        assert_eq!(Bytes::from_request(req, &()).await.unwrap(), "short");
+    }
+
+    #[tokio::test]
+    async fn with_limited_body_after_replacing_body() {
+        use crate::extract::rejection::{BytesRejection, FailedToBufferBody};
+
+        let mut req = Request::new(Body::from("short"));
+        req.extensions_mut().insert(DefaultBodyLimitKind::Limit(5));
+        let req = req.with_limited_body();
+
+        // for example a middleware that decompresses the body
+        let (parts, _) = req.into_parts();
+        let req = Request::from_parts(parts, Body::from("much longer"));
+
+        let rejection = Bytes::from_request(req, &()).await.unwrap_err();
+        assert!(matches!(
+            rejection,
+            BytesRejection::FailedToBufferBody(FailedToBufferBody::LengthLimitError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn with_limited_body_after_polling() {
+        use http_body_util::BodyExt;
+        use std::convert::Infallible;
+
+        let stream = futures_util::stream::iter([Ok::<_, Infallible>("abc"), Ok("defgh")]);
+        let mut req = Request::new(Body::from_stream(stream));
+        req.extensions_mut().insert(DefaultBodyLimitKind::Limit(10));
+        let mut req = req.with_limited_body();
+
+        let frame = req.body_mut().frame().await.unwrap().unwrap();
+        assert_eq!(frame.into_data().unwrap(), "abc");
+
+        // the first limit is already in effect, so the lower one must wrap the body again
+        req.extensions_mut().insert(DefaultBodyLimitKind::Limit(4));
+        let req = req.with_limited_body();
+        assert!(req.into_body().collect().await.is_err());
+    }
+
     // this stuff just needs to compile
     #[allow(dead_code)]
     struct WorksForCustomExtractor {
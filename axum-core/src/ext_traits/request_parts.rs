@@ -1,6 +1,7 @@
 use crate::extract::FromRequestParts;
+use crate::response::{IntoResponse, Response};
 use futures_util::future::BoxFuture;
-use http::request::Parts;
+use http::{request::Parts, HeaderName, HeaderValue};
 
 mod sealed {
     pub trait Sealed {}
@@ -117,6 +118,62 @@ pub trait RequestPartsExt: sealed::Sealed + Sized {
     where
         E: FromRequestParts<S> + 'static,
         S: Send + Sync;
+
+    /// Apply an extractor to this `Parts`, converting the rejection into a [`Response`].
+    ///
+    /// This is just a convenience for
+    /// `parts.extract::<E>().await.map_err(IntoResponse::into_response)`, for when the typed
+    /// rejection isn't needed, for example in middleware.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axum::{
+    ///     extract::{Path, Request},
+    ///     middleware::Next,
+    ///     response::Response,
+    ///     RequestPartsExt,
+    /// };
+    ///
+    /// async fn log_user_id(req: Request, next: Next) -> Result<Response, Response> {
+    ///     let (mut parts, body) = req.into_parts();
+    ///
+    ///     let Path(user_id) = parts.extract_response::<Path<u32>>().await?;
+    ///     println!("user id: {user_id}");
+    ///
+    ///     Ok(next.run(Request::from_parts(parts, body)).await)
+    /// }
+    /// ```
+    fn extract_response<E>(&mut self) -> BoxFuture<'_, Result<E, Response>>
+    where
+        E: FromRequestParts<()> + 'static;
+
+    /// Get all headers, in order, with their values converted to strings.
+    ///
+    /// Unlike calling [`HeaderValue::to_str`] and skipping failures, values that aren't visible
+    /// ASCII, such as binary headers, are kept as an `Err` containing the raw value. Headers with
+    /// multiple values appear once per value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axum::{http::Request, RequestPartsExt};
+    ///
+    /// let (parts, _) = Request::builder()
+    ///     .header("x-text", "hello")
+    ///     .header("x-binary", &[0xfa, 0xfb][..])
+    ///     .body(())
+    ///     .unwrap()
+    ///     .into_parts();
+    ///
+    /// for (name, value) in parts.extract_headers() {
+    ///     match value {
+    ///         Ok(value) => println!("{name}: {value}"),
+    ///         Err(raw) => println!("{name}: <{} binary bytes>", raw.len()),
+    ///     }
+    /// }
+    /// ```
+    fn extract_headers(&self) -> Vec<(HeaderName, Result<String, HeaderValue>)>;
 }
 
 impl RequestPartsExt for Parts {
@@ -137,6 +194,27 @@ impl RequestPartsExt for Parts {
     {
         E::from_request_parts(self, state)
     }
+
+    fn extract_response<E>(&mut self) -> BoxFuture<'_, Result<E, Response>>
+    where
+        E: FromRequestParts<()> + 'static,
+    {
+        let future = self.extract::<E>();
+        Box::pin(async move { future.await.map_err(IntoResponse::into_response) })
+    }
+
+    fn extract_headers(&self) -> Vec<(HeaderName, Result<String, HeaderValue>)> {
+        self.headers
+            .iter()
+            .map(|(name, value)| {
+                let value = value
+                    .to_str()
+                    .map(ToOwned::to_owned)
+                    .map_err(|_| value.clone());
+                (name.clone(), value)
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -174,6 +252,56 @@ mod tests {
         assert_eq!(extracted_state, state);
     }
 
+    #[tokio::test]
+    async fn extract_response() {
+        struct Fails;
+
+        #[async_trait]
+        impl<S> FromRequestParts<S> for Fails
+        where
+            S: Send + Sync,
+        {
+            type Rejection = http::StatusCode;
+
+            async fn from_request_parts(_: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+                Err(http::StatusCode::UNAUTHORIZED)
+            }
+        }
+
+        let (mut parts, _) = Request::new(()).into_parts();
+
+        let method: Method = parts.extract_response().await.unwrap();
+        assert_eq!(method, Method::GET);
+
+        let res = parts.extract_response::<Fails>().await.unwrap_err();
+        assert_eq!(res.status(), http::StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn extract_headers() {
+        let (parts, _) = Request::builder()
+            .header("x-text", "hello")
+            .header("x-binary", &[0xfa, 0xfb][..])
+            .header("x-text", "world")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        let headers = parts.extract_headers();
+
+        assert_eq!(
+            headers,
+            [
+                (HeaderName::from_static("x-text"), Ok("hello".to_owned())),
+                (HeaderName::from_static("x-text"), Ok("world".to_owned())),
+                (
+                    HeaderName::from_static("x-binary"),
+                    Err(HeaderValue::from_bytes(&[0xfa, 0xfb]).unwrap())
+                ),
+            ]
+        );
+    }
+
     // this stuff just needs to compile
     #[allow(dead_code)]
     struct WorksForCustomExtractor {
@@ -62,6 +62,30 @@ define_rejection! {
     pub struct InvalidUtf8(Error);
 }
 
+define_rejection! {
+    #[status = PAYLOAD_TOO_LARGE]
+    #[body = "Request body contains too many characters"]
+    /// Rejection type used by [`LimitedChars`](super::LimitedChars) if the body contains more
+    /// characters than allowed.
+    pub struct TooManyCharacters;
+}
+
+define_rejection! {
+    #[status = METHOD_NOT_ALLOWED]
+    #[body = "Request method is not safe"]
+    /// Rejection type used by [`SafeMethod`](super::SafeMethod) if the request method isn't
+    /// `GET`, `HEAD`, `OPTIONS`, or `TRACE`.
+    pub struct MethodNotSafe;
+}
+
+define_rejection! {
+    #[status = METHOD_NOT_ALLOWED]
+    #[body = "Request method is not idempotent"]
+    /// Rejection type used by [`IdempotentMethod`](super::IdempotentMethod) if the request
+    /// method isn't idempotent.
+    pub struct MethodNotIdempotent;
+}
+
 composite_rejection! {
     /// Rejection used for [`Bytes`](bytes::Bytes).
     ///
@@ -81,3 +105,15 @@ composite_rejection! {
         InvalidUtf8,
     }
 }
+
+composite_rejection! {
+    /// Rejection used for [`LimitedChars`](super::LimitedChars).
+    ///
+    /// Contains one variant for each way the [`LimitedChars`](super::LimitedChars) extractor can
+    /// fail.
+    pub enum LimitedCharsRejection {
+        FailedToBufferBody,
+        InvalidUtf8,
+        TooManyCharacters,
+    }
+}
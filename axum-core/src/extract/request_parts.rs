@@ -1,10 +1,10 @@
 use super::{rejection::*, FromRequest, FromRequestParts, Request};
-use crate::{body::Body, RequestExt};
+use crate::{body::Body, ext_traits::request::effective_body_limit, RequestExt};
 use async_trait::async_trait;
 use bytes::Bytes;
-use http::{request::Parts, Extensions, HeaderMap, Method, Uri, Version};
+use http::{header, request::Parts, Extensions, HeaderMap, Method, Uri, Version};
 use http_body_util::BodyExt;
-use std::convert::Infallible;
+use std::{convert::Infallible, fmt};
 
 #[async_trait]
 impl<S> FromRequest<S> for Request
@@ -30,6 +30,86 @@ where
     }
 }
 
+/// Extractor that succeeds only for [safe] request methods.
+///
+/// Safe methods are `GET`, `HEAD`, `OPTIONS`, and `TRACE`. Other methods are rejected with
+/// `405 Method Not Allowed`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{extract::SafeMethod, routing::any, Router};
+///
+/// async fn handler(SafeMethod(method): SafeMethod) {
+///     // `method` is guaranteed not to modify any state on the server
+/// }
+///
+/// let app = Router::new().route("/", any(handler));
+/// # let _: Router = app;
+/// ```
+///
+/// [safe]: https://www.rfc-editor.org/rfc/rfc9110#section-9.2.1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafeMethod(pub Method);
+
+impl SafeMethod {
+    /// Returns whether `method` is safe.
+    pub fn is_safe(method: &Method) -> bool {
+        matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+        )
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for SafeMethod
+where
+    S: Send + Sync,
+{
+    type Rejection = MethodNotSafe;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        if Self::is_safe(&parts.method) {
+            Ok(Self(parts.method.clone()))
+        } else {
+            Err(MethodNotSafe)
+        }
+    }
+}
+
+/// Extractor that succeeds only for [idempotent] request methods.
+///
+/// Idempotent methods are the [safe](SafeMethod) methods plus `PUT` and `DELETE`. Other methods
+/// are rejected with `405 Method Not Allowed`.
+///
+/// [idempotent]: https://www.rfc-editor.org/rfc/rfc9110#section-9.2.2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotentMethod(pub Method);
+
+impl IdempotentMethod {
+    /// Returns whether `method` is idempotent.
+    pub fn is_idempotent(method: &Method) -> bool {
+        SafeMethod::is_safe(method) || matches!(*method, Method::PUT | Method::DELETE)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for IdempotentMethod
+where
+    S: Send + Sync,
+{
+    type Rejection = MethodNotIdempotent;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        if Self::is_idempotent(&parts.method) {
+            Ok(Self(parts.method.clone()))
+        } else {
+            Err(MethodNotIdempotent)
+        }
+    }
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for Uri
 where
@@ -79,6 +159,8 @@ where
     type Rejection = BytesRejection;
 
     async fn from_request(req: Request, _: &S) -> Result<Self, Self::Rejection> {
+        check_content_length(&req)?;
+
         let bytes = req
             .into_limited_body()
             .collect()
@@ -90,6 +172,46 @@ where
     }
 }
 
+/// Reject requests whose `Content-Length` exceeds the body limit without reading the body.
+///
+/// Bodies without a `Content-Length` are still limited while they're buffered.
+fn check_content_length(req: &Request) -> Result<(), FailedToBufferBody> {
+    let Some(limit) = effective_body_limit(req) else {
+        return Ok(());
+    };
+
+    let content_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    match content_length {
+        Some(length) if length > limit as u64 => {
+            Err(LengthLimitError::from_err(ContentLengthExceedsLimit { length, limit }).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug)]
+struct ContentLengthExceedsLimit {
+    length: u64,
+    limit: usize,
+}
+
+impl fmt::Display for ContentLengthExceedsLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "content length of {} bytes exceeds the limit of {} bytes",
+            self.length, self.limit
+        )
+    }
+}
+
+impl std::error::Error for ContentLengthExceedsLimit {}
+
 #[async_trait]
 impl<S> FromRequest<S> for String
 where
@@ -114,6 +236,104 @@ where
     }
 }
 
+/// Extractor that buffers the request body into a [`String`] with at most `N` characters.
+///
+/// Characters are counted as Unicode scalar values, not bytes, so multi-byte characters count as
+/// one. Requests with more characters are rejected with `413 Payload Too Large`.
+///
+/// Since a character is at most four bytes of UTF-8, the body is limited to `4 * N` bytes while
+/// buffering, in addition to the [default body limit](super::DefaultBodyLimit).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{extract::LimitedChars, routing::post, Router};
+///
+/// async fn set_display_name(LimitedChars(name): LimitedChars<64>) {
+///     // `name` is at most 64 characters
+/// }
+///
+/// let app = Router::new().route("/display-name", post(set_display_name));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitedChars<const N: usize>(pub String);
+
+#[async_trait]
+impl<S, const N: usize> FromRequest<S> for LimitedChars<N>
+where
+    S: Send + Sync,
+{
+    type Rejection = LimitedCharsRejection;
+
+    async fn from_request(req: Request, _: &S) -> Result<Self, Self::Rejection> {
+        let body = req.into_limited_body();
+        let bytes = http_body_util::Limited::new(body, N.saturating_mul(4))
+            .collect()
+            .await
+            .map_err(FailedToBufferBody::from_err)?
+            .to_bytes();
+
+        let string = std::str::from_utf8(&bytes).map_err(InvalidUtf8::from_err)?;
+
+        if string.chars().nth(N).is_some() {
+            return Err(TooManyCharacters.into());
+        }
+
+        Ok(Self(string.to_owned()))
+    }
+}
+
+/// Extractor that buffers the request body and its trailers.
+///
+/// The [`Bytes`] extractor discards any [trailers] sent after the body. Since the body extractor
+/// must be the last argument to a handler, trailers can't be extracted separately afterwards, so
+/// this extractor returns both. If the request has no trailers the [`HeaderMap`] is empty.
+///
+/// Note that trailers are only sent with HTTP/2 requests or HTTP/1.1 requests using chunked
+/// transfer encoding.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{extract::BytesAndTrailers, routing::post, Router};
+///
+/// async fn upload(BytesAndTrailers(bytes, trailers): BytesAndTrailers) {
+///     if let Some(checksum) = trailers.get("x-checksum") {
+///         // verify `bytes` against `checksum`...
+///     }
+/// }
+///
+/// let app = Router::new().route("/upload", post(upload));
+/// # let _: Router = app;
+/// ```
+///
+/// [trailers]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Trailer
+#[derive(Debug, Clone)]
+pub struct BytesAndTrailers(pub Bytes, pub HeaderMap);
+
+#[async_trait]
+impl<S> FromRequest<S> for BytesAndTrailers
+where
+    S: Send + Sync,
+{
+    type Rejection = BytesRejection;
+
+    async fn from_request(req: Request, _: &S) -> Result<Self, Self::Rejection> {
+        check_content_length(&req)?;
+
+        let collected = req
+            .into_limited_body()
+            .collect()
+            .await
+            .map_err(FailedToBufferBody::from_err)?;
+
+        let trailers = collected.trailers().cloned().unwrap_or_default();
+
+        Ok(Self(collected.to_bytes(), trailers))
+    }
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for Parts
 where
@@ -149,3 +369,173 @@ where
         Ok(req.into_body())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn extract<E>(method: Method) -> bool
+    where
+        E: FromRequestParts<()> + 'static,
+    {
+        let mut req = Request::builder()
+            .method(method)
+            .body(Body::empty())
+            .unwrap();
+        req.extract_parts::<E>().await.is_ok()
+    }
+
+    #[tokio::test]
+    async fn limited_chars() {
+        use http::StatusCode;
+
+        async fn limited<const N: usize>(body: &'static str) -> Result<String, StatusCode> {
+            Request::new(Body::from(body))
+                .extract::<LimitedChars<N>, _>()
+                .await
+                .map(|LimitedChars(string)| string)
+                .map_err(|rejection| rejection.status())
+        }
+
+        assert_eq!(limited::<5>("hello").await.unwrap(), "hello");
+        assert_eq!(
+            limited::<4>("hello").await.unwrap_err(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
+        // 5 characters but 15 bytes
+        assert_eq!(limited::<5>("こんにちは").await.unwrap(), "こんにちは");
+        assert_eq!(
+            limited::<4>("こんにちは").await.unwrap_err(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
+        // 4 bytes per character
+        assert_eq!(limited::<2>("🦀🦀").await.unwrap(), "🦀🦀");
+        assert_eq!(
+            limited::<1>("🦀🦀").await.unwrap_err(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[tokio::test]
+    async fn declared_oversize_body_is_rejected_without_reading() {
+        use crate::extract::DefaultBodyLimitKind;
+        use http_body::Frame;
+        use std::{
+            pin::Pin,
+            task::{Context, Poll},
+        };
+
+        struct UnreadableBody;
+
+        impl http_body::Body for UnreadableBody {
+            type Data = Bytes;
+            type Error = Infallible;
+
+            fn poll_frame(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+                panic!("body was read")
+            }
+        }
+
+        let mut req = Request::new(Body::new(UnreadableBody));
+        req.headers_mut()
+            .insert(header::CONTENT_LENGTH, "11".parse().unwrap());
+        req.extensions_mut().insert(DefaultBodyLimitKind::Limit(10));
+
+        let rejection = Bytes::from_request(req, &()).await.unwrap_err();
+        assert!(matches!(
+            rejection,
+            BytesRejection::FailedToBufferBody(FailedToBufferBody::LengthLimitError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn chunked_oversize_body_is_rejected() {
+        use crate::extract::DefaultBodyLimitKind;
+
+        let mut req = Request::new(Body::from("hello world"));
+        req.extensions_mut().insert(DefaultBodyLimitKind::Limit(10));
+        assert!(req.headers().get(header::CONTENT_LENGTH).is_none());
+
+        let rejection = String::from_request(req, &()).await.unwrap_err();
+        assert!(matches!(
+            rejection,
+            StringRejection::FailedToBufferBody(FailedToBufferBody::LengthLimitError(_))
+        ));
+
+        let mut req = Request::new(Body::from("hello"));
+        req.headers_mut()
+            .insert(header::CONTENT_LENGTH, "5".parse().unwrap());
+        req.extensions_mut().insert(DefaultBodyLimitKind::Limit(10));
+        assert_eq!(String::from_request(req, &()).await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn bytes_and_trailers() {
+        use futures_util::stream;
+        use http_body::Frame;
+        use http_body_util::StreamBody;
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", "abc".parse().unwrap());
+
+        let body = StreamBody::new(stream::iter([
+            Ok::<_, Infallible>(Frame::data(Bytes::from("hello "))),
+            Ok(Frame::data(Bytes::from("world"))),
+            Ok(Frame::trailers(trailers)),
+        ]));
+
+        let BytesAndTrailers(bytes, trailers) = Request::new(Body::new(body))
+            .extract::<BytesAndTrailers, _>()
+            .await
+            .unwrap();
+        assert_eq!(bytes, "hello world");
+        assert_eq!(trailers["x-checksum"], "abc");
+
+        let BytesAndTrailers(bytes, trailers) = Request::new(Body::from("hello"))
+            .extract::<BytesAndTrailers, _>()
+            .await
+            .unwrap();
+        assert_eq!(bytes, "hello");
+        assert!(trailers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn safe_and_idempotent_methods() {
+        let cases = [
+            (Method::GET, true, true),
+            (Method::HEAD, true, true),
+            (Method::OPTIONS, true, true),
+            (Method::TRACE, true, true),
+            (Method::PUT, false, true),
+            (Method::DELETE, false, true),
+            (Method::POST, false, false),
+            (Method::PATCH, false, false),
+            (Method::CONNECT, false, false),
+        ];
+
+        for (method, safe, idempotent) in cases {
+            assert_eq!(
+                extract::<SafeMethod>(method.clone()).await,
+                safe,
+                "{method}"
+            );
+            assert_eq!(
+                extract::<IdempotentMethod>(method.clone()).await,
+                idempotent,
+                "{method}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn rejection_status() {
+        let mut req = Request::post("/").body(Body::empty()).unwrap();
+        let rejection = req.extract_parts::<SafeMethod>().await.unwrap_err();
+        assert_eq!(rejection.status(), http::StatusCode::METHOD_NOT_ALLOWED);
+    }
+}
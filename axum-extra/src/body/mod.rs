@@ -5,3 +5,9 @@ mod async_read_body;
 
 #[cfg(feature = "async-read-body")]
 pub use self::async_read_body::AsyncReadBody;
+
+#[cfg(feature = "tee-body")]
+mod tee_body;
+
+#[cfg(feature = "tee-body")]
+pub use self::tee_body::{Tee, TeeBody, TeeRejection, TeeSender};
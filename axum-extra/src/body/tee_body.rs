@@ -0,0 +1,263 @@
+use axum::{
+    async_trait,
+    body::{Body, Bytes, HttpBody},
+    extract::{FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use http::StatusCode;
+use http_body::{Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::{
+    fmt,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::PollSender;
+
+pin_project! {
+    /// An [`HttpBody`] that sends a copy of each data chunk to a channel while yielding it.
+    ///
+    /// The channel receives exactly the chunks that are read from the body, in the same order.
+    /// Trailers are not sent.
+    ///
+    /// # Backpressure
+    ///
+    /// Before reading a chunk from the inner body, `TeeBody` waits for capacity in the channel.
+    /// This means a slow receiver slows down whoever is reading the body, and a receiver that
+    /// never reads will stall the body once the channel's buffer is full. Choose the channel
+    /// capacity accordingly.
+    ///
+    /// If the receiver is dropped, `TeeBody` stops sending chunks and keeps yielding them as
+    /// normal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::body::{Body, Bytes};
+    /// use axum_extra::body::TeeBody;
+    /// use http_body_util::BodyExt;
+    /// use tokio::sync::mpsc;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let (tx, mut rx) = mpsc::channel::<Bytes>(16);
+    ///
+    /// let body = TeeBody::new(Body::from("hello"), tx);
+    /// let bytes = body.collect().await.unwrap().to_bytes();
+    ///
+    /// assert_eq!(bytes, "hello");
+    /// assert_eq!(rx.recv().await.unwrap(), "hello");
+    /// # }
+    /// ```
+    pub struct TeeBody<B> {
+        #[pin]
+        inner: B,
+        sender: Option<PollSender<Bytes>>,
+    }
+}
+
+impl<B> TeeBody<B> {
+    /// Create a new `TeeBody` that sends chunks read from `inner` to `sender`.
+    pub fn new(inner: B, sender: mpsc::Sender<Bytes>) -> Self {
+        Self {
+            inner,
+            sender: Some(PollSender::new(sender)),
+        }
+    }
+}
+
+impl<B> fmt::Debug for TeeBody<B>
+where
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TeeBody")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> HttpBody for TeeBody<B>
+where
+    B: HttpBody<Data = Bytes>,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+
+        if let Some(sender) = this.sender.as_mut() {
+            if ready!(sender.poll_reserve(cx)).is_err() {
+                // the receiver is gone so there is nothing to tee to
+                *this.sender = None;
+            }
+        }
+
+        let frame = ready!(this.inner.poll_frame(cx));
+
+        if let Some(sender) = this.sender.as_mut() {
+            match &frame {
+                Some(Ok(frame)) => {
+                    if let Some(data) = frame.data_ref() {
+                        if sender.send_item(data.clone()).is_err() {
+                            *this.sender = None;
+                        }
+                    }
+                }
+                Some(Err(_)) | None => {
+                    // close the channel so the receiver knows the body is done
+                    *this.sender = None;
+                }
+            }
+        }
+
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Request extension holding the channel used by the [`Tee`] extractor.
+///
+/// This is usually inserted by a middleware that creates one channel per request and hands the
+/// receiving end to an audit log or similar.
+#[derive(Debug, Clone)]
+pub struct TeeSender(pub mpsc::Sender<Bytes>);
+
+/// Extractor that runs another body extractor while sending a copy of the body to a channel.
+///
+/// The channel is taken from the [`TeeSender`] request extension and the request body is wrapped
+/// in a [`TeeBody`] before running the inner extractor, so the channel sees exactly the bytes the
+/// inner extractor consumes. See [`TeeBody`] for details about backpressure.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     body::Bytes,
+///     extract::Request,
+///     middleware::{self, Next},
+///     routing::post,
+///     Json, Router,
+/// };
+/// use axum_extra::body::{Tee, TeeSender};
+/// use serde_json::Value;
+/// use tokio::sync::mpsc;
+///
+/// async fn audit(mut req: Request, next: Next) -> axum::response::Response {
+///     let (tx, mut rx) = mpsc::channel::<Bytes>(16);
+///     req.extensions_mut().insert(TeeSender(tx));
+///     tokio::spawn(async move {
+///         while let Some(chunk) = rx.recv().await {
+///             // write `chunk` to the audit log...
+///         }
+///     });
+///     next.run(req).await
+/// }
+///
+/// async fn handler(Tee(Json(payload)): Tee<Json<Value>>) {
+///     // ...
+/// }
+///
+/// let app = Router::new()
+///     .route("/", post(handler))
+///     .layer(middleware::from_fn(audit));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tee<E>(pub E);
+
+#[async_trait]
+impl<S, E> FromRequest<S> for Tee<E>
+where
+    S: Send + Sync,
+    E: FromRequest<S>,
+{
+    type Rejection = TeeRejection<E::Rejection>;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let TeeSender(sender) = req
+            .extensions()
+            .get::<TeeSender>()
+            .cloned()
+            .ok_or(TeeRejection::MissingTeeSender)?;
+
+        let req = req.map(|body| Body::new(TeeBody::new(body, sender)));
+
+        E::from_request(req, state)
+            .await
+            .map(Self)
+            .map_err(TeeRejection::Inner)
+    }
+}
+
+axum_core::__impl_deref!(Tee);
+
+/// Rejection used for [`Tee`].
+#[derive(Debug)]
+pub enum TeeRejection<R> {
+    /// The [`TeeSender`] request extension was missing.
+    MissingTeeSender,
+    /// The inner extractor failed.
+    Inner(R),
+}
+
+impl<R> IntoResponse for TeeRejection<R>
+where
+    R: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        match self {
+            Self::MissingTeeSender => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Missing request extension: `TeeSender`",
+            )
+                .into_response(),
+            Self::Inner(inner) => inner.into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+    use std::convert::Infallible;
+
+    #[tokio::test]
+    async fn tee_receives_identical_bytes() {
+        let (tx, mut rx) = mpsc::channel::<Bytes>(16);
+
+        let chunks = vec!["foo", "bar", "", "baz"];
+        let body = Body::from_stream(stream::iter(chunks.into_iter().map(Ok::<_, Infallible>)));
+        let mut req = Request::new(body);
+        req.extensions_mut().insert(TeeSender(tx));
+
+        let Tee(bytes) = Tee::<Bytes>::from_request(req, &()).await.unwrap();
+        assert_eq!(bytes, "foobarbaz");
+
+        let mut teed = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            teed.extend_from_slice(&chunk);
+        }
+        assert_eq!(teed, b"foobarbaz");
+    }
+
+    #[tokio::test]
+    async fn missing_tee_sender() {
+        let req = Request::new(Body::from("foo"));
+        let rejection = Tee::<Bytes>::from_request(req, &()).await.unwrap_err();
+        assert!(matches!(rejection, TeeRejection::MissingTeeSender));
+    }
+}
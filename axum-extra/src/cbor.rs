@@ -0,0 +1,299 @@
+//! CBOR extractor and response.
+
+use axum::{
+    async_trait,
+    extract::{rejection::BytesRejection, FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use axum_core::__composite_rejection as composite_rejection;
+use axum_core::__define_rejection as define_rejection;
+use bytes::Bytes;
+use http::{header, HeaderMap, HeaderValue, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A [CBOR] extractor and response.
+///
+/// This can be used both as an extractor and as a response.
+///
+/// # As extractor
+///
+/// When used as an extractor, it can deserialize request bodies into some type that
+/// implements [`serde::de::DeserializeOwned`], using [`ciborium`]. The request will be rejected
+/// (and a [`CborRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: application/cbor` (or similar) header.
+/// - The body doesn't contain syntactically valid CBOR.
+/// - The body contains syntactically valid CBOR but it couldn't be deserialized into the target
+///   type.
+/// - Buffering the request body fails.
+///
+/// See [`CborRejection`] for more details.
+///
+/// The request body is buffered in memory and limited by [`DefaultBodyLimit`].
+///
+/// [`DefaultBodyLimit`]: axum::extract::DefaultBodyLimit
+///
+/// # Extractor example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::cbor::Cbor;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Reading {
+///     sensor: u32,
+///     celsius: f32,
+/// }
+///
+/// async fn record_reading(Cbor(reading): Cbor<Reading>) {
+///     // reading is `Reading`
+/// }
+///
+/// let app = Router::new().route("/readings", post(record_reading));
+/// # let _: Router = app;
+/// ```
+///
+/// # As response
+///
+/// When used as a response, it can serialize any type that implements [`serde::Serialize`] to
+/// CBOR, and will automatically set the `Content-Type: application/cbor` header.
+///
+/// [CBOR]: https://www.rfc-editor.org/rfc/rfc8949
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+#[must_use]
+pub struct Cbor<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Cbor<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = CborRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !cbor_content_type(req.headers()) {
+            return Err(MissingCborContentType.into());
+        }
+
+        let bytes = Bytes::from_request(req, state).await?;
+
+        match ciborium::de::from_reader(&*bytes) {
+            Ok(value) => Ok(Self(value)),
+            Err(err @ ciborium::de::Error::Semantic(..)) => {
+                Err(CborDataError::from_err(err).into())
+            }
+            Err(err) => Err(CborSyntaxError::from_err(err).into()),
+        }
+    }
+}
+
+axum_core::__impl_deref!(Cbor);
+
+fn cbor_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers.get(header::CONTENT_TYPE) else {
+        return false;
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return false;
+    };
+    let Ok(mime) = content_type.parse::<mime::Mime>() else {
+        return false;
+    };
+
+    mime.type_() == "application"
+        && (mime.subtype() == "cbor" || mime.suffix().map_or(false, |name| name == "cbor"))
+}
+
+impl<T> From<T> for Cbor<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+impl<T> IntoResponse for Cbor<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        let mut buf = Vec::with_capacity(128);
+        match ciborium::ser::into_writer(&self.0, &mut buf) {
+            Ok(()) => (
+                [(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/cbor"),
+                )],
+                buf,
+            )
+                .into_response(),
+            Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+        }
+    }
+}
+
+define_rejection! {
+    #[status = UNPROCESSABLE_ENTITY]
+    #[body = "Failed to deserialize the CBOR body into the target type"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    /// Rejection type for [`Cbor`].
+    ///
+    /// This rejection is used if the request body is syntactically valid CBOR but couldn't be
+    /// deserialized into the target type.
+    pub struct CborDataError(Error);
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to parse the request body as CBOR"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    /// Rejection type for [`Cbor`].
+    ///
+    /// This rejection is used if the request body didn't contain syntactically valid CBOR.
+    pub struct CborSyntaxError(Error);
+}
+
+define_rejection! {
+    #[status = UNSUPPORTED_MEDIA_TYPE]
+    #[body = "Expected request with `Content-Type: application/cbor`"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    /// Rejection type for [`Cbor`] used if the `Content-Type`
+    /// header is missing or isn't a CBOR content type.
+    pub struct MissingCborContentType;
+}
+
+composite_rejection! {
+    /// Rejection used for [`Cbor`].
+    ///
+    /// Contains one variant for each way the [`Cbor`] extractor
+    /// can fail.
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    pub enum CborRejection {
+        CborDataError,
+        CborSyntaxError,
+        MissingCborContentType,
+        BytesRejection,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize)]
+    struct Reading {
+        sensor: u32,
+        celsius: f32,
+    }
+
+    fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut buf = Vec::new();
+        ciborium::ser::into_writer(value, &mut buf).unwrap();
+        buf
+    }
+
+    #[tokio::test]
+    async fn decode_body() {
+        let app = Router::new().route(
+            "/",
+            post(|Cbor(reading): Cbor<Reading>| async move { reading.sensor.to_string() }),
+        );
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .header("content-type", "application/cbor")
+            .body(encode(&Reading {
+                sensor: 7,
+                celsius: 21.5,
+            }))
+            .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "7");
+    }
+
+    #[tokio::test]
+    async fn malformed_body() {
+        let app = Router::new().route(
+            "/",
+            post(|Cbor(reading): Cbor<Reading>| async move { reading.sensor.to_string() }),
+        );
+
+        let client = TestClient::new(app);
+
+        // a map header announcing two entries followed by nothing
+        let res = client
+            .post("/")
+            .header("content-type", "application/cbor")
+            .body(vec![0xa2])
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        // valid CBOR of the wrong shape
+        let res = client
+            .post("/")
+            .header("content-type", "application/cbor")
+            .body(encode(&"not a reading"))
+            .await;
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn requires_cbor_content_type() {
+        let body = encode(&Reading {
+            sensor: 7,
+            celsius: 21.5,
+        });
+        let app = Router::new().route(
+            "/",
+            post(|Cbor(reading): Cbor<Reading>| async move { reading.sensor.to_string() }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client.post("/").body(body.clone()).await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/json")
+            .body(body.clone())
+            .await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/senml+cbor")
+            .body(body)
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn encode_body() {
+        let app = Router::new().route(
+            "/",
+            post(|Cbor(reading): Cbor<Reading>| async move { Cbor(reading) }),
+        );
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .header("content-type", "application/cbor")
+            .body(encode(&Reading {
+                sensor: 7,
+                celsius: 21.5,
+            }))
+            .await;
+
+        assert_eq!(res.headers()["content-type"], "application/cbor");
+        let reading: Reading = ciborium::de::from_reader(&*res.bytes().await).unwrap();
+        assert_eq!(reading.sensor, 7);
+        assert_eq!(reading.celsius, 21.5);
+    }
+}
@@ -0,0 +1,298 @@
+use axum::{async_trait, extract::FromRequestParts};
+use axum_core::__define_rejection as define_rejection;
+use http::{header::ACCEPT_ENCODING, request::Parts, HeaderMap};
+use std::fmt;
+
+/// Extractor that parses the `Accept-Encoding` header.
+///
+/// Use [`AcceptEncoding::prefers`] to pick the best encoding the server supports.
+///
+/// If the header is missing only the `identity` encoding is considered acceptable. If the header
+/// is malformed the request is rejected with `400 Bad Request`. Encodings other than the ones in
+/// [`Encoding`] are ignored.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{AcceptEncoding, Encoding};
+///
+/// async fn handler(accept_encoding: AcceptEncoding) {
+///     match accept_encoding.prefers(&[Encoding::Br, Encoding::Gzip, Encoding::Identity]) {
+///         Some(Encoding::Br) => { /* compress with brotli */ }
+///         Some(Encoding::Gzip) => { /* compress with gzip */ }
+///         Some(_) => { /* don't compress */ }
+///         None => { /* no acceptable encoding, respond with `406 Not Acceptable` */ }
+///     }
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AcceptEncoding {
+    encodings: Vec<(Encoding, QValue)>,
+    wildcard: Option<QValue>,
+}
+
+/// A content coding that can appear in the `Accept-Encoding` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// The `gzip` encoding.
+    Gzip,
+    /// The `deflate` encoding.
+    Deflate,
+    /// The `br` (brotli) encoding.
+    Br,
+    /// The `zstd` encoding.
+    Zstd,
+    /// The `identity` encoding, meaning no encoding.
+    Identity,
+}
+
+impl Encoding {
+    /// The name of the encoding, as used in the `Accept-Encoding` and `Content-Encoding` headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+            Self::Br => "br",
+            Self::Zstd => "zstd",
+            Self::Identity => "identity",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        [
+            Self::Gzip,
+            Self::Deflate,
+            Self::Br,
+            Self::Zstd,
+            Self::Identity,
+        ]
+        .into_iter()
+        .find(|encoding| name.eq_ignore_ascii_case(encoding.as_str()))
+        .or_else(|| name.eq_ignore_ascii_case("x-gzip").then_some(Self::Gzip))
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A quality value in thousandths, so `q=0.5` is `500`.
+type QValue = u16;
+
+impl AcceptEncoding {
+    /// Parse the `Accept-Encoding` headers in `headers`.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, InvalidAcceptEncoding> {
+        let values = headers
+            .get_all(ACCEPT_ENCODING)
+            .iter()
+            .map(|value| value.to_str())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| InvalidAcceptEncoding)?;
+
+        Self::parse(&values).ok_or(InvalidAcceptEncoding)
+    }
+
+    /// Get the quality value the client assigned to `encoding`, between `0.0` and `1.0`.
+    ///
+    /// `0.0` means the encoding is not acceptable. The `identity` encoding has a quality of `1.0`
+    /// unless the header refuses it.
+    pub fn quality(&self, encoding: Encoding) -> f32 {
+        let (q, _) = self.q(encoding);
+        f32::from(q) / 1000.0
+    }
+
+    /// Pick the encoding from `available` the client prefers the most.
+    ///
+    /// Encodings the client considers equally good are resolved in the order of `available`, so
+    /// list the encodings the server prefers first. The `identity` encoding is only picked over
+    /// the encodings the header lists if it's listed itself. Returns `None` if none of the
+    /// encodings are acceptable.
+    pub fn prefers(&self, available: &[Encoding]) -> Option<Encoding> {
+        let mut best: Option<(Encoding, (bool, QValue))> = None;
+
+        for &encoding in available {
+            let (q, listed) = self.q(encoding);
+            let rank = (listed, q);
+            if q > 0 && best.map_or(true, |(_, best_rank)| rank > best_rank) {
+                best = Some((encoding, rank));
+            }
+        }
+
+        best.map(|(encoding, _)| encoding)
+    }
+
+    /// Returns the quality value of `encoding` and whether the header lists it, by name or with
+    /// `*`.
+    fn q(&self, encoding: Encoding) -> (QValue, bool) {
+        if let Some((_, q)) = self.encodings.iter().find(|(e, _)| *e == encoding) {
+            return (*q, true);
+        }
+
+        match (encoding, self.wildcard) {
+            (_, Some(q)) => (q, true),
+            // identity is always acceptable unless explicitly refused
+            (Encoding::Identity, None) => (1000, false),
+            (_, None) => (0, false),
+        }
+    }
+
+    fn parse(values: &[&str]) -> Option<Self> {
+        let mut accept = Self::default();
+
+        for item in values.iter().flat_map(|value| value.split(',')) {
+            let item = item.trim();
+            if item.is_empty() {
+                continue;
+            }
+
+            let mut params = item.split(';');
+            let name = params.next()?.trim();
+            let mut q = 1000;
+            for param in params {
+                let (key, value) = param.trim().split_once('=')?;
+                if key.trim().eq_ignore_ascii_case("q") {
+                    q = parse_q(value.trim())?;
+                }
+            }
+
+            if name == "*" {
+                accept.wildcard = Some(q);
+            } else if !is_token(name) {
+                return None;
+            } else if let Some(encoding) = Encoding::parse(name) {
+                accept.encodings.push((encoding, q));
+            }
+        }
+
+        Some(accept)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AcceptEncoding
+where
+    S: Send + Sync,
+{
+    type Rejection = InvalidAcceptEncoding;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::from_headers(&parts.headers)
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to parse `Accept-Encoding` header"]
+    /// Rejection type for [`AcceptEncoding`] used if the header is malformed.
+    pub struct InvalidAcceptEncoding;
+}
+
+pub(super) fn parse_q(value: &str) -> Option<QValue> {
+    let (int, frac) = value.split_once('.').unwrap_or((value, ""));
+    if frac.len() > 3 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let frac = format!("{frac:0<3}").parse::<QValue>().ok()?;
+    match int {
+        "0" => Some(frac),
+        "1" if frac == 0 => Some(1000),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_token(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> AcceptEncoding {
+        AcceptEncoding::parse(&[value]).unwrap()
+    }
+
+    #[test]
+    fn q_values() {
+        let accept = parse("gzip;q=0.5, br;q=1.0, deflate;q=0");
+        assert_eq!(
+            accept.prefers(&[Encoding::Gzip, Encoding::Br, Encoding::Deflate]),
+            Some(Encoding::Br)
+        );
+        assert_eq!(
+            accept.prefers(&[Encoding::Gzip, Encoding::Deflate]),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(accept.prefers(&[Encoding::Deflate]), None);
+        assert_eq!(accept.quality(Encoding::Gzip), 0.5);
+    }
+
+    #[test]
+    fn wildcard() {
+        let accept = parse("gzip;q=0.2, *;q=0.8");
+        assert_eq!(
+            accept.prefers(&[Encoding::Gzip, Encoding::Zstd]),
+            Some(Encoding::Zstd)
+        );
+        assert_eq!(
+            accept.prefers(&[Encoding::Identity]),
+            Some(Encoding::Identity)
+        );
+
+        let accept = parse("*");
+        assert_eq!(accept.prefers(&[Encoding::Br]), Some(Encoding::Br));
+    }
+
+    #[test]
+    fn identity_refused() {
+        let accept = parse("gzip, identity;q=0");
+        assert_eq!(accept.prefers(&[Encoding::Identity]), None);
+        assert_eq!(
+            accept.prefers(&[Encoding::Identity, Encoding::Gzip]),
+            Some(Encoding::Gzip)
+        );
+
+        let accept = parse("*;q=0");
+        assert_eq!(accept.prefers(&[Encoding::Identity, Encoding::Gzip]), None);
+    }
+
+    #[test]
+    fn missing_header_means_identity() {
+        let accept = AcceptEncoding::parse(&[]).unwrap();
+        assert_eq!(
+            accept.prefers(&[Encoding::Gzip, Encoding::Identity]),
+            Some(Encoding::Identity)
+        );
+        assert_eq!(accept.prefers(&[Encoding::Gzip]), None);
+        assert_eq!(accept.quality(Encoding::Identity), 1.0);
+        assert_eq!(accept.quality(Encoding::Gzip), 0.0);
+    }
+
+    #[test]
+    fn unlisted_identity() {
+        let accept = parse("br;q=0.5");
+        assert_eq!(accept.quality(Encoding::Identity), 1.0);
+        assert_eq!(
+            accept.prefers(&[Encoding::Identity, Encoding::Br]),
+            Some(Encoding::Br)
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        for value in ["gzip;q=2", "gzip;q=0.1234", "gzip;q", "g zip", "gzip;q=abc"] {
+            assert!(AcceptEncoding::parse(&[value]).is_none(), "{value}");
+        }
+    }
+}
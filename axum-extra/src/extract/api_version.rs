@@ -0,0 +1,207 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    response::{IntoResponse, Response},
+};
+use http::{header::ACCEPT, request::Parts, StatusCode};
+use std::fmt;
+
+/// Extractor that resolves the version of the API requested by the client.
+///
+/// The version is looked up in the following places, in order, and the first one found is used:
+///
+/// 1. A vendor media type in the `Accept` header whose subtype ends with `.v<version>`, for
+///    example `application/vnd.myapp.v2+json`.
+/// 2. The `X-Api-Version` header.
+/// 3. The `version` query parameter.
+///
+/// If none of them are present `DEFAULT` (1 unless specified) is used. If the `X-Api-Version`
+/// header or the `version` query parameter isn't a valid number the request is rejected with
+/// `400 Bad Request`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::ApiVersion;
+///
+/// async fn list_users(ApiVersion(version): ApiVersion) {
+///     match version {
+///         1 => { /* ... */ }
+///         _ => { /* ... */ }
+///     }
+/// }
+///
+/// let app = Router::new().route("/users", get(list_users));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ApiVersion<const DEFAULT: u32 = 1>(pub u32);
+
+#[async_trait]
+impl<S, const DEFAULT: u32> FromRequestParts<S> for ApiVersion<DEFAULT>
+where
+    S: Send + Sync,
+{
+    type Rejection = InvalidApiVersion;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let from_accept = parts
+            .headers
+            .get_all(ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .find_map(parse_media_type_version);
+        if let Some(version) = from_accept {
+            return Ok(Self(version));
+        }
+
+        if let Some(value) = parts.headers.get("x-api-version") {
+            return value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .map(Self)
+                .ok_or(InvalidApiVersion(Source::Header));
+        }
+
+        let from_query = parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == "version");
+        if let Some((_, value)) = from_query {
+            return value
+                .parse()
+                .map(Self)
+                .map_err(|_| InvalidApiVersion(Source::Query));
+        }
+
+        Ok(Self(DEFAULT))
+    }
+}
+
+/// Parse the version from a media type like `application/vnd.myapp.v2+json`.
+fn parse_media_type_version(media_type: &str) -> Option<u32> {
+    let essence = media_type.split(';').next()?.trim();
+    let (_, subtype) = essence.split_once('/')?;
+    let subtype = subtype.strip_prefix("vnd.")?;
+    let subtype = subtype.split('+').next()?;
+    let (_, version) = subtype.rsplit_once('.')?;
+    let version = version.strip_prefix('v')?;
+    if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    version.parse().ok()
+}
+
+/// Rejection used for [`ApiVersion`].
+#[derive(Debug)]
+pub struct InvalidApiVersion(Source);
+
+#[derive(Debug)]
+enum Source {
+    Header,
+    Query,
+}
+
+impl IntoResponse for InvalidApiVersion {
+    fn into_response(self) -> Response {
+        let body = self.to_string();
+        let status = StatusCode::BAD_REQUEST;
+        axum_core::__log_rejection!(rejection_type = Self, body_text = body, status = status,);
+        (status, body).into_response()
+    }
+}
+
+impl fmt::Display for InvalidApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Source::Header => write!(f, "Invalid `X-Api-Version` header"),
+            Source::Query => write!(f, "Invalid `version` query parameter"),
+        }
+    }
+}
+
+impl std::error::Error for InvalidApiVersion {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    #[tokio::test]
+    async fn sources() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|ApiVersion(version): ApiVersion| async move { version.to_string() }),
+            )
+            .route(
+                "/default",
+                get(|ApiVersion(version): ApiVersion<3>| async move { version.to_string() }),
+            );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("accept", "application/vnd.myapp.v2+json")
+            .await;
+        assert_eq!(res.text().await, "2");
+
+        let res = client.get("/").header("x-api-version", "4").await;
+        assert_eq!(res.text().await, "4");
+
+        let res = client.get("/?page=1&version=5").await;
+        assert_eq!(res.text().await, "5");
+
+        let res = client.get("/").header("accept", "application/json").await;
+        assert_eq!(res.text().await, "1");
+
+        let res = client.get("/default").await;
+        assert_eq!(res.text().await, "3");
+    }
+
+    #[tokio::test]
+    async fn precedence() {
+        let app = Router::new().route(
+            "/",
+            get(|ApiVersion(version): ApiVersion| async move { version.to_string() }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/?version=5")
+            .header("accept", "text/html, application/vnd.myapp.v2+json;q=0.9")
+            .header("x-api-version", "4")
+            .await;
+        assert_eq!(res.text().await, "2");
+
+        let res = client.get("/?version=5").header("x-api-version", "4").await;
+        assert_eq!(res.text().await, "4");
+    }
+
+    #[tokio::test]
+    async fn invalid() {
+        let app = Router::new().route(
+            "/",
+            get(|ApiVersion(version): ApiVersion| async move { version.to_string() }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("x-api-version", "two").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "Invalid `X-Api-Version` header");
+
+        let res = client.get("/?version=-1").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "Invalid `version` query parameter");
+    }
+}
@@ -0,0 +1,198 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use http::{header, request::Parts, StatusCode};
+use std::fmt;
+
+/// Extractor that gets the credentials from an `Authorization: Basic <credentials>` header.
+///
+/// The credentials are base64 decoded and split on the first colon, so passwords may contain
+/// colons but usernames may not.
+///
+/// The `Basic` scheme is matched case-insensitively. If the header is missing, doesn't use the
+/// `Basic` scheme, or the credentials aren't valid base64 encoded UTF-8 containing a colon, the
+/// request is rejected with `401 Unauthorized` and a `WWW-Authenticate: Basic` header.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::BasicAuth;
+///
+/// async fn handler(BasicAuth { username, password }: BasicAuth) {
+///     // validate `username` and `password`...
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct BasicAuth {
+    /// The username.
+    pub username: String,
+    /// The password.
+    pub password: String,
+}
+
+impl fmt::Debug for BasicAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BasicAuth")
+            .field("username", &self.username)
+            .field("password", &"[redacted]")
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for BasicAuth
+where
+    S: Send + Sync,
+{
+    type Rejection = BasicAuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .ok_or(BasicAuthRejection::Missing)?
+            .to_str()
+            .map_err(|_| BasicAuthRejection::Malformed)?;
+
+        parse_basic(value).ok_or(BasicAuthRejection::Malformed)
+    }
+}
+
+fn parse_basic(value: &str) -> Option<BasicAuth> {
+    const SCHEME: &str = "Basic ";
+
+    if value.len() <= SCHEME.len() || !value.is_char_boundary(SCHEME.len()) {
+        return None;
+    }
+
+    let (scheme, credentials) = value.split_at(SCHEME.len());
+    if !scheme.eq_ignore_ascii_case(SCHEME) {
+        return None;
+    }
+
+    let decoded = STANDARD.decode(credentials.trim()).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+
+    Some(BasicAuth {
+        username: username.to_owned(),
+        password: password.to_owned(),
+    })
+}
+
+/// Rejection used for [`BasicAuth`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BasicAuthRejection {
+    /// The `Authorization` header was missing.
+    Missing,
+    /// The `Authorization` header didn't contain valid basic credentials.
+    Malformed,
+}
+
+impl IntoResponse for BasicAuthRejection {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Basic")],
+            self.to_string(),
+        )
+            .into_response()
+    }
+}
+
+impl fmt::Display for BasicAuthRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "Missing `Authorization` header"),
+            Self::Malformed => write!(
+                f,
+                "`Authorization` header didn't contain valid basic credentials"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BasicAuthRejection {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    fn basic(credentials: &str) -> String {
+        format!("Basic {}", STANDARD.encode(credentials))
+    }
+
+    #[tokio::test]
+    async fn valid_credentials() {
+        let app = Router::new().route(
+            "/",
+            get(|BasicAuth { username, password }: BasicAuth| async move {
+                format!("{username}/{password}")
+            }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("authorization", basic("user:pass"))
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "user/pass");
+    }
+
+    #[tokio::test]
+    async fn password_with_colons() {
+        let app = Router::new().route(
+            "/",
+            get(|BasicAuth { username, password }: BasicAuth| async move {
+                format!("{username}/{password}")
+            }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("authorization", basic("user:pa:ss:"))
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "user/pa:ss:");
+    }
+
+    #[tokio::test]
+    async fn missing_header() {
+        let app = Router::new().route("/", get(|_: BasicAuth| async {}));
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.headers()["www-authenticate"], "Basic");
+        assert_eq!(res.text().await, "Missing `Authorization` header");
+    }
+
+    #[tokio::test]
+    async fn invalid_base64() {
+        let app = Router::new().route("/", get(|_: BasicAuth| async {}));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("authorization", "Basic not-base64!")
+            .await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.headers()["www-authenticate"], "Basic");
+    }
+}
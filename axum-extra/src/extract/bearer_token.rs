@@ -0,0 +1,158 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    response::{IntoResponse, Response},
+};
+use http::{header, request::Parts, StatusCode};
+use std::fmt;
+
+/// Extractor that gets the token from an `Authorization: Bearer <token>` header.
+///
+/// The `Bearer` scheme is matched case-insensitively. If the header is missing or doesn't use the
+/// `Bearer` scheme the request is rejected with `401 Unauthorized` and a
+/// `WWW-Authenticate: Bearer` header.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::BearerToken;
+///
+/// async fn handler(BearerToken(token): BearerToken) {
+///     // validate `token`...
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BearerToken(pub String);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for BearerToken
+where
+    S: Send + Sync,
+{
+    type Rejection = BearerTokenRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .ok_or(BearerTokenRejection::Missing)?
+            .to_str()
+            .map_err(|_| BearerTokenRejection::Malformed)?;
+
+        parse_bearer(value)
+            .map(|token| Self(token.to_owned()))
+            .ok_or(BearerTokenRejection::Malformed)
+    }
+}
+
+axum_core::__impl_deref!(BearerToken: String);
+
+fn parse_bearer(value: &str) -> Option<&str> {
+    const SCHEME: &str = "Bearer ";
+
+    if value.len() <= SCHEME.len() || !value.is_char_boundary(SCHEME.len()) {
+        return None;
+    }
+
+    let (scheme, token) = value.split_at(SCHEME.len());
+    if !scheme.eq_ignore_ascii_case(SCHEME) {
+        return None;
+    }
+
+    let token = token.trim();
+    if token.is_empty() {
+        None
+    } else {
+        Some(token)
+    }
+}
+
+/// Rejection used for [`BearerToken`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BearerTokenRejection {
+    /// The `Authorization` header was missing.
+    Missing,
+    /// The `Authorization` header didn't contain a bearer token.
+    Malformed,
+}
+
+impl IntoResponse for BearerTokenRejection {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            self.to_string(),
+        )
+            .into_response()
+    }
+}
+
+impl fmt::Display for BearerTokenRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "Missing `Authorization` header"),
+            Self::Malformed => write!(f, "`Authorization` header didn't contain a bearer token"),
+        }
+    }
+}
+
+impl std::error::Error for BearerTokenRejection {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    #[tokio::test]
+    async fn valid_bearer() {
+        let app = Router::new().route("/", get(|BearerToken(token): BearerToken| async { token }));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("authorization", "Bearer abc123")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "abc123");
+
+        let res = client
+            .get("/")
+            .header("authorization", "bearer abc123")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "abc123");
+    }
+
+    #[tokio::test]
+    async fn wrong_scheme() {
+        let app = Router::new().route("/", get(|_: BearerToken| async {}));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("authorization", "Basic dXNlcjpwYXNz")
+            .await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.headers()["www-authenticate"], "Bearer");
+    }
+
+    #[tokio::test]
+    async fn missing_header() {
+        let app = Router::new().route("/", get(|_: BearerToken| async {}));
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.headers()["www-authenticate"], "Bearer");
+        assert_eq!(res.text().await, "Missing `Authorization` header");
+    }
+}
@@ -0,0 +1,240 @@
+use super::accept_encoding::{is_token, parse_q};
+use axum::{async_trait, extract::FromRequestParts};
+use axum_core::__define_rejection as define_rejection;
+use http::{header::ACCEPT_CHARSET, request::Parts};
+
+/// Extractor that parses the `Accept-Charset` header.
+///
+/// Use [`Charset::negotiate`] to pick the best charset the server supports.
+///
+/// If the header is missing `utf-8` is preferred, though other charsets are still acceptable.
+/// If the header is malformed the request is rejected with `400 Bad Request`. Charsets are
+/// compared case-insensitively.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{http::header, response::IntoResponse, routing::get, Router};
+/// use axum_extra::extract::Charset;
+///
+/// async fn handler(charset: Charset) -> impl IntoResponse {
+///     let charset = charset
+///         .negotiate(&["utf-8", "iso-8859-1"])
+///         .unwrap_or_else(|| "utf-8".to_owned());
+///
+///     (
+///         [(header::CONTENT_TYPE, format!("text/plain; charset={charset}"))],
+///         // encode the body with `charset`
+///         "hello",
+///     )
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Charset {
+    // sorted by descending q-value
+    charsets: Vec<(String, QValue)>,
+    wildcard: Option<QValue>,
+}
+
+/// A quality value in thousandths, so `q=0.5` is `500`.
+type QValue = u16;
+
+impl Charset {
+    /// Get the quality value the client assigned to `charset`, between `0.0` and `1.0`.
+    ///
+    /// `0.0` means the charset is not acceptable.
+    pub fn quality(&self, charset: &str) -> f32 {
+        f32::from(self.q(charset)) / 1000.0
+    }
+
+    /// Iterate over the acceptable charsets the client listed, most preferred first.
+    ///
+    /// This doesn't include `*`.
+    pub fn preferences(&self) -> impl Iterator<Item = &str> {
+        self.charsets
+            .iter()
+            .filter(|(_, q)| *q > 0)
+            .map(|(charset, _)| charset.as_str())
+    }
+
+    /// Pick the charset from `available` the client prefers the most.
+    ///
+    /// Charsets the client considers equally good are resolved in the order of `available`, so
+    /// list the charsets the server prefers first. Returns `None` if none of the charsets are
+    /// acceptable.
+    pub fn negotiate(&self, available: &[&str]) -> Option<String> {
+        let mut best: Option<(&str, QValue)> = None;
+
+        for &charset in available {
+            let q = self.q(charset);
+            if q > 0 && best.map_or(true, |(_, best_q)| q > best_q) {
+                best = Some((charset, q));
+            }
+        }
+
+        best.map(|(charset, _)| charset.to_owned())
+    }
+
+    fn q(&self, charset: &str) -> QValue {
+        self.charsets
+            .iter()
+            .find(|(c, _)| c.eq_ignore_ascii_case(charset))
+            .map(|(_, q)| *q)
+            .or(self.wildcard)
+            .unwrap_or(0)
+    }
+
+    fn parse(values: &[&str]) -> Option<Self> {
+        if values.is_empty() {
+            return Some(Self {
+                charsets: vec![("utf-8".to_owned(), 1000)],
+                wildcard: Some(1),
+            });
+        }
+
+        let mut accept = Self {
+            charsets: Vec::new(),
+            wildcard: None,
+        };
+
+        for item in values.iter().flat_map(|value| value.split(',')) {
+            let item = item.trim();
+            if item.is_empty() {
+                continue;
+            }
+
+            let mut params = item.split(';');
+            let name = params.next()?.trim();
+            let mut q = 1000;
+            for param in params {
+                let (key, value) = param.trim().split_once('=')?;
+                if key.trim().eq_ignore_ascii_case("q") {
+                    q = parse_q(value.trim())?;
+                }
+            }
+
+            if name == "*" {
+                accept.wildcard = Some(q);
+            } else if is_token(name) {
+                accept.charsets.push((name.to_owned(), q));
+            } else {
+                return None;
+            }
+        }
+
+        // stable, so charsets with equal q-values keep their order
+        accept.charsets.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        Some(accept)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Charset
+where
+    S: Send + Sync,
+{
+    type Rejection = InvalidAcceptCharset;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let values = parts
+            .headers
+            .get_all(ACCEPT_CHARSET)
+            .iter()
+            .map(|value| value.to_str())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| InvalidAcceptCharset)?;
+
+        Self::parse(&values).ok_or(InvalidAcceptCharset)
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to parse `Accept-Charset` header"]
+    /// Rejection type for [`Charset`] used if the header is malformed.
+    pub struct InvalidAcceptCharset;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use http::StatusCode;
+
+    fn parse(value: &str) -> Charset {
+        Charset::parse(&[value]).unwrap()
+    }
+
+    #[test]
+    fn explicit_preference() {
+        let charset = parse("iso-8859-1;q=0.5, UTF-8, windows-1252;q=0");
+        assert_eq!(
+            charset.negotiate(&["iso-8859-1", "utf-8"]),
+            Some("utf-8".to_owned())
+        );
+        assert_eq!(
+            charset.negotiate(&["iso-8859-1", "windows-1252"]),
+            Some("iso-8859-1".to_owned())
+        );
+        assert_eq!(charset.negotiate(&["windows-1252", "shift_jis"]), None);
+        assert_eq!(
+            charset.preferences().collect::<Vec<_>>(),
+            ["UTF-8", "iso-8859-1"]
+        );
+        assert_eq!(charset.quality("ISO-8859-1"), 0.5);
+    }
+
+    #[test]
+    fn wildcard() {
+        let charset = parse("iso-8859-1, *;q=0.1");
+        assert_eq!(
+            charset.negotiate(&["utf-8", "iso-8859-1"]),
+            Some("iso-8859-1".to_owned())
+        );
+        assert_eq!(charset.negotiate(&["utf-8"]), Some("utf-8".to_owned()));
+
+        let charset = parse("*;q=0");
+        assert_eq!(charset.negotiate(&["utf-8"]), None);
+    }
+
+    #[test]
+    fn missing_header_prefers_utf8() {
+        let charset = Charset::parse(&[]).unwrap();
+        assert_eq!(
+            charset.negotiate(&["iso-8859-1", "utf-8"]),
+            Some("utf-8".to_owned())
+        );
+        assert_eq!(
+            charset.negotiate(&["iso-8859-1"]),
+            Some("iso-8859-1".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn extractor() {
+        let app = Router::new().route(
+            "/",
+            get(|charset: Charset| async move {
+                charset
+                    .negotiate(&["utf-8", "iso-8859-1"])
+                    .unwrap_or_default()
+            }),
+        );
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("accept-charset", "iso-8859-1").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "iso-8859-1");
+
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "utf-8");
+
+        let res = client.get("/").header("accept-charset", "utf-8;q=2").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+}
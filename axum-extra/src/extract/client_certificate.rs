@@ -0,0 +1,261 @@
+use axum::{async_trait, extract::FromRequestParts};
+use axum_core::__define_rejection as define_rejection;
+use bytes::Bytes;
+use http::request::Parts;
+
+/// Request extension holding the certificate chain presented by the client.
+///
+/// This is meant to be inserted by the TLS layer when a client certificate was negotiated, for
+/// example from [`rustls::ServerConnection::peer_certificates`]. Each certificate is DER-encoded
+/// and the leaf certificate comes first.
+///
+/// [`rustls::ServerConnection::peer_certificates`]: https://docs.rs/rustls/latest/rustls/server/struct.ServerConnection.html#method.peer_certificates
+#[derive(Debug, Clone, Default)]
+pub struct PeerCertificates(pub Vec<Bytes>);
+
+/// Extractor for the certificate the client presented during a mutual TLS handshake.
+///
+/// This reads the [`PeerCertificates`] request extension, which must be inserted by the TLS
+/// layer, similarly to how [`ConnectInfo`] is provided by the server. If the extension is missing
+/// or empty, meaning mutual TLS wasn't negotiated, the request is rejected with
+/// `403 Forbidden`.
+///
+/// Note that this extractor doesn't verify the certificate. That is the job of the TLS layer.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::ClientCertificate;
+///
+/// async fn handler(cert: ClientCertificate) -> String {
+///     match cert.common_name() {
+///         Some(name) => format!("Hello, {name}"),
+///         None => "Hello, anonymous client".to_owned(),
+///     }
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+///
+/// [`ConnectInfo`]: axum::extract::ConnectInfo
+#[derive(Debug, Clone)]
+pub struct ClientCertificate {
+    der: Bytes,
+    common_name: Option<String>,
+}
+
+impl ClientCertificate {
+    /// The DER-encoded leaf certificate.
+    pub fn der(&self) -> &Bytes {
+        &self.der
+    }
+
+    /// The common name (CN) of the certificate's subject, if it has one.
+    pub fn common_name(&self) -> Option<&str> {
+        self.common_name.as_deref()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientCertificate
+where
+    S: Send + Sync,
+{
+    type Rejection = NoClientCertificate;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let der = parts
+            .extensions
+            .get::<PeerCertificates>()
+            .and_then(|PeerCertificates(chain)| chain.first())
+            .cloned()
+            .ok_or(NoClientCertificate)?;
+
+        let common_name = subject_common_name(&der);
+
+        Ok(Self { der, common_name })
+    }
+}
+
+define_rejection! {
+    #[status = FORBIDDEN]
+    #[body = "No client certificate was presented"]
+    /// Rejection type for [`ClientCertificate`] used if the client didn't present a certificate.
+    pub struct NoClientCertificate;
+}
+
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+const OBJECT_IDENTIFIER: u8 = 0x06;
+const EXPLICIT_VERSION: u8 = 0xa0;
+
+// 2.5.4.3
+const COMMON_NAME_OID: &[u8] = &[0x55, 0x04, 0x03];
+
+/// Find the subject common name in a DER-encoded X.509 certificate.
+fn subject_common_name(der: &[u8]) -> Option<String> {
+    let mut input = der;
+    let mut cert = expect(&mut input, SEQUENCE)?;
+    let mut tbs = expect(&mut cert, SEQUENCE)?;
+
+    if tbs.first() == Some(&EXPLICIT_VERSION) {
+        read_tlv(&mut tbs)?;
+    }
+
+    // skip `serialNumber`, `signature`, `issuer`, and `validity`
+    for _ in 0..4 {
+        read_tlv(&mut tbs)?;
+    }
+
+    let mut subject = expect(&mut tbs, SEQUENCE)?;
+    while !subject.is_empty() {
+        let mut rdn = expect(&mut subject, SET)?;
+        while !rdn.is_empty() {
+            let mut attribute = expect(&mut rdn, SEQUENCE)?;
+            let oid = expect(&mut attribute, OBJECT_IDENTIFIER)?;
+            if oid != COMMON_NAME_OID {
+                continue;
+            }
+
+            let (tag, value) = read_tlv(&mut attribute)?;
+            return match tag {
+                // UTF8String, PrintableString, TeletexString, and IA5String
+                0x0c | 0x13 | 0x14 | 0x16 => String::from_utf8(value.to_vec()).ok(),
+                _ => None,
+            };
+        }
+    }
+
+    None
+}
+
+fn expect<'a>(input: &mut &'a [u8], expected_tag: u8) -> Option<&'a [u8]> {
+    let (tag, value) = read_tlv(input)?;
+    (tag == expected_tag).then_some(value)
+}
+
+/// Read a single DER tag-length-value from the front of `input`.
+fn read_tlv<'a>(input: &mut &'a [u8]) -> Option<(u8, &'a [u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&len, mut rest) = rest.split_first()?;
+
+    let len = if len & 0x80 == 0 {
+        usize::from(len)
+    } else {
+        let num_bytes = usize::from(len & 0x7f);
+        if num_bytes == 0 || num_bytes > std::mem::size_of::<usize>() || rest.len() < num_bytes {
+            return None;
+        }
+        let (len_bytes, after) = rest.split_at(num_bytes);
+        rest = after;
+        len_bytes
+            .iter()
+            .fold(0, |len, &byte| (len << 8) | usize::from(byte))
+    };
+
+    if rest.len() < len {
+        return None;
+    }
+
+    let (value, rest) = rest.split_at(len);
+    *input = rest;
+    Some((tag, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use http::StatusCode;
+
+    fn tlv(tag: u8, content: &[&[u8]]) -> Vec<u8> {
+        let content = content.concat();
+        let mut out = vec![tag];
+        if content.len() < 0x80 {
+            out.push(content.len() as u8);
+        } else {
+            out.extend([0x82, (content.len() >> 8) as u8, content.len() as u8]);
+        }
+        out.extend(content);
+        out
+    }
+
+    fn name(attributes: &[(&[u8], &str)]) -> Vec<u8> {
+        let rdns = attributes
+            .iter()
+            .map(|&(oid, value)| {
+                tlv(
+                    SET,
+                    &[&tlv(
+                        SEQUENCE,
+                        &[
+                            &tlv(OBJECT_IDENTIFIER, &[oid]),
+                            &tlv(0x0c, &[value.as_bytes()]),
+                        ],
+                    )],
+                )
+            })
+            .collect::<Vec<_>>();
+        let rdns = rdns.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        tlv(SEQUENCE, &rdns)
+    }
+
+    fn fake_cert(subject: &[(&[u8], &str)]) -> Bytes {
+        // sha256WithRSAEncryption
+        let algorithm = tlv(
+            SEQUENCE,
+            &[&tlv(
+                OBJECT_IDENTIFIER,
+                &[&[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b]],
+            )],
+        );
+        let tbs = tlv(
+            SEQUENCE,
+            &[
+                &tlv(EXPLICIT_VERSION, &[&tlv(0x02, &[&[2]])]),
+                &tlv(0x02, &[&[1]]),
+                &algorithm,
+                &name(&[(COMMON_NAME_OID, "Test CA")]),
+                &tlv(SEQUENCE, &[]),
+                &name(subject),
+                // padding to exercise the long form length encoding
+                &tlv(0x04, &[&[0; 200]]),
+            ],
+        );
+        tlv(SEQUENCE, &[&tbs, &algorithm, &tlv(0x03, &[&[0]])]).into()
+    }
+
+    #[tokio::test]
+    async fn extracts_subject_common_name() {
+        // 2.5.4.10, organizationName
+        let organization: &[u8] = &[0x55, 0x04, 0x0a];
+        let der = fake_cert(&[
+            (organization, "Acme"),
+            (COMMON_NAME_OID, "client.example.com"),
+        ]);
+
+        let mut req = http::Request::new(());
+        req.extensions_mut()
+            .insert(PeerCertificates(vec![der.clone()]));
+        let (mut parts, ()) = req.into_parts();
+
+        let cert = ClientCertificate::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+
+        assert_eq!(cert.der(), &der);
+        assert_eq!(cert.common_name(), Some("client.example.com"));
+    }
+
+    #[tokio::test]
+    async fn missing_certificate() {
+        let app = Router::new().route("/", get(|_: ClientCertificate| async {}));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+}
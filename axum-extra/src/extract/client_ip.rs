@@ -0,0 +1,320 @@
+use super::forwarded::{parse_forwarded, ForwardedNode};
+use axum::{
+    async_trait,
+    extract::{rejection::ExtensionRejection, ConnectInfo, FromRef, FromRequestParts},
+};
+use http::{header::FORWARDED, request::Parts, HeaderMap};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+/// Extractor that resolves the IP address of the client, taking trusted proxies into account.
+///
+/// The address of the peer is read from the [`ConnectInfo<SocketAddr>`](ConnectInfo) request
+/// extension, so the app must be served with [`Router::into_make_service_with_connect_info`]. If
+/// the peer is one of the [`TrustedProxies`], which must be accessible from the state via
+/// [`FromRef`], the chain of addresses the proxies added to the request is walked from the last
+/// hop towards the client, and the first address that isn't a trusted proxy is the client IP.
+///
+/// The chain is read from the first of these headers that is present:
+///
+/// - `Forwarded`, using the `for` parameter of each element.
+/// - `X-Forwarded-For`.
+/// - `X-Real-IP`, which only contains a single address.
+///
+/// Since only addresses added by trusted proxies are considered, clients can't spoof their
+/// address by sending these headers themselves. If the chain ends, or contains a hop that isn't
+/// an IP address, before an untrusted address is found, the last address that was reached is
+/// used. If the request has no `ConnectInfo` extension it is rejected with
+/// `500 Internal Server Error`, like [`ConnectInfo`] itself.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{ClientIp, TrustedProxies};
+/// use std::net::SocketAddr;
+///
+/// async fn handler(ClientIp(ip): ClientIp) -> String {
+///     format!("Hello, {ip}!")
+/// }
+///
+/// let trusted_proxies = TrustedProxies::new()
+///     .trust("10.0.0.0/8")
+///     .trust("fd00::/8");
+///
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .with_state(trusted_proxies);
+///
+/// # async {
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+/// axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+///     .await
+///     .unwrap();
+/// # };
+/// ```
+///
+/// [`Router::into_make_service_with_connect_info`]: axum::Router::into_make_service_with_connect_info
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+    TrustedProxies: FromRef<S>,
+{
+    type Rejection = ExtensionRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ConnectInfo(peer) = ConnectInfo::<SocketAddr>::from_request_parts(parts, state).await?;
+        let trusted_proxies = TrustedProxies::from_ref(state);
+
+        Ok(Self(trusted_proxies.resolve(peer.ip(), &parts.headers)))
+    }
+}
+
+axum_core::__impl_deref!(ClientIp: IpAddr);
+
+/// Returns the addresses in the first proxy header present in `headers`, ordered from the
+/// client to the last proxy. Hops that aren't IP addresses are `None`.
+fn forwarded_chain(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    if headers.contains_key(FORWARDED) {
+        let mut elements = Vec::new();
+        for value in headers.get_all(FORWARDED) {
+            let parsed = value
+                .to_str()
+                .ok()
+                .and_then(|value| parse_forwarded(value, &mut elements));
+            if parsed.is_none() {
+                // a malformed header can't be attributed to a hop
+                return vec![None];
+            }
+        }
+
+        return elements
+            .into_iter()
+            .map(|element| match element.for_ {
+                Some(ForwardedNode::Ip { ip, .. }) => Some(ip),
+                _ => None,
+            })
+            .collect();
+    }
+
+    let list = |name: &str| {
+        headers
+            .get_all(name)
+            .iter()
+            .flat_map(|value| value.to_str().unwrap_or_default().split(','))
+            .map(|hop| hop.trim().parse().ok())
+            .collect::<Vec<_>>()
+    };
+
+    if headers.contains_key("x-forwarded-for") {
+        list("x-forwarded-for")
+    } else {
+        list("x-real-ip")
+    }
+}
+
+/// The proxies whose forwarding headers are trusted by [`ClientIp`].
+///
+/// Cloning is cheap.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Arc<Vec<IpNetwork>>,
+}
+
+impl TrustedProxies {
+    /// Create a new `TrustedProxies` that doesn't trust any proxy.
+    ///
+    /// With no trusted proxies [`ClientIp`] is always the address of the peer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust the proxies in `network`.
+    ///
+    /// `network` is either a single IP address, such as `10.0.0.1`, or a network in CIDR
+    /// notation, such as `10.0.0.0/8` or `fd00::/8`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `network` isn't a valid IP address or CIDR network.
+    pub fn trust(mut self, network: &str) -> Self {
+        let network = IpNetwork::parse(network)
+            .unwrap_or_else(|| panic!("`{network}` is not a valid IP address or CIDR network"));
+        Arc::make_mut(&mut self.networks).push(network);
+        self
+    }
+
+    /// Returns `true` if `ip` is a trusted proxy.
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.networks.iter().any(|network| network.contains(ip))
+    }
+
+    /// Resolve the client IP of a request sent by `peer`, walking the proxy headers from the last
+    /// hop towards the client until an address that isn't trusted is found.
+    pub(crate) fn resolve(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let mut ip = peer;
+        let mut chain = forwarded_chain(headers).into_iter().rev();
+        while self.is_trusted(ip) {
+            match chain.next() {
+                Some(Some(hop)) => ip = hop,
+                // the chain ended, or a proxy didn't reveal the address of the previous hop
+                Some(None) | None => break,
+            }
+        }
+        ip
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    fn parse(value: &str) -> Option<Self> {
+        let (addr, prefix_len) = match value.split_once('/') {
+            Some((addr, prefix_len)) => (addr.parse().ok()?, Some(prefix_len.parse().ok()?)),
+            None => (value.parse().ok()?, None),
+        };
+
+        let max_len = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        let prefix_len = prefix_len.unwrap_or(max_len);
+        (prefix_len <= max_len).then_some(Self { addr, prefix_len })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        let ip = match ip {
+            IpAddr::V6(ip) => ip.to_ipv4_mapped().map_or(IpAddr::V6(ip), IpAddr::V4),
+            ip => ip,
+        };
+
+        match (self.addr, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.prefix_len));
+                let mask = mask.unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(self.prefix_len));
+                let mask = mask.unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use http::StatusCode;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn client_ip(peer: [u8; 4], headers: &[(&str, &str)]) -> String {
+        let trusted_proxies = TrustedProxies::new()
+            .trust("10.0.0.0/8")
+            .trust("192.168.1.1");
+        let app = Router::new()
+            .route(
+                "/",
+                get(|ClientIp(ip): ClientIp| async move { ip.to_string() }),
+            )
+            .with_state(trusted_proxies);
+
+        let mut req = Request::get("/");
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        let mut req = req.body(Body::empty()).unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((peer, 1234))));
+
+        let res = app.oneshot(req).await.unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn single_proxy() {
+        let headers = [("x-forwarded-for", "203.0.113.7")];
+        assert_eq!(client_ip([10, 0, 0, 1], &headers).await, "203.0.113.7");
+
+        let headers = [("x-real-ip", "203.0.113.7")];
+        assert_eq!(client_ip([192, 168, 1, 1], &headers).await, "203.0.113.7");
+
+        // no proxy headers
+        assert_eq!(client_ip([10, 0, 0, 1], &[]).await, "10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn multi_hop_chain() {
+        let headers = [("x-forwarded-for", "203.0.113.7, 10.0.0.5, 192.168.1.1")];
+        assert_eq!(client_ip([10, 0, 0, 1], &headers).await, "203.0.113.7");
+
+        let headers = [(
+            "forwarded",
+            "for=203.0.113.7, for=\"[fd00::1]:8080\", for=10.0.0.5",
+        )];
+        assert_eq!(client_ip([10, 0, 0, 1], &headers).await, "fd00::1");
+
+        // `Forwarded` takes precedence over `X-Forwarded-For`
+        let headers = [
+            ("forwarded", "for=203.0.113.7"),
+            ("x-forwarded-for", "198.51.100.9"),
+        ];
+        assert_eq!(client_ip([10, 0, 0, 1], &headers).await, "203.0.113.7");
+    }
+
+    #[tokio::test]
+    async fn spoofing_attempts() {
+        // the client added a fake address in front of the one our proxy added
+        let headers = [("x-forwarded-for", "1.2.3.4, 198.51.100.9")];
+        assert_eq!(client_ip([10, 0, 0, 1], &headers).await, "198.51.100.9");
+
+        // the client isn't behind a trusted proxy at all
+        let headers = [("x-forwarded-for", "1.2.3.4")];
+        assert_eq!(client_ip([198, 51, 100, 9], &headers).await, "198.51.100.9");
+
+        // the client pretends to be a trusted proxy
+        let headers = [("x-forwarded-for", "1.2.3.4, 10.0.0.5")];
+        assert_eq!(client_ip([198, 51, 100, 9], &headers).await, "198.51.100.9");
+    }
+
+    #[tokio::test]
+    async fn missing_connect_info() {
+        let app = Router::new()
+            .route("/", get(|_: ClientIp| async {}))
+            .with_state(TrustedProxies::new());
+
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn networks() {
+        let network = IpNetwork::parse("10.0.0.0/8").unwrap();
+        assert!(network.contains("10.255.0.1".parse().unwrap()));
+        assert!(network.contains("::ffff:10.0.0.1".parse().unwrap()));
+        assert!(!network.contains("11.0.0.1".parse().unwrap()));
+
+        let network = IpNetwork::parse("::/0").unwrap();
+        assert!(network.contains("fd00::1".parse().unwrap()));
+
+        for invalid in ["10.0.0.0/33", "10.0.0.0/", "example.com", "fd00::/129"] {
+            assert!(IpNetwork::parse(invalid).is_none(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, FromRequestParts, Request},
+    response::{IntoResponse, Response},
+};
+use std::fmt;
+
+/// Extractor that combines an extractor for the request parts with an extractor for the body.
+///
+/// `Combined<P, B>` runs `P`, for example [`Query`], on the request parts first and then `B`, for
+/// example [`Json`], on the request. This is the same as using the two extractors as separate
+/// handler arguments, but lets APIs that accept some fields in the query string and some in the
+/// body extract them as one argument with a single rejection type.
+///
+/// Since `B` consumes the request body, `Combined` must be the last argument of the handler.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{extract::Query, routing::post, Json, Router};
+/// use axum_extra::extract::Combined;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Pagination {
+///     page: u32,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Filter {
+///     tags: Vec<String>,
+/// }
+///
+/// async fn search(
+///     Combined(Query(pagination), Json(filter)): Combined<Query<Pagination>, Json<Filter>>,
+/// ) {
+///     // ...
+/// }
+///
+/// let app = Router::new().route("/search", post(search));
+/// # let _: Router = app;
+/// ```
+///
+/// [`Query`]: axum::extract::Query
+/// [`Json`]: axum::Json
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Combined<P, B>(pub P, pub B);
+
+#[async_trait]
+impl<S, P, B> FromRequest<S> for Combined<P, B>
+where
+    S: Send + Sync,
+    P: FromRequestParts<S> + Send,
+    B: FromRequest<S>,
+{
+    type Rejection = CombinedRejection<P::Rejection, B::Rejection>;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let (mut parts, body) = req.into_parts();
+
+        let p = P::from_request_parts(&mut parts, state)
+            .await
+            .map_err(CombinedRejection::Parts)?;
+
+        let req = Request::from_parts(parts, body);
+        let b = B::from_request(req, state)
+            .await
+            .map_err(CombinedRejection::Body)?;
+
+        Ok(Self(p, b))
+    }
+}
+
+/// Rejection used for [`Combined`].
+#[derive(Debug)]
+pub enum CombinedRejection<P, B> {
+    /// The extractor for the request parts failed.
+    Parts(P),
+    /// The extractor for the body failed.
+    Body(B),
+}
+
+impl<P, B> IntoResponse for CombinedRejection<P, B>
+where
+    P: IntoResponse,
+    B: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        match self {
+            Self::Parts(inner) => inner.into_response(),
+            Self::Body(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl<P, B> fmt::Display for CombinedRejection<P, B>
+where
+    P: fmt::Display,
+    B: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parts(inner) => inner.fmt(f),
+            Self::Body(inner) => inner.fmt(f),
+        }
+    }
+}
+
+impl<P, B> std::error::Error for CombinedRejection<P, B>
+where
+    P: std::error::Error + 'static,
+    B: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parts(inner) => Some(inner),
+            Self::Body(inner) => Some(inner),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{extract::Query, routing::post, Json, Router};
+    use http::StatusCode;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Pagination {
+        page: u32,
+    }
+
+    #[derive(Deserialize)]
+    struct Filter {
+        tag: String,
+    }
+
+    async fn handler(
+        Combined(Query(pagination), Json(filter)): Combined<Query<Pagination>, Json<Filter>>,
+    ) -> String {
+        format!("{} {}", pagination.page, filter.tag)
+    }
+
+    #[tokio::test]
+    async fn extracts_query_and_json_body() {
+        let app = Router::new().route("/", post(handler));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/?page=2")
+            .json(&serde_json::json!({ "tag": "rust" }))
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "2 rust");
+    }
+
+    #[tokio::test]
+    async fn rejections() {
+        let app = Router::new().route("/", post(handler));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .json(&serde_json::json!({ "tag": "rust" }))
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = client.post("/?page=2").body("not json").await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+}
@@ -0,0 +1,280 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    response::{IntoResponse, Response},
+};
+use http::{header::COOKIE, request::Parts, HeaderName, StatusCode};
+use std::{fmt, sync::Arc};
+
+/// Extractor that validates a CSRF token sent in a request header.
+///
+/// The token is read from the `X-CSRF-Token` header, or the header set with
+/// [`CsrfConfig::header`], and compared in constant time against the expected token for the
+/// request. The expected token is looked up with a [`CsrfConfig`], which must be accessible from
+/// the state via [`FromRef`].
+///
+/// The expected token can either be bound to the user's session, using [`CsrfConfig::new`], or
+/// come from a cookie with [`CsrfConfig::double_submit_cookie`], which implements the
+/// [double-submit cookie] pattern.
+///
+/// If the header is missing, there is no expected token, or the tokens don't match the request is
+/// rejected with `403 Forbidden`.
+///
+/// Tokens submitted in a form field can be checked with [`CsrfConfig::verify`] after the form has
+/// been extracted.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::extract::{CsrfConfig, CsrfToken};
+///
+/// async fn handler(_: CsrfToken) {
+///     // the request has a valid CSRF token
+/// }
+///
+/// let csrf = CsrfConfig::double_submit_cookie("csrf_token");
+///
+/// let app = Router::new().route("/", post(handler)).with_state(csrf);
+/// # let _: Router = app;
+/// ```
+///
+/// [double-submit cookie]: https://cheatsheetseries.owasp.org/cheatsheets/Cross-Site_Request_Forgery_Prevention_Cheat_Sheet.html#alternative-using-a-double-submit-cookie-pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrfToken(pub String);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CsrfToken
+where
+    S: Send + Sync,
+    CsrfConfig: FromRef<S>,
+{
+    type Rejection = CsrfTokenRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let config = CsrfConfig::from_ref(state);
+
+        let token = parts
+            .headers
+            .get(&config.header)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(CsrfTokenRejection::Missing)?;
+
+        if config.verify(parts, token) {
+            Ok(Self(token.to_owned()))
+        } else {
+            Err(CsrfTokenRejection::Invalid)
+        }
+    }
+}
+
+axum_core::__impl_deref!(CsrfToken: String);
+
+/// Configuration used by [`CsrfToken`].
+///
+/// Cloning is cheap.
+#[derive(Clone)]
+pub struct CsrfConfig {
+    header: HeaderName,
+    expected: Arc<dyn Fn(&Parts) -> Option<String> + Send + Sync>,
+}
+
+impl CsrfConfig {
+    /// Create a new `CsrfConfig` that gets the expected token for a request with `expected`.
+    ///
+    /// This is typically used to load the token bound to the user's session, for example from a
+    /// request extension inserted by a session middleware. Return `None` if there is no expected
+    /// token, in which case the request is rejected.
+    pub fn new<F>(expected: F) -> Self
+    where
+        F: Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            header: HeaderName::from_static("x-csrf-token"),
+            expected: Arc::new(expected),
+        }
+    }
+
+    /// Create a new `CsrfConfig` that expects the token to match the value of a cookie.
+    ///
+    /// The cookie should be set to a random value when the form is served, and it should not be
+    /// `HttpOnly` if the token is sent in a header by JavaScript.
+    pub fn double_submit_cookie(cookie_name: impl Into<String>) -> Self {
+        let cookie_name = cookie_name.into();
+        Self::new(move |parts| cookie(parts, &cookie_name).map(ToOwned::to_owned))
+    }
+
+    /// Set the header the token is read from.
+    ///
+    /// Defaults to `X-CSRF-Token`.
+    pub fn header(mut self, header: HeaderName) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Check whether `token` matches the expected token for the request.
+    ///
+    /// The comparison runs in constant time with respect to the contents of the tokens.
+    pub fn verify(&self, parts: &Parts, token: &str) -> bool {
+        match (self.expected)(parts) {
+            Some(expected) => !token.is_empty() && constant_time_eq(token, &expected),
+            None => false,
+        }
+    }
+}
+
+impl fmt::Debug for CsrfConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CsrfConfig")
+            .field("header", &self.header)
+            .finish_non_exhaustive()
+    }
+}
+
+fn cookie<'a>(parts: &'a Parts, name: &str) -> Option<&'a str> {
+    parts
+        .headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Compare two strings without short-circuiting on the first differing byte, so the time taken
+/// doesn't reveal how much of a guessed token is correct.
+///
+/// Only the length is leaked, which is fine for tokens of a fixed length.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.bytes()
+        .zip(b.bytes())
+        .fold(0, |acc, (a, b)| acc | (a ^ b))
+        == 0
+}
+
+/// Rejection used for [`CsrfToken`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CsrfTokenRejection {
+    /// The request didn't contain a CSRF token.
+    Missing,
+    /// The CSRF token didn't match the expected token.
+    Invalid,
+}
+
+impl IntoResponse for CsrfTokenRejection {
+    fn into_response(self) -> Response {
+        (StatusCode::FORBIDDEN, self.to_string()).into_response()
+    }
+}
+
+impl fmt::Display for CsrfTokenRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "Missing CSRF token"),
+            Self::Invalid => write!(f, "Invalid CSRF token"),
+        }
+    }
+}
+
+impl std::error::Error for CsrfTokenRejection {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+
+    #[tokio::test]
+    async fn matching_token() {
+        let app = Router::new()
+            .route("/", post(|CsrfToken(token): CsrfToken| async { token }))
+            .with_state(CsrfConfig::double_submit_cookie("csrf_token"));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("cookie", "theme=dark; csrf_token=abc123")
+            .header("x-csrf-token", "abc123")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "abc123");
+    }
+
+    #[tokio::test]
+    async fn mismatched_token() {
+        let app = Router::new()
+            .route("/", post(|_: CsrfToken| async {}))
+            .with_state(CsrfConfig::double_submit_cookie("csrf_token"));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("cookie", "csrf_token=abc123")
+            .header("x-csrf-token", "abc124")
+            .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert_eq!(res.text().await, "Invalid CSRF token");
+
+        // no cookie to compare against
+        let res = client.post("/").header("x-csrf-token", "abc123").await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert_eq!(res.text().await, "Invalid CSRF token");
+    }
+
+    #[tokio::test]
+    async fn missing_token() {
+        let app = Router::new()
+            .route("/", post(|_: CsrfToken| async {}))
+            .with_state(CsrfConfig::double_submit_cookie("csrf_token"));
+
+        let client = TestClient::new(app);
+
+        let res = client.post("/").header("cookie", "csrf_token=abc123").await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert_eq!(res.text().await, "Missing CSRF token");
+    }
+
+    #[tokio::test]
+    async fn session_bound_token_and_custom_header() {
+        #[derive(Clone)]
+        struct SessionToken(String);
+
+        let csrf = CsrfConfig::new(|parts| {
+            parts
+                .extensions
+                .get::<SessionToken>()
+                .map(|token| token.0.clone())
+        })
+        .header(HeaderName::from_static("x-token"));
+
+        let app = Router::new()
+            .route("/", post(|_: CsrfToken| async {}))
+            .layer(axum::Extension(SessionToken("secret".to_owned())))
+            .with_state(csrf);
+        let client = TestClient::new(app);
+
+        let res = client.post("/").header("x-token", "secret").await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = client.post("/").header("x-csrf-token", "secret").await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn constant_time_comparison() {
+        assert!(constant_time_eq("abc", "abc"));
+        assert!(!constant_time_eq("abc", "abd"));
+        assert!(!constant_time_eq("abc", "xbc"));
+        assert!(!constant_time_eq("abc", "abcd"));
+        assert!(constant_time_eq("", ""));
+    }
+}
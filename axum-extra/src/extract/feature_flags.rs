@@ -0,0 +1,385 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use http::{header::COOKIE, request::Parts, StatusCode};
+use sha2::Sha256;
+use std::{collections::HashSet, fmt, sync::Arc};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Extractor that resolves which feature flags are enabled for the request.
+///
+/// Flags are resolved from these sources, where later sources take priority over earlier ones:
+///
+/// 1. The defaults of the [`FeatureFlagsConfig`], which must be accessible from the state via
+///    [`FromRef`].
+/// 2. A cookie, named `feature_flags` by default.
+/// 3. A signed `X-Feature-Flags` header.
+///
+/// The cookie and header contain a comma separated list of flag names. A name enables the flag
+/// and a name prefixed with `-` disables it, so `new-checkout,-beta-search` enables
+/// `new-checkout` and disables `beta-search` regardless of the defaults. Flags that aren't
+/// mentioned keep the value of the previous source. Flag names may only contain ASCII letters,
+/// digits, `-`, `_` and `.`.
+///
+/// The header must be signed with [`FeatureFlagsConfig::sign`], which is useful for letting
+/// internal tools or tests enable flags that users shouldn't be able to enable themselves. If
+/// the header is malformed the request is rejected with `400 Bad Request`, and if the signature
+/// doesn't match it's rejected with `403 Forbidden`.
+///
+/// The cookie isn't signed, so users can change it. Only flags allowed with
+/// [`FeatureFlagsConfig::allow_in_cookie`], such as opting into a beta, are read from it and
+/// other flags in the cookie are ignored. A malformed cookie is ignored.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{FeatureFlags, FeatureFlagsConfig};
+///
+/// async fn checkout(flags: FeatureFlags) -> &'static str {
+///     if flags.is_enabled("new-checkout") {
+///         "new checkout"
+///     } else {
+///         "old checkout"
+///     }
+/// }
+///
+/// let config = FeatureFlagsConfig::new(b"a secret key, at least 32 bytes long")
+///     .enable("dark-mode")
+///     .allow_in_cookie("new-checkout");
+///
+/// let app = Router::new()
+///     .route("/checkout", get(checkout))
+///     .with_state(config);
+/// # let _: Router = app;
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "feature-flags")))]
+#[derive(Debug, Clone, Default)]
+pub struct FeatureFlags {
+    enabled: HashSet<String>,
+}
+
+impl FeatureFlags {
+    /// Returns `true` if the flag `name` is enabled.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.enabled.contains(name)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for FeatureFlags
+where
+    S: Send + Sync,
+    FeatureFlagsConfig: FromRef<S>,
+{
+    type Rejection = FeatureFlagsRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let config = FeatureFlagsConfig::from_ref(state);
+        let mut enabled = (*config.defaults).clone();
+
+        if let Some(mut overrides) = cookie(parts, &config.cookie_name).and_then(parse_flags) {
+            overrides.retain(|(name, _)| config.cookie_flags.contains(*name));
+            apply(&mut enabled, overrides);
+        }
+
+        if let Some(value) = parts.headers.get("x-feature-flags") {
+            let value = value
+                .to_str()
+                .map_err(|_| FeatureFlagsRejection::Malformed)?;
+            let (flags, signature) = value
+                .split_once(';')
+                .and_then(|(flags, signature)| {
+                    let signature = signature.trim().strip_prefix("sig=")?;
+                    Some((flags.trim(), URL_SAFE_NO_PAD.decode(signature).ok()?))
+                })
+                .ok_or(FeatureFlagsRejection::Malformed)?;
+            let overrides = parse_flags(flags).ok_or(FeatureFlagsRejection::Malformed)?;
+
+            config
+                .mac(flags)
+                .verify_slice(&signature)
+                .map_err(|_| FeatureFlagsRejection::InvalidSignature)?;
+
+            apply(&mut enabled, overrides);
+        }
+
+        Ok(Self { enabled })
+    }
+}
+
+/// Parses a list of flags into `(name, enabled)` pairs.
+fn parse_flags(value: &str) -> Option<Vec<(&str, bool)>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|flag| !flag.is_empty())
+        .map(|flag| {
+            let (name, enabled) = match flag.strip_prefix('-') {
+                Some(name) => (name, false),
+                None => (flag, true),
+            };
+            is_valid_name(name).then_some((name, enabled))
+        })
+        .collect()
+}
+
+fn apply(enabled: &mut HashSet<String>, overrides: Vec<(&str, bool)>) {
+    for (name, enable) in overrides {
+        if enable {
+            enabled.insert(name.to_owned());
+        } else {
+            enabled.remove(name);
+        }
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+fn cookie<'a>(parts: &'a Parts, name: &str) -> Option<&'a str> {
+    parts
+        .headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Configuration for [`FeatureFlags`].
+///
+/// Cloning is cheap.
+#[cfg_attr(docsrs, doc(cfg(feature = "feature-flags")))]
+#[derive(Clone)]
+pub struct FeatureFlagsConfig {
+    key: Arc<[u8]>,
+    defaults: Arc<HashSet<String>>,
+    cookie_flags: Arc<HashSet<String>>,
+    cookie_name: Arc<str>,
+}
+
+impl FeatureFlagsConfig {
+    /// Create a new `FeatureFlagsConfig` that verifies the `X-Feature-Flags` header with `key`.
+    ///
+    /// The key should be at least 32 random bytes. No flags are enabled by default and no flags
+    /// are read from the cookie.
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: key.as_ref().into(),
+            defaults: Default::default(),
+            cookie_flags: Default::default(),
+            cookie_name: "feature_flags".into(),
+        }
+    }
+
+    /// Enable the flag `name` by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a valid flag name.
+    pub fn enable(mut self, name: &str) -> Self {
+        assert!(is_valid_name(name), "`{name}` is not a valid flag name");
+        Arc::make_mut(&mut self.defaults).insert(name.to_owned());
+        self
+    }
+
+    /// Allow users to enable or disable the flag `name` with the cookie.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a valid flag name.
+    pub fn allow_in_cookie(mut self, name: &str) -> Self {
+        assert!(is_valid_name(name), "`{name}` is not a valid flag name");
+        Arc::make_mut(&mut self.cookie_flags).insert(name.to_owned());
+        self
+    }
+
+    /// Set the name of the cookie flags are read from.
+    ///
+    /// Defaults to `feature_flags`.
+    pub fn cookie_name(mut self, cookie_name: &str) -> Self {
+        self.cookie_name = cookie_name.into();
+        self
+    }
+
+    /// Sign a comma separated list of flags and return a value for the `X-Feature-Flags` header.
+    pub fn sign(&self, flags: &str) -> String {
+        let flags = flags.trim();
+        let signature = self.mac(flags).finalize().into_bytes();
+        format!("{flags}; sig={}", URL_SAFE_NO_PAD.encode(signature))
+    }
+
+    fn mac(&self, flags: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any size");
+        mac.update(flags.as_bytes());
+        mac
+    }
+}
+
+impl fmt::Debug for FeatureFlagsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeatureFlagsConfig")
+            .field("defaults", &self.defaults)
+            .field("cookie_flags", &self.cookie_flags)
+            .field("cookie_name", &self.cookie_name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Rejection used for [`FeatureFlags`].
+#[cfg_attr(docsrs, doc(cfg(feature = "feature-flags")))]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FeatureFlagsRejection {
+    /// The `X-Feature-Flags` header was malformed.
+    Malformed,
+    /// The signature of the `X-Feature-Flags` header didn't match the flags.
+    InvalidSignature,
+}
+
+impl IntoResponse for FeatureFlagsRejection {
+    fn into_response(self) -> Response {
+        let body = self.to_string();
+        let status = match self {
+            Self::Malformed => StatusCode::BAD_REQUEST,
+            Self::InvalidSignature => StatusCode::FORBIDDEN,
+        };
+        axum_core::__log_rejection!(rejection_type = Self, body_text = body, status = status,);
+        (status, body).into_response()
+    }
+}
+
+impl fmt::Display for FeatureFlagsRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "Malformed `X-Feature-Flags` header"),
+            Self::InvalidSignature => write!(f, "Invalid `X-Feature-Flags` signature"),
+        }
+    }
+}
+
+impl std::error::Error for FeatureFlagsRejection {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    fn config() -> FeatureFlagsConfig {
+        FeatureFlagsConfig::new(b"an insecure key only used in tests")
+            .enable("dark-mode")
+            .enable("search")
+            .allow_in_cookie("beta")
+            .allow_in_cookie("search")
+    }
+
+    async fn enabled_flags(flags: FeatureFlags) -> String {
+        let mut enabled = ["dark-mode", "search", "beta", "internal"]
+            .into_iter()
+            .filter(|name| flags.is_enabled(name))
+            .collect::<Vec<_>>();
+        enabled.sort_unstable();
+        enabled.join(",")
+    }
+
+    #[tokio::test]
+    async fn defaults() {
+        let app = Router::new()
+            .route("/", get(enabled_flags))
+            .with_state(config());
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "dark-mode,search");
+    }
+
+    #[tokio::test]
+    async fn cookie_flags() {
+        let app = Router::new()
+            .route("/", get(enabled_flags))
+            .with_state(config());
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("cookie", "theme=light; feature_flags=beta,-search")
+            .await;
+        assert_eq!(res.text().await, "beta,dark-mode");
+
+        // flags that aren't allowed in the cookie are ignored
+        let res = client
+            .get("/")
+            .header("cookie", "feature_flags=internal,-dark-mode")
+            .await;
+        assert_eq!(res.text().await, "dark-mode,search");
+
+        // malformed cookies are ignored
+        let res = client
+            .get("/")
+            .header("cookie", "feature_flags=beta,not a flag")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "dark-mode,search");
+    }
+
+    #[tokio::test]
+    async fn header_flags() {
+        let app = Router::new()
+            .route("/", get(enabled_flags))
+            .with_state(config());
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("x-feature-flags", config().sign("internal,-dark-mode"))
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "internal,search");
+
+        // the header takes priority over the cookie
+        let res = client
+            .get("/")
+            .header("cookie", "feature_flags=beta,internal")
+            .header("x-feature-flags", config().sign("-internal"))
+            .await;
+        assert_eq!(res.text().await, "beta,dark-mode,search");
+    }
+
+    #[tokio::test]
+    async fn invalid_header() {
+        let app = Router::new()
+            .route("/", get(|_: FeatureFlags| async {}))
+            .with_state(config());
+
+        let client = TestClient::new(app);
+
+        for value in ["internal", "internal; sig=!!!", "not a flag; sig=abc"] {
+            let res = client.get("/").header("x-feature-flags", value).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{value}");
+        }
+
+        let other_key = FeatureFlagsConfig::new(b"another key");
+        let res = client
+            .get("/")
+            .header("x-feature-flags", other_key.sign("internal"))
+            .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,274 @@
+use axum::{async_trait, extract::FromRequestParts};
+use http::{request::Parts, HeaderMap};
+use std::convert::Infallible;
+
+/// Extractor for the [Fetch Metadata] request headers.
+///
+/// Browsers send the `Sec-Fetch-Site`, `Sec-Fetch-Mode`, `Sec-Fetch-Dest` and `Sec-Fetch-User`
+/// headers to describe the context a request was made in. Servers can use them to reject
+/// cross-site requests to endpoints that aren't meant to be embedded or called from other sites,
+/// which protects against CSRF and cross-site leaks.
+///
+/// Headers that are missing or have an unknown value are `None`, so this extractor never
+/// rejects the request. Note that only browsers send these headers, and older browsers don't send
+/// them at all, so a policy based on them should allow requests without them.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{http::StatusCode, routing::post, Router};
+/// use axum_extra::extract::{FetchMetadata, FetchMode};
+///
+/// async fn transfer_money(fetch_metadata: FetchMetadata) -> Result<(), StatusCode> {
+///     if fetch_metadata.is_cross_site() && fetch_metadata.mode != Some(FetchMode::Navigate) {
+///         return Err(StatusCode::FORBIDDEN);
+///     }
+///     // ...
+///     # Ok(())
+/// }
+///
+/// let app = Router::new().route("/transfer", post(transfer_money));
+/// # let _: Router = app;
+/// ```
+///
+/// [Fetch Metadata]: https://www.w3.org/TR/fetch-metadata/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchMetadata {
+    /// The value of the `Sec-Fetch-Site` header.
+    pub site: Option<FetchSite>,
+    /// The value of the `Sec-Fetch-Mode` header.
+    pub mode: Option<FetchMode>,
+    /// The value of the `Sec-Fetch-Dest` header.
+    pub dest: Option<FetchDest>,
+    /// Whether the `Sec-Fetch-User` header was `?1`, meaning the request was triggered by the
+    /// user, such as by clicking a link.
+    pub user: bool,
+}
+
+impl FetchMetadata {
+    /// Parse the fetch metadata from the headers in `headers`.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+        Self {
+            site: header("sec-fetch-site").and_then(FetchSite::parse),
+            mode: header("sec-fetch-mode").and_then(FetchMode::parse),
+            dest: header("sec-fetch-dest").and_then(FetchDest::parse),
+            user: header("sec-fetch-user") == Some("?1"),
+        }
+    }
+
+    /// Returns `true` if the request was made by the same origin as the server.
+    pub fn is_same_origin(&self) -> bool {
+        self.site == Some(FetchSite::SameOrigin)
+    }
+
+    /// Returns `true` if the request was made by the same site as the server, which includes
+    /// the same origin.
+    pub fn is_same_site(&self) -> bool {
+        matches!(
+            self.site,
+            Some(FetchSite::SameOrigin) | Some(FetchSite::SameSite)
+        )
+    }
+
+    /// Returns `true` if the request was made by a different site than the server.
+    pub fn is_cross_site(&self) -> bool {
+        self.site == Some(FetchSite::CrossSite)
+    }
+
+    /// Returns `true` if the request is a navigation, such as following a link.
+    pub fn is_navigation(&self) -> bool {
+        self.mode == Some(FetchMode::Navigate)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for FetchMetadata
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+macro_rules! fetch_enum {
+    (
+        $(#[$m:meta])*
+        pub enum $name:ident {
+            $(
+                #[doc = $doc:literal]
+                $variant:ident => $value:literal,
+            )*
+        }
+    ) => {
+        $(#[$m])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum $name {
+            $(
+                #[doc = $doc]
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// The value of the variant, as sent in the header.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $value,)*
+                }
+            }
+
+            fn parse(value: &str) -> Option<Self> {
+                match value {
+                    $($value => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+fetch_enum! {
+    /// The relationship between the origin of the request and the server, from the
+    /// `Sec-Fetch-Site` header.
+    pub enum FetchSite {
+        /// `cross-site`, the request was made by another site.
+        CrossSite => "cross-site",
+        /// `same-origin`, the request was made by the same origin.
+        SameOrigin => "same-origin",
+        /// `same-site`, the request was made by another origin of the same site.
+        SameSite => "same-site",
+        /// `none`, the request was initiated by the user, such as by typing the URL.
+        None => "none",
+    }
+}
+
+fetch_enum! {
+    /// The mode of the request, from the `Sec-Fetch-Mode` header.
+    pub enum FetchMode {
+        /// `cors`, a CORS request.
+        Cors => "cors",
+        /// `navigate`, a navigation between documents.
+        Navigate => "navigate",
+        /// `no-cors`, a request without CORS, such as loading an image.
+        NoCors => "no-cors",
+        /// `same-origin`, a request that must be made by the same origin.
+        SameOrigin => "same-origin",
+        /// `websocket`, a WebSocket connection.
+        Websocket => "websocket",
+    }
+}
+
+fetch_enum! {
+    /// The destination of the request, from the `Sec-Fetch-Dest` header.
+    pub enum FetchDest {
+        /// `audio`
+        Audio => "audio",
+        /// `audioworklet`
+        AudioWorklet => "audioworklet",
+        /// `document`
+        Document => "document",
+        /// `embed`
+        Embed => "embed",
+        /// `empty`, used by `fetch()` and `XMLHttpRequest`.
+        Empty => "empty",
+        /// `font`
+        Font => "font",
+        /// `frame`
+        Frame => "frame",
+        /// `iframe`
+        Iframe => "iframe",
+        /// `image`
+        Image => "image",
+        /// `manifest`
+        Manifest => "manifest",
+        /// `object`
+        Object => "object",
+        /// `paintworklet`
+        PaintWorklet => "paintworklet",
+        /// `report`
+        Report => "report",
+        /// `script`
+        Script => "script",
+        /// `serviceworker`
+        ServiceWorker => "serviceworker",
+        /// `sharedworker`
+        SharedWorker => "sharedworker",
+        /// `style`
+        Style => "style",
+        /// `track`
+        Track => "track",
+        /// `video`
+        Video => "video",
+        /// `worker`
+        Worker => "worker",
+        /// `xslt`
+        Xslt => "xslt",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn parse(values: &[(&'static str, &'static str)]) -> FetchMetadata {
+        let mut headers = HeaderMap::new();
+        for (name, value) in values {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        FetchMetadata::from_headers(&headers)
+    }
+
+    #[test]
+    fn same_origin_navigation() {
+        let fetch_metadata = parse(&[
+            ("sec-fetch-site", "same-origin"),
+            ("sec-fetch-mode", "navigate"),
+            ("sec-fetch-dest", "document"),
+            ("sec-fetch-user", "?1"),
+        ]);
+
+        assert_eq!(
+            fetch_metadata,
+            FetchMetadata {
+                site: Some(FetchSite::SameOrigin),
+                mode: Some(FetchMode::Navigate),
+                dest: Some(FetchDest::Document),
+                user: true,
+            }
+        );
+        assert!(fetch_metadata.is_same_origin());
+        assert!(fetch_metadata.is_same_site());
+        assert!(!fetch_metadata.is_cross_site());
+        assert!(fetch_metadata.is_navigation());
+    }
+
+    #[test]
+    fn cross_site_request() {
+        let fetch_metadata = parse(&[
+            ("sec-fetch-site", "cross-site"),
+            ("sec-fetch-mode", "no-cors"),
+            ("sec-fetch-dest", "image"),
+        ]);
+
+        assert!(fetch_metadata.is_cross_site());
+        assert!(!fetch_metadata.is_same_site());
+        assert!(!fetch_metadata.is_navigation());
+        assert_eq!(fetch_metadata.dest, Some(FetchDest::Image));
+        assert!(!fetch_metadata.user);
+    }
+
+    #[test]
+    fn missing_and_unknown_headers() {
+        assert_eq!(parse(&[]), FetchMetadata::default());
+
+        let fetch_metadata = parse(&[("sec-fetch-site", "elsewhere"), ("sec-fetch-user", "?0")]);
+        assert_eq!(fetch_metadata, FetchMetadata::default());
+    }
+}
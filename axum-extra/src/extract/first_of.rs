@@ -0,0 +1,258 @@
+use crate::either::*;
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    response::{IntoResponse, Response},
+};
+use http::request::Parts;
+use std::fmt;
+
+/// Extractor that tries a tuple of extractors in order and returns the first that succeeds.
+///
+/// `FirstOf<(A, B, C)>` runs `A`, then `B` if `A` failed, then `C` if `B` failed. Extractors after
+/// the first one that succeeds are never run. The extracted value is an [`Either3`] (or
+/// [`Either`], [`Either4`], ... depending on the size of the tuple) whose variant says which
+/// extractor succeeded.
+///
+/// Unlike using the [`Either*`](crate::either) types as extractors directly, the rejection is only
+/// returned if all extractors fail and it contains all of their rejections. Its response is the
+/// response of the last extractor's rejection.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::{Extension, Query},
+///     routing::get,
+///     Router,
+/// };
+/// use axum_extra::{
+///     either::Either3,
+///     extract::{BearerToken, FirstOf},
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Clone)]
+/// struct User {
+///     // set by a session middleware...
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct ApiKey {
+///     api_key: String,
+/// }
+///
+/// async fn handler(
+///     FirstOf(auth): FirstOf<(BearerToken, Extension<User>, Query<ApiKey>)>,
+/// ) {
+///     match auth {
+///         Either3::E1(token) => { /* ... */ }
+///         Either3::E2(Extension(user)) => { /* ... */ }
+///         Either3::E3(Query(api_key)) => { /* ... */ }
+///     }
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+pub struct FirstOf<T>(pub T::Output)
+where
+    T: FirstOfTuple;
+
+impl<T> fmt::Debug for FirstOf<T>
+where
+    T: FirstOfTuple,
+    T::Output: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FirstOf").field(&self.0).finish()
+    }
+}
+
+impl<T> Clone for FirstOf<T>
+where
+    T: FirstOfTuple,
+    T::Output: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Tuples of extractors that can be used with [`FirstOf`].
+///
+/// This trait is sealed and implemented for tuples with 2 to 8 elements.
+pub trait FirstOfTuple: sealed::Sealed {
+    /// The value extracted by [`FirstOf`].
+    type Output;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Rejection used for [`FirstOf`].
+///
+/// Contains a tuple with the rejections of all the extractors, in order.
+#[derive(Debug)]
+pub struct FirstOfRejection<R>(pub R);
+
+macro_rules! impl_first_of {
+    (
+        $either:ident =>
+        [$($ty:ident: $rejection:ident),* $(,)?],
+        $last_ty:ident: $last_rejection:ident $(,)?
+    ) => {
+        impl<$($ty),*, $last_ty> sealed::Sealed for ($($ty),*, $last_ty) {}
+
+        impl<$($ty),*, $last_ty> FirstOfTuple for ($($ty),*, $last_ty) {
+            type Output = $either<$($ty),*, $last_ty>;
+        }
+
+        #[async_trait]
+        impl<S, $($ty),*, $last_ty> FromRequestParts<S> for FirstOf<($($ty),*, $last_ty)>
+        where
+            $($ty: FromRequestParts<S>),*,
+            $last_ty: FromRequestParts<S>,
+            S: Send + Sync,
+        {
+            type Rejection = FirstOfRejection<($($ty::Rejection),*, $last_ty::Rejection)>;
+
+            #[allow(non_snake_case)]
+            async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+                $(
+                    let $rejection = match $ty::from_request_parts(parts, state).await {
+                        Ok(value) => return Ok(Self($either::$ty(value))),
+                        Err(rejection) => rejection,
+                    };
+                )*
+
+                match $last_ty::from_request_parts(parts, state).await {
+                    Ok(value) => Ok(Self($either::$last_ty(value))),
+                    Err($last_rejection) => {
+                        Err(FirstOfRejection(($($rejection),*, $last_rejection)))
+                    }
+                }
+            }
+        }
+
+        impl<$($rejection),*, $last_rejection> IntoResponse
+            for FirstOfRejection<($($rejection),*, $last_rejection)>
+        where
+            $last_rejection: IntoResponse,
+        {
+            fn into_response(self) -> Response {
+                let (.., last) = self.0;
+                last.into_response()
+            }
+        }
+
+        impl<$($rejection),*, $last_rejection> fmt::Display
+            for FirstOfRejection<($($rejection),*, $last_rejection)>
+        where
+            $last_rejection: fmt::Display,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let (.., last) = &self.0;
+                last.fmt(f)
+            }
+        }
+
+        impl<$($rejection),*, $last_rejection> std::error::Error
+            for FirstOfRejection<($($rejection),*, $last_rejection)>
+        where
+            $($rejection: fmt::Debug),*,
+            $last_rejection: std::error::Error + 'static,
+        {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                let (.., last) = &self.0;
+                Some(last)
+            }
+        }
+    };
+}
+
+impl_first_of!(Either => [E1: R1], E2: R2);
+impl_first_of!(Either3 => [E1: R1, E2: R2], E3: R3);
+impl_first_of!(Either4 => [E1: R1, E2: R2, E3: R3], E4: R4);
+impl_first_of!(Either5 => [E1: R1, E2: R2, E3: R3, E4: R4], E5: R5);
+impl_first_of!(Either6 => [E1: R1, E2: R2, E3: R3, E4: R4, E5: R5], E6: R6);
+impl_first_of!(Either7 => [E1: R1, E2: R2, E3: R3, E4: R4, E5: R5, E6: R6], E7: R7);
+impl_first_of!(Either8 => [E1: R1, E2: R2, E3: R3, E4: R4, E5: R5, E6: R6, E7: R7], E8: R8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use http::StatusCode;
+
+    struct Fails;
+
+    #[async_trait]
+    impl<S> FromRequestParts<S> for Fails
+    where
+        S: Send + Sync,
+    {
+        type Rejection = StatusCode;
+
+        async fn from_request_parts(_: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
+
+    struct Succeeds;
+
+    #[async_trait]
+    impl<S> FromRequestParts<S> for Succeeds
+    where
+        S: Send + Sync,
+    {
+        type Rejection = StatusCode;
+
+        async fn from_request_parts(_: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+            Ok(Self)
+        }
+    }
+
+    struct NeverRun;
+
+    #[async_trait]
+    impl<S> FromRequestParts<S> for NeverRun
+    where
+        S: Send + Sync,
+    {
+        type Rejection = StatusCode;
+
+        async fn from_request_parts(_: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+            panic!("extractors after the first one that succeeds must not run")
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_first_that_succeeds() {
+        async fn handler(FirstOf(value): FirstOf<(Fails, Succeeds, NeverRun)>) -> &'static str {
+            match value {
+                Either3::E1(_) => "first",
+                Either3::E2(_) => "second",
+                Either3::E3(_) => "third",
+            }
+        }
+
+        let client = TestClient::new(Router::new().route("/", get(handler)));
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "second");
+    }
+
+    #[tokio::test]
+    async fn rejects_if_all_fail() {
+        async fn handler(_: FirstOf<(Fails, Fails)>) {}
+
+        let client = TestClient::new(Router::new().route("/", get(handler)));
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+}
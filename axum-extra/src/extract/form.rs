@@ -6,7 +6,7 @@ use axum::{
 };
 use http::StatusCode;
 use serde::de::DeserializeOwned;
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
 /// Extractor that deserializes `application/x-www-form-urlencoded` requests
 /// into some type.
@@ -64,9 +64,55 @@ where
     }
 }
 
-/// Rejection used for [`Form`].
+/// Extractor that collects `application/x-www-form-urlencoded` requests into a map.
 ///
-/// Contains one variant for each way the [`Form`] extractor can fail.
+/// This is useful for dynamic forms whose fields aren't known ahead of time. Each field name maps
+/// to all of its values, in the order they were sent, so fields with multiple values such as
+/// checkboxes are preserved. Names and values are percent-decoded.
+///
+/// Like [`Form`], the body is only read for requests with a
+/// `Content-Type: application/x-www-form-urlencoded` header, and for `GET` and `HEAD` requests
+/// the query string is used instead. Other requests are rejected with
+/// `415 Unsupported Media Type`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum_extra::extract::FormMap;
+///
+/// async fn accept_form(FormMap(fields): FormMap) {
+///     for (name, values) in fields {
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg(feature = "form")]
+pub struct FormMap(pub HashMap<String, Vec<String>>);
+
+axum_core::__impl_deref!(FormMap: HashMap<String, Vec<String>>);
+
+#[async_trait]
+impl<S> FromRequest<S> for FormMap
+where
+    S: Send + Sync,
+{
+    type Rejection = FormRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Form(pairs) = Form::<Vec<(String, String)>>::from_request(req, state).await?;
+
+        let mut fields = HashMap::<_, Vec<_>>::new();
+        for (name, value) in pairs {
+            fields.entry(name).or_default().push(value);
+        }
+        Ok(Self(fields))
+    }
+}
+
+/// Rejection used for [`Form`] and [`FormMap`].
+///
+/// Contains one variant for each way the [`Form`] and [`FormMap`] extractors can fail.
 #[derive(Debug)]
 #[non_exhaustive]
 #[cfg(feature = "form")]
@@ -145,4 +191,49 @@ mod tests {
         assert_eq!(res.status(), StatusCode::OK);
         assert_eq!(res.text().await, "one,two");
     }
+
+    #[tokio::test]
+    async fn form_map() {
+        let app = Router::new().route(
+            "/",
+            post(|FormMap(fields): FormMap| async move {
+                let mut fields = fields.into_iter().collect::<Vec<_>>();
+                fields.sort();
+                format!("{fields:?}")
+            }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body("name=alice")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, r#"[("name", ["alice"])]"#);
+
+        let res = client
+            .post("/")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body("tag=a&name=bob&tag=b")
+            .await;
+        assert_eq!(
+            res.text().await,
+            r#"[("name", ["bob"]), ("tag", ["a", "b"])]"#
+        );
+
+        let res = client
+            .post("/")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body("full%20name=J%C3%BCrgen+M%C3%BCller&sym=%26%3D")
+            .await;
+        assert_eq!(
+            res.text().await,
+            r#"[("full name", ["Jürgen Müller"]), ("sym", ["&="])]"#
+        );
+
+        let res = client.post("/").body("name=alice").await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
 }
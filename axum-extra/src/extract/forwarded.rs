@@ -0,0 +1,336 @@
+use super::accept_encoding::is_token;
+use axum::{async_trait, extract::FromRequestParts};
+use axum_core::__define_rejection as define_rejection;
+use http::{header::FORWARDED, request::Parts};
+use std::net::{IpAddr, Ipv6Addr};
+
+/// Extractor that parses the [RFC 7239] `Forwarded` header.
+///
+/// Each proxy a request passed through adds an element to the header. The elements are returned
+/// in the order they appear in the request, so the first element describes the hop closest to
+/// the client. Multiple `Forwarded` headers are treated as one comma separated list.
+///
+/// If the header is missing the list is empty. If the header is malformed the request is
+/// rejected with `400 Bad Request`.
+///
+/// Note that the header is set by clients and proxies so it must only be trusted if the proxies
+/// in front of your application are known to overwrite or validate it.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{ForwardedHeader, ForwardedNode};
+///
+/// async fn handler(ForwardedHeader(elements): ForwardedHeader) {
+///     if let Some(ForwardedNode::Ip { ip, .. }) = elements.first().and_then(|e| e.for_.as_ref()) {
+///         // `ip` is the address of the client, according to the first proxy
+///     }
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+///
+/// [RFC 7239]: https://www.rfc-editor.org/rfc/rfc7239
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForwardedHeader(pub Vec<ForwardedElement>);
+
+/// A single element of the `Forwarded` header.
+///
+/// See [`ForwardedHeader`] for more details.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ForwardedElement {
+    /// The `for` parameter, identifying the node that made the request to the proxy.
+    pub for_: Option<ForwardedNode>,
+    /// The `by` parameter, identifying the interface where the request came in to the proxy.
+    pub by: Option<ForwardedNode>,
+    /// The `host` parameter, the `Host` header as received by the proxy.
+    pub host: Option<String>,
+    /// The `proto` parameter, the protocol used to make the request to the proxy.
+    pub proto: Option<String>,
+}
+
+/// A node identifier used in the `for` and `by` parameters of the `Forwarded` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForwardedNode {
+    /// An IP address, optionally with a port.
+    ///
+    /// Obfuscated ports, such as `_8080`, are discarded.
+    Ip {
+        /// The IP address.
+        ip: IpAddr,
+        /// The port, if one was given.
+        port: Option<u16>,
+    },
+    /// The `unknown` identifier, used when the proxy doesn't know or doesn't want to reveal the
+    /// node.
+    Unknown,
+    /// An obfuscated identifier, such as `_hidden`.
+    Obfuscated(String),
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ForwardedHeader
+where
+    S: Send + Sync,
+{
+    type Rejection = InvalidForwardedHeader;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let mut elements = Vec::new();
+
+        for value in parts.headers.get_all(FORWARDED) {
+            let value = value.to_str().map_err(|_| InvalidForwardedHeader)?;
+            parse_forwarded(value, &mut elements).ok_or(InvalidForwardedHeader)?;
+        }
+
+        Ok(Self(elements))
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to parse `Forwarded` header"]
+    /// Rejection type for [`ForwardedHeader`] used if the header is malformed.
+    pub struct InvalidForwardedHeader;
+}
+
+pub(super) fn parse_forwarded(value: &str, elements: &mut Vec<ForwardedElement>) -> Option<()> {
+    for element in split_unquoted(value, ',')? {
+        let element = element.trim();
+        if element.is_empty() {
+            continue;
+        }
+
+        let mut parsed = ForwardedElement::default();
+        for pair in split_unquoted(element, ';')? {
+            let (key, value) = pair.trim().split_once('=')?;
+            let value = unquote(value)?;
+
+            let slot_is_taken = match key.to_ascii_lowercase().as_str() {
+                "for" => parsed.for_.replace(ForwardedNode::parse(&value)?).is_some(),
+                "by" => parsed.by.replace(ForwardedNode::parse(&value)?).is_some(),
+                "host" => parsed.host.replace(value).is_some(),
+                "proto" => parsed.proto.replace(value).is_some(),
+                key if is_token(key) => false,
+                _ => return None,
+            };
+
+            // parameters must not occur more than once per element
+            if slot_is_taken {
+                return None;
+            }
+        }
+
+        elements.push(parsed);
+    }
+
+    Some(())
+}
+
+impl ForwardedNode {
+    fn parse(value: &str) -> Option<Self> {
+        if value.eq_ignore_ascii_case("unknown") {
+            return Some(Self::Unknown);
+        }
+
+        if value.starts_with('_') {
+            return is_obfuscated(value).then(|| Self::Obfuscated(value.to_owned()));
+        }
+
+        let (ip, port) = if let Some(rest) = value.strip_prefix('[') {
+            let (ip, rest) = rest.split_once(']')?;
+            let ip = ip.parse::<Ipv6Addr>().ok()?;
+            let port = match rest {
+                "" => None,
+                _ => Some(rest.strip_prefix(':')?),
+            };
+            (IpAddr::V6(ip), port)
+        } else {
+            let (ip, port) = match value.split_once(':') {
+                Some((ip, port)) => (ip, Some(port)),
+                None => (value, None),
+            };
+            (IpAddr::V4(ip.parse().ok()?), port)
+        };
+
+        let port = match port {
+            None => None,
+            Some(port) if port.starts_with('_') => {
+                is_obfuscated(port).then_some(())?;
+                None
+            }
+            Some(port) => Some(port.parse().ok()?),
+        };
+
+        Some(Self::Ip { ip, port })
+    }
+}
+
+fn is_obfuscated(value: &str) -> bool {
+    value.len() > 1
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+}
+
+/// Split `value` on `delimiter`, ignoring delimiters inside quoted strings.
+fn split_unquoted(value: &str, delimiter: char) -> Option<Vec<&str>> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (idx, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if in_quotes && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == delimiter && !in_quotes {
+            parts.push(&value[start..idx]);
+            start = idx + c.len_utf8();
+        }
+    }
+
+    if in_quotes {
+        return None;
+    }
+
+    parts.push(&value[start..]);
+    Some(parts)
+}
+
+/// Parse a parameter value, which is either a token or a quoted string.
+fn unquote(value: &str) -> Option<String> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        return is_token(value).then(|| value.to_owned());
+    };
+    let quoted = quoted.strip_suffix('"')?;
+
+    let mut out = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.push(chars.next()?),
+            '"' => return None,
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use http::StatusCode;
+    use std::net::Ipv4Addr;
+
+    async fn parse(values: &[&str]) -> Result<Vec<ForwardedElement>, InvalidForwardedHeader> {
+        let mut req = http::Request::builder();
+        for value in values {
+            req = req.header(FORWARDED, *value);
+        }
+        let (mut parts, ()) = req.body(()).unwrap().into_parts();
+        ForwardedHeader::from_request_parts(&mut parts, &())
+            .await
+            .map(|ForwardedHeader(elements)| elements)
+    }
+
+    #[tokio::test]
+    async fn multi_element_chain() {
+        let elements = parse(&[
+            "for=192.0.2.43;proto=https;host=example.com, for=198.51.100.17;by=203.0.113.60",
+            "for=unknown",
+        ])
+        .await
+        .unwrap();
+
+        assert_eq!(
+            elements,
+            vec![
+                ForwardedElement {
+                    for_: Some(ForwardedNode::Ip {
+                        ip: IpAddr::V4(Ipv4Addr::new(192, 0, 2, 43)),
+                        port: None,
+                    }),
+                    by: None,
+                    host: Some("example.com".to_owned()),
+                    proto: Some("https".to_owned()),
+                },
+                ForwardedElement {
+                    for_: Some(ForwardedNode::Ip {
+                        ip: IpAddr::V4(Ipv4Addr::new(198, 51, 100, 17)),
+                        port: None,
+                    }),
+                    by: Some(ForwardedNode::Ip {
+                        ip: IpAddr::V4(Ipv4Addr::new(203, 0, 113, 60)),
+                        port: None,
+                    }),
+                    host: None,
+                    proto: None,
+                },
+                ForwardedElement {
+                    for_: Some(ForwardedNode::Unknown),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn quoted_for_values() {
+        let elements =
+            parse(&[r#"For="[2001:db8:cafe::17]:4711", for="_hidden", for="192.0.2.43:_port""#])
+                .await
+                .unwrap();
+
+        let nodes = elements
+            .into_iter()
+            .map(|element| element.for_.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            nodes,
+            vec![
+                ForwardedNode::Ip {
+                    ip: "2001:db8:cafe::17".parse().unwrap(),
+                    port: Some(4711),
+                },
+                ForwardedNode::Obfuscated("_hidden".to_owned()),
+                ForwardedNode::Ip {
+                    ip: IpAddr::V4(Ipv4Addr::new(192, 0, 2, 43)),
+                    port: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn absent_header() {
+        assert!(parse(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn malformed_header() {
+        for value in [
+            "for",
+            "for=\"192.0.2.43",
+            "for=[2001:db8::1]",
+            "for=192.0.2.43;for=192.0.2.44",
+            "for=not-an-ip",
+        ] {
+            assert!(parse(&[value]).await.is_err(), "{value}");
+        }
+
+        let app = Router::new().route("/", get(|_: ForwardedHeader| async {}));
+        let client = TestClient::new(app);
+        let res = client.get("/").header("forwarded", "for").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,209 @@
+use axum::{async_trait, extract::FromRequestParts};
+use http::{request::Parts, HeaderMap};
+use std::{convert::Infallible, fmt, marker::PhantomData};
+
+/// The names of the headers a CDN or edge platform uses to pass the location of the client.
+///
+/// This is used as the provider tag of [`GeoLocation`]. Implementations are provided for
+/// [Cloudflare](CloudflareGeo), [Vercel](VercelGeo) and [CloudFront](CloudFrontGeo), and other
+/// providers can be supported by implementing this trait.
+pub trait GeoProvider {
+    /// The header containing the ISO 3166-1 alpha-2 country code.
+    const COUNTRY: &'static str;
+
+    /// The header containing the region, if the provider sends one.
+    const REGION: Option<&'static str>;
+
+    /// The header containing the city, if the provider sends one.
+    const CITY: Option<&'static str>;
+}
+
+/// Provider tag for the headers sent by Cloudflare.
+///
+/// The `CF-Region` and `CF-IPCity` headers are only sent if the "Add visitor location headers"
+/// managed transform is enabled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CloudflareGeo;
+
+impl GeoProvider for CloudflareGeo {
+    const COUNTRY: &'static str = "cf-ipcountry";
+    const REGION: Option<&'static str> = Some("cf-region");
+    const CITY: Option<&'static str> = Some("cf-ipcity");
+}
+
+/// Provider tag for the headers sent by Vercel.
+///
+/// Note that Vercel percent-encodes the city, which is passed through as is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VercelGeo;
+
+impl GeoProvider for VercelGeo {
+    const COUNTRY: &'static str = "x-vercel-ip-country";
+    const REGION: Option<&'static str> = Some("x-vercel-ip-country-region");
+    const CITY: Option<&'static str> = Some("x-vercel-ip-city");
+}
+
+/// Provider tag for the headers sent by Amazon CloudFront.
+///
+/// The headers are only sent if they're included in the origin request policy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CloudFrontGeo;
+
+impl GeoProvider for CloudFrontGeo {
+    const COUNTRY: &'static str = "cloudfront-viewer-country";
+    const REGION: Option<&'static str> = Some("cloudfront-viewer-country-region");
+    const CITY: Option<&'static str> = Some("cloudfront-viewer-city");
+}
+
+/// Extractor for the location of the client as determined by a CDN or edge platform.
+///
+/// The location is read from the headers injected by the provider given by `P`, which defaults
+/// to [`CloudflareGeo`]. Each field is `None` if the corresponding header is missing, empty or
+/// not valid UTF-8, or if the provider doesn't send it. The country is also `None` if it's
+/// `XX`, which Cloudflare uses for unknown locations. This extractor never rejects the request.
+///
+/// These headers are set by the provider and are only trustworthy if the app can't be reached
+/// without going through it.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{GeoLocation, VercelGeo};
+///
+/// async fn handler(location: GeoLocation<VercelGeo>) {
+///     if location.country.as_deref() == Some("DE") {
+///         // ...
+///     }
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+pub struct GeoLocation<P = CloudflareGeo> {
+    /// The ISO 3166-1 alpha-2 country code, such as `US`.
+    pub country: Option<String>,
+    /// The region, such as a state or province, in the format used by the provider.
+    pub region: Option<String>,
+    /// The city.
+    pub city: Option<String>,
+    _provider: PhantomData<fn() -> P>,
+}
+
+impl<P> GeoLocation<P>
+where
+    P: GeoProvider,
+{
+    /// Read the location from the headers of provider `P`.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: Option<&str>| {
+            let value = headers.get(name?)?.to_str().ok()?.trim();
+            (!value.is_empty()).then(|| value.to_owned())
+        };
+
+        Self {
+            country: header(Some(P::COUNTRY)).filter(|country| country != "XX"),
+            region: header(P::REGION),
+            city: header(P::CITY),
+            _provider: PhantomData,
+        }
+    }
+}
+
+impl<P> fmt::Debug for GeoLocation<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GeoLocation")
+            .field("country", &self.country)
+            .field("region", &self.region)
+            .field("city", &self.city)
+            .finish()
+    }
+}
+
+impl<P> Clone for GeoLocation<P> {
+    fn clone(&self) -> Self {
+        Self {
+            country: self.country.clone(),
+            region: self.region.clone(),
+            city: self.city.clone(),
+            _provider: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<S, P> FromRequestParts<S> for GeoLocation<P>
+where
+    S: Send + Sync,
+    P: GeoProvider,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    async fn handler<P>(location: GeoLocation<P>) -> String
+    where
+        P: GeoProvider,
+    {
+        format!(
+            "{:?} {:?} {:?}",
+            location.country, location.region, location.city
+        )
+    }
+
+    #[tokio::test]
+    async fn cloudflare() {
+        let app = Router::new().route("/", get(handler::<CloudflareGeo>));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("cf-ipcountry", "US")
+            .header("cf-region", "California")
+            .header("cf-ipcity", "San Francisco")
+            .await;
+        assert_eq!(
+            res.text().await,
+            r#"Some("US") Some("California") Some("San Francisco")"#
+        );
+
+        let res = client.get("/").header("cf-ipcountry", "XX").await;
+        assert_eq!(res.text().await, "None None None");
+    }
+
+    #[tokio::test]
+    async fn vercel() {
+        let app = Router::new().route("/", get(handler::<VercelGeo>));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("x-vercel-ip-country", "DE")
+            .header("x-vercel-ip-country-region", "BE")
+            // headers of other providers are ignored
+            .header("cf-ipcity", "San Francisco")
+            .await;
+        assert_eq!(res.text().await, r#"Some("DE") Some("BE") None"#);
+    }
+
+    #[tokio::test]
+    async fn missing_headers() {
+        let app = Router::new().route("/", get(handler::<VercelGeo>));
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "None None None");
+    }
+}
@@ -0,0 +1,269 @@
+use axum::{async_trait, extract::FromRequestParts};
+use axum_core::__define_rejection as define_rejection;
+use http::{header::IF_RANGE, request::Parts, HeaderValue};
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Extractor for the `If-Range` header, as defined in [RFC 7233].
+///
+/// Contains `None` if the header is missing. Otherwise the header must either be an entity tag
+/// or an HTTP date, other values are rejected with `400 Bad Request`.
+///
+/// A `Range` request with an `If-Range` header should only be served partially if the
+/// representation hasn't changed, which can be checked with [`IfRange::matches`]. Otherwise the
+/// whole representation should be sent.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{EntityTag, IfRangeHeader};
+///
+/// async fn download(IfRangeHeader(if_range): IfRangeHeader) {
+///     let etag = EntityTag::parse("\"v2\"").unwrap();
+///
+///     let partial = if_range.map_or(true, |if_range| if_range.matches(Some(&etag), None));
+///     if partial {
+///         // serve the requested range
+///     } else {
+///         // serve the whole file
+///     }
+/// }
+///
+/// let app = Router::new().route("/file", get(download));
+/// # let _: Router = app;
+/// ```
+///
+/// [RFC 7233]: https://datatracker.ietf.org/doc/html/rfc7233#section-3.2
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "if-range")))]
+pub struct IfRangeHeader(pub Option<IfRange>);
+
+/// The value of an `If-Range` header.
+///
+/// See [`IfRangeHeader`] for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "if-range")))]
+pub enum IfRange {
+    /// An entity tag, such as `"xyzzy"`.
+    EntityTag(EntityTag),
+    /// An HTTP date, such as `Sun, 06 Nov 1994 08:49:37 GMT`.
+    Date(SystemTime),
+}
+
+impl IfRange {
+    /// Parse the value of an `If-Range` header.
+    pub fn from_header_value(value: &HeaderValue) -> Option<Self> {
+        let value = value.to_str().ok()?.trim();
+        if value.starts_with('"') || value.starts_with("W/") {
+            EntityTag::parse(value).map(Self::EntityTag)
+        } else {
+            httpdate::parse_http_date(value).ok().map(Self::Date)
+        }
+    }
+
+    /// Returns `true` if the representation described by `etag` and `last_modified` is the one
+    /// the client has, meaning the requested range can be served.
+    ///
+    /// Entity tags are compared with the strong comparison, so a weak entity tag never matches.
+    /// Dates match if they're equal to `last_modified` to the second.
+    pub fn matches(&self, etag: Option<&EntityTag>, last_modified: Option<SystemTime>) -> bool {
+        match self {
+            Self::EntityTag(tag) => etag.map_or(false, |etag| tag.strong_eq(etag)),
+            Self::Date(date) => last_modified.map_or(false, |last_modified| {
+                let secs = |time: SystemTime| {
+                    time.duration_since(UNIX_EPOCH)
+                        .map(|duration| duration.as_secs())
+                        .ok()
+                };
+                secs(*date).is_some() && secs(*date) == secs(last_modified)
+            }),
+        }
+    }
+}
+
+/// An entity tag, as used in the `ETag`, `If-Match`, `If-None-Match` and `If-Range` headers.
+///
+/// See [RFC 7232] for more details.
+///
+/// [RFC 7232]: https://datatracker.ietf.org/doc/html/rfc7232#section-2.3
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(docsrs, doc(cfg(feature = "if-range")))]
+pub struct EntityTag {
+    weak: bool,
+    tag: String,
+}
+
+impl EntityTag {
+    /// Parse an entity tag, such as `"xyzzy"` or `W/"xyzzy"`.
+    ///
+    /// Returns `None` if the tag isn't quoted or contains characters that aren't allowed.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (weak, quoted) = match value.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, value),
+        };
+
+        let tag = quoted.strip_prefix('"')?.strip_suffix('"')?;
+        if !tag.bytes().all(|b| b == 0x21 || (0x23..=0x7e).contains(&b)) {
+            return None;
+        }
+
+        Some(Self {
+            weak,
+            tag: tag.to_owned(),
+        })
+    }
+
+    /// Returns `true` if this is a weak entity tag.
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Returns the tag, without the quotes and weakness indicator.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Compare two entity tags using the strong comparison.
+    ///
+    /// Two entity tags are equivalent if neither of them is weak and their tags are equal.
+    pub fn strong_eq(&self, other: &Self) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Compare two entity tags using the weak comparison.
+    ///
+    /// Two entity tags are equivalent if their tags are equal, regardless of whether either of
+    /// them is weak.
+    pub fn weak_eq(&self, other: &Self) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl fmt::Display for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            f.write_str("W/")?;
+        }
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for IfRangeHeader
+where
+    S: Send + Sync,
+{
+    type Rejection = InvalidIfRange;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.headers.get(IF_RANGE) {
+            Some(value) => IfRange::from_header_value(value)
+                .map(|if_range| Self(Some(if_range)))
+                .ok_or(InvalidIfRange),
+            None => Ok(Self(None)),
+        }
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Invalid `If-Range` header"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "if-range")))]
+    /// Rejection type for [`IfRangeHeader`] used if the `If-Range` header is malformed.
+    pub struct InvalidIfRange;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use http::StatusCode;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn entity_tags() {
+        let app = Router::new().route(
+            "/",
+            get(|IfRangeHeader(if_range): IfRangeHeader| async move {
+                match if_range {
+                    Some(IfRange::EntityTag(tag)) if tag.is_weak() => format!("weak {}", tag.tag()),
+                    Some(IfRange::EntityTag(tag)) => format!("strong {}", tag.tag()),
+                    _ => "none".to_owned(),
+                }
+            }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("if-range", "\"x\"").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "strong x");
+
+        let res = client.get("/").header("if-range", "W/\"x\"").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "weak x");
+
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "none");
+    }
+
+    #[tokio::test]
+    async fn date() {
+        let app = Router::new().route(
+            "/",
+            get(|IfRangeHeader(if_range): IfRangeHeader| async move {
+                matches!(if_range, Some(IfRange::Date(_))).to_string()
+            }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("if-range", "Sun, 06 Nov 1994 08:49:37 GMT")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "true");
+    }
+
+    #[tokio::test]
+    async fn malformed() {
+        let app = Router::new().route("/", get(|_: IfRangeHeader| async {}));
+
+        let client = TestClient::new(app);
+
+        for value in ["x", "\"x", "W/x", "\"a\"b\"", "yesterday"] {
+            let res = client.get("/").header("if-range", value).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{value}");
+        }
+    }
+
+    #[test]
+    fn comparison() {
+        let strong = EntityTag::parse("\"x\"").unwrap();
+        let weak = EntityTag::parse("W/\"x\"").unwrap();
+        let other = EntityTag::parse("\"y\"").unwrap();
+
+        assert!(strong.strong_eq(&strong));
+        assert!(!strong.strong_eq(&weak));
+        assert!(!weak.strong_eq(&weak));
+        assert!(strong.weak_eq(&weak));
+        assert!(weak.weak_eq(&weak));
+        assert!(!strong.weak_eq(&other));
+        assert_eq!(weak.to_string(), "W/\"x\"");
+
+        assert!(IfRange::EntityTag(strong.clone()).matches(Some(&strong), None));
+        assert!(!IfRange::EntityTag(weak.clone()).matches(Some(&weak), None));
+
+        let date = UNIX_EPOCH + Duration::from_secs(784111777);
+        let if_range = IfRange::Date(date);
+        assert!(if_range.matches(None, Some(date + Duration::from_millis(500))));
+        assert!(!if_range.matches(None, Some(date + Duration::from_secs(1))));
+        assert!(!if_range.matches(Some(&strong), None));
+    }
+}
@@ -0,0 +1,157 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, FromRequestParts, Request},
+};
+use http::request::Parts;
+use std::{any::type_name, future::Future, time::Instant};
+use tracing::{field::Empty, Instrument, Span};
+
+/// Extractor that runs another extractor inside a [`tracing`] span.
+///
+/// The span is called `extract` and has the following fields:
+///
+/// - `extractor`: The type name of the wrapped extractor.
+/// - `success`: Whether the extraction succeeded.
+/// - `duration_us`: How long the extraction took, in microseconds.
+///
+/// This is useful for finding out which extractors are slow. If the span is disabled, for example
+/// because no subscriber is interested in it, the extraction isn't timed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Json, Router};
+/// use axum_extra::extract::Instrumented;
+/// use serde_json::Value;
+///
+/// async fn handler(Instrumented(Json(payload)): Instrumented<Json<Value>>) {
+///     // ...
+/// }
+///
+/// let app = Router::new().route("/", post(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Instrumented<E>(pub E);
+
+#[async_trait]
+impl<E, S> FromRequest<S> for Instrumented<E>
+where
+    S: Send + Sync,
+    E: FromRequest<S>,
+{
+    type Rejection = E::Rejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        instrument(E::from_request(req, state)).await.map(Self)
+    }
+}
+
+#[async_trait]
+impl<E, S> FromRequestParts<S> for Instrumented<E>
+where
+    S: Send + Sync,
+    E: FromRequestParts<S>,
+{
+    type Rejection = E::Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        instrument(E::from_request_parts(parts, state))
+            .await
+            .map(Self)
+    }
+}
+
+axum_core::__impl_deref!(Instrumented);
+
+async fn instrument<E, R, F>(future: F) -> Result<E, R>
+where
+    F: Future<Output = Result<E, R>>,
+{
+    let span = tracing::info_span!(
+        "extract",
+        extractor = type_name::<E>(),
+        success = Empty,
+        duration_us = Empty,
+    );
+
+    if span.is_disabled() {
+        return future.await;
+    }
+
+    let start = Instant::now();
+    let result = future.instrument(span.clone()).await;
+    record(&span, result.is_ok(), start);
+    result
+}
+
+fn record(span: &Span, success: bool, start: Instant) {
+    span.record("success", success);
+    span.record("duration_us", start.elapsed().as_micros() as u64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use http::Method;
+    use std::sync::{Arc, Mutex};
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Subscriber,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+    #[derive(Default, Clone)]
+    struct RecordFields(Arc<Mutex<Vec<(String, String)>>>);
+
+    impl Visit for RecordFields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((field.name().to_owned(), format!("{value:?}")));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((field.name().to_owned(), value.to_owned()));
+        }
+    }
+
+    impl<S> Layer<S> for RecordFields
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            if attrs.metadata().name() == "extract" {
+                attrs.record(&mut self.clone());
+            }
+        }
+
+        fn on_record(&self, _id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            values.record(&mut self.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn emits_span_with_extractor_name() {
+        let fields = RecordFields::default();
+        let subscriber = tracing_subscriber::registry().with(fields.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (mut parts, _) = Request::new(Body::empty()).into_parts();
+        let Instrumented(method) = Instrumented::<Method>::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+        assert_eq!(method, Method::GET);
+
+        let fields = fields.0.lock().unwrap().clone();
+        assert!(fields.contains(&("extractor".to_owned(), type_name::<Method>().to_owned())));
+        assert!(fields.contains(&("success".to_owned(), "true".to_owned())));
+        assert!(fields.iter().any(|(name, _)| name == "duration_us"));
+    }
+}
@@ -0,0 +1,508 @@
+use axum::{
+    async_trait,
+    body::BodyDataStream,
+    extract::{FromRequest, Request},
+    RequestExt,
+};
+use axum_core::__composite_rejection as composite_rejection;
+use axum_core::__define_rejection as define_rejection;
+use futures_util::stream::{self, BoxStream, Stream, StreamExt};
+use serde::de::{DeserializeOwned, Error as _};
+use std::{
+    collections::VecDeque,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Extractor that streams the elements of a JSON array in the request body.
+///
+/// The body is parsed as it arrives and each element of the top-level array is deserialized into
+/// a `T` as soon as it's complete, so only one element at a time is held in memory, no matter how
+/// large the array is. This makes it possible to accept large uploads, such as bulk imports,
+/// without buffering the whole body.
+///
+/// Each element may be at most `MAX_ELEMENT_SIZE` bytes (2 MB unless specified) long, and the
+/// whole body is limited by [`DefaultBodyLimit`], which can be raised for routes accepting large
+/// arrays.
+///
+/// If the body doesn't start with a JSON array the request is rejected with `400 Bad Request`.
+/// Errors found later are yielded by the stream. If an element can't be deserialized into a `T`
+/// the stream continues with the next element, while after malformed JSON, an element larger than
+/// `MAX_ELEMENT_SIZE`, a body larger than the body limit, or a body that ends before the array is
+/// closed, the stream ends.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{extract::DefaultBodyLimit, routing::post, Router};
+/// use axum_extra::extract::JsonArrayStream;
+/// use futures_util::stream::StreamExt;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// async fn import_users(mut users: JsonArrayStream<User>) {
+///     while let Some(user) = users.next().await {
+///         match user {
+///             Ok(user) => { /* store the user */ }
+///             Err(err) => { /* the body was malformed */ }
+///         }
+///     }
+/// }
+///
+/// let app = Router::new()
+///     .route("/users/import", post(import_users))
+///     // accept up to 1 GB of users
+///     .layer(DefaultBodyLimit::max(1_000_000_000));
+/// # let _: Router = app;
+/// ```
+///
+/// [`DefaultBodyLimit`]: axum::extract::DefaultBodyLimit
+#[cfg_attr(docsrs, doc(cfg(feature = "json-array-stream")))]
+#[must_use]
+pub struct JsonArrayStream<T, const MAX_ELEMENT_SIZE: usize = 2_097_152> {
+    stream: BoxStream<'static, Result<T, axum::Error>>,
+}
+
+#[async_trait]
+impl<T, S, const MAX_ELEMENT_SIZE: usize> FromRequest<S> for JsonArrayStream<T, MAX_ELEMENT_SIZE>
+where
+    T: DeserializeOwned + Send + 'static,
+    S: Send + Sync,
+{
+    type Rejection = JsonArrayStreamRejection;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let mut state = State {
+            body: req.into_limited_body().into_data_stream(),
+            splitter: Splitter::new(MAX_ELEMENT_SIZE),
+            ready: VecDeque::new(),
+            done: false,
+        };
+
+        // read until the start of the body so requests that don't contain an array can be
+        // rejected before the handler runs
+        while state.splitter.phase == Phase::Start {
+            match state.body.next().await {
+                Some(Ok(chunk)) => state.feed(&chunk),
+                Some(Err(err)) => return Err(JsonArrayBodyError::from_err(err).into()),
+                None => return Err(NotAJsonArray.into()),
+            }
+        }
+        if state.splitter.phase == Phase::NotAnArray {
+            return Err(NotAJsonArray.into());
+        }
+
+        let stream = stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.ready.pop_front() {
+                    return Some((item, state));
+                }
+                if state.done {
+                    return None;
+                }
+
+                match state.body.next().await {
+                    Some(Ok(chunk)) => state.feed(&chunk),
+                    Some(Err(err)) => state.fail(err),
+                    None => {
+                        if state.splitter.phase != Phase::Done {
+                            let err = serde_json::Error::custom("unexpected end of JSON array");
+                            state.fail(axum::Error::new(err));
+                        }
+                        state.done = true;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            stream: stream.boxed(),
+        })
+    }
+}
+
+impl<T, const MAX_ELEMENT_SIZE: usize> Stream for JsonArrayStream<T, MAX_ELEMENT_SIZE> {
+    type Item = Result<T, axum::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+impl<T, const MAX_ELEMENT_SIZE: usize> fmt::Debug for JsonArrayStream<T, MAX_ELEMENT_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonArrayStream").finish_non_exhaustive()
+    }
+}
+
+struct State<T> {
+    body: BodyDataStream,
+    splitter: Splitter,
+    ready: VecDeque<Result<T, axum::Error>>,
+    done: bool,
+}
+
+impl<T> State<T>
+where
+    T: DeserializeOwned,
+{
+    fn feed(&mut self, chunk: &[u8]) {
+        if self.done {
+            return;
+        }
+
+        let ready = &mut self.ready;
+        let result = self.splitter.feed(chunk, &mut |element| {
+            ready.push_back(serde_json::from_slice(element).map_err(axum::Error::new));
+        });
+
+        if let Err(err) = result {
+            self.fail(axum::Error::new(err));
+        }
+    }
+
+    fn fail(&mut self, err: axum::Error) {
+        self.ready.push_back(Err(err));
+        self.done = true;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Before the opening `[`.
+    Start,
+    /// The body didn't start with `[`.
+    NotAnArray,
+    /// After the opening `[`, expecting the first element or `]`.
+    First,
+    /// After a `,`, expecting an element.
+    Next,
+    /// Inside an element.
+    Element,
+    /// After an element, expecting `,` or `]`.
+    AfterElement,
+    /// After the closing `]`.
+    Done,
+}
+
+/// Splits the bytes of a JSON array into the bytes of its elements, without parsing the elements.
+///
+/// Only the structure needed to find where elements end is tracked: nesting depth and whether
+/// the current byte is inside a string. The elements themselves are validated when they're
+/// deserialized.
+#[derive(Debug)]
+struct Splitter {
+    phase: Phase,
+    element: Vec<u8>,
+    max_element_size: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl Splitter {
+    fn new(max_element_size: usize) -> Self {
+        Self {
+            phase: Phase::Start,
+            element: Vec::new(),
+            max_element_size,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+        }
+    }
+
+    fn feed(
+        &mut self,
+        chunk: &[u8],
+        on_element: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), serde_json::Error> {
+        for &b in chunk {
+            match self.phase {
+                _ if b.is_ascii_whitespace() && self.phase != Phase::Element => {}
+                Phase::Start if b == b'[' => self.phase = Phase::First,
+                Phase::Start => {
+                    self.phase = Phase::NotAnArray;
+                    return Ok(());
+                }
+                Phase::NotAnArray => return Ok(()),
+                Phase::First if b == b']' => self.phase = Phase::Done,
+                Phase::First | Phase::Next => {
+                    if b == b']' || b == b',' {
+                        return Err(serde_json::Error::custom("expected an array element"));
+                    }
+                    self.start_element(b);
+                }
+                Phase::Element => self.continue_element(b, on_element)?,
+                Phase::AfterElement if b == b',' => self.phase = Phase::Next,
+                Phase::AfterElement if b == b']' => self.phase = Phase::Done,
+                Phase::AfterElement => {
+                    return Err(serde_json::Error::custom("expected `,` or `]`"));
+                }
+                Phase::Done => {
+                    return Err(serde_json::Error::custom(
+                        "trailing characters after JSON array",
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start_element(&mut self, b: u8) {
+        self.element.clear();
+        self.element.push(b);
+        self.phase = Phase::Element;
+        match b {
+            b'{' | b'[' => self.depth = 1,
+            b'"' => self.in_string = true,
+            _ => {}
+        }
+    }
+
+    fn continue_element(
+        &mut self,
+        b: u8,
+        on_element: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), serde_json::Error> {
+        if self.in_string {
+            self.push(b)?;
+            if self.escaped {
+                self.escaped = false;
+            } else if b == b'\\' {
+                self.escaped = true;
+            } else if b == b'"' {
+                self.in_string = false;
+                if self.depth == 0 {
+                    self.end_element(on_element);
+                }
+            }
+        } else if self.depth > 0 {
+            self.push(b)?;
+            match b {
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        self.end_element(on_element);
+                    }
+                }
+                b'"' => self.in_string = true,
+                _ => {}
+            }
+        } else if b == b',' || b == b']' || b.is_ascii_whitespace() {
+            // numbers and literals end at the next delimiter
+            self.end_element(on_element);
+            return self.feed(&[b], on_element);
+        } else {
+            self.push(b)?;
+        }
+
+        Ok(())
+    }
+
+    fn push(&mut self, b: u8) -> Result<(), serde_json::Error> {
+        if self.element.len() >= self.max_element_size {
+            return Err(serde_json::Error::custom("array element is too large"));
+        }
+        self.element.push(b);
+        Ok(())
+    }
+
+    fn end_element(&mut self, on_element: &mut dyn FnMut(&[u8])) {
+        on_element(&self.element);
+        self.element.clear();
+        self.phase = Phase::AfterElement;
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Expected the request body to be a JSON array"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json-array-stream")))]
+    /// Rejection type for [`JsonArrayStream`] used if the request body doesn't start with a
+    /// JSON array.
+    pub struct NotAJsonArray;
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to read the request body"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json-array-stream")))]
+    /// Rejection type for [`JsonArrayStream`] used if reading the start of the request body
+    /// failed.
+    pub struct JsonArrayBodyError(Error);
+}
+
+composite_rejection! {
+    /// Rejection used for [`JsonArrayStream`].
+    ///
+    /// Contains one variant for each way the [`JsonArrayStream`] extractor
+    /// can fail.
+    #[cfg_attr(docsrs, doc(cfg(feature = "json-array-stream")))]
+    pub enum JsonArrayStreamRejection {
+        NotAJsonArray,
+        JsonArrayBodyError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::DefaultBodyLimit, routing::post, Router};
+    use bytes::Bytes;
+    use http::StatusCode;
+    use http_body_util::BodyExt;
+    use serde::Deserialize;
+    use std::convert::Infallible;
+    use tower::ServiceExt;
+
+    #[derive(Debug, PartialEq, Eq, Deserialize)]
+    struct User {
+        id: u32,
+        name: String,
+    }
+
+    async fn extract<B>(chunks: B) -> Result<JsonArrayStream<User>, JsonArrayStreamRejection>
+    where
+        B: Stream<Item = Result<Bytes, Infallible>> + Send + 'static,
+    {
+        let req = Request::new(Body::from_stream(chunks));
+        JsonArrayStream::from_request(req, &()).await
+    }
+
+    fn chunks(body: &str, size: usize) -> Vec<Result<Bytes, Infallible>> {
+        body.as_bytes()
+            .chunks(size)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn streams_large_array() {
+        let users = (0..10_000)
+            .map(|id| format!(r#"{{"id": {id}, "name": "user \"{id}\" [{{"}}"#))
+            .collect::<Vec<_>>();
+        let body = format!("[{}]", users.join(",\n  "));
+
+        let stream = extract(stream::iter(chunks(&body, 7))).await.unwrap();
+        let users = stream.collect::<Vec<_>>().await;
+        assert_eq!(users.len(), 10_000);
+        for (id, user) in users.into_iter().enumerate() {
+            let user = user.unwrap();
+            assert_eq!(user.id, id as u32);
+            assert_eq!(user.name, format!("user \"{id}\" [{{"));
+        }
+    }
+
+    #[tokio::test]
+    async fn yields_elements_before_body_ends() {
+        let first = r#"[{"id": 1, "name": "a"}, {"id": 2, "#;
+        // the body never ends, so nothing could be yielded if it was buffered
+        let body = stream::iter(chunks(first, 5)).chain(stream::pending());
+
+        let mut stream = extract(body).await.unwrap();
+        let user = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            user,
+            User {
+                id: 1,
+                name: "a".to_owned(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn scalars() {
+        let req = Request::new(Body::from(r#"[1, -2.5e3,"x,]" , true,null ,[]]"#));
+        let stream = JsonArrayStream::<serde_json::Value>::from_request(req, &())
+            .await
+            .unwrap();
+        let values = stream
+            .map(Result::unwrap)
+            .collect::<Vec<serde_json::Value>>()
+            .await;
+        assert_eq!(
+            values,
+            serde_json::json!([1, -2.5e3, "x,]", true, null, []])
+                .as_array()
+                .unwrap()
+                .clone()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_non_arrays() {
+        for body in [r#"{"id": 1}"#, "", "  "] {
+            let err = extract(stream::iter(chunks(body, 1))).await.unwrap_err();
+            assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn errors_end_the_stream() {
+        for body in [
+            r#"[{"id": 1, "name": "a"} {"id": 2, "name": "b"}]"#,
+            r#"[{"id": 1, "name": "a"}, {"id": 2"#,
+            r#"[{"id": 1, "name": "a"},]"#,
+        ] {
+            let mut stream = extract(stream::iter(chunks(body, 3))).await.unwrap();
+            assert!(stream.next().await.unwrap().is_ok(), "{body}");
+            assert!(stream.next().await.unwrap().is_err(), "{body}");
+            assert!(stream.next().await.is_none(), "{body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_elements_dont_end_the_stream() {
+        let body = r#"[{"id": 1, "name": "a"}, {"id": "two"}, {"id": 3, "name": "c"}]"#;
+        let stream = extract(stream::iter(chunks(body, 4))).await.unwrap();
+        let users = stream.collect::<Vec<_>>().await;
+        assert_eq!(users.len(), 3);
+        assert!(users[0].is_ok());
+        assert!(users[1].is_err());
+        assert_eq!(users[2].as_ref().unwrap().id, 3);
+    }
+
+    #[tokio::test]
+    async fn too_large_element() {
+        let body = r#"[{"id": 1, "name": "a"}, {"id": 2, "name": "a longer name"}]"#;
+        let req = Request::new(Body::from_stream(stream::iter(chunks(body, 8))));
+
+        let mut stream = JsonArrayStream::<User, 32>::from_request(req, &())
+            .await
+            .unwrap();
+        assert!(stream.next().await.unwrap().is_ok());
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn body_limit() {
+        let app = Router::new()
+            .route(
+                "/",
+                post(|stream: JsonArrayStream<User>| async move {
+                    let users = stream.collect::<Vec<_>>().await;
+                    let ended_with_error = users.last().map_or(false, Result::is_err);
+                    format!("{} {ended_with_error}", users.len())
+                }),
+            )
+            .layer(DefaultBodyLimit::max(256));
+
+        let body = format!("[{}]", vec![r#"{"id": 1, "name": "a"}"#; 100].join(","));
+        let req = http::Request::post("/")
+            .body(Body::from_stream(stream::iter(chunks(&body, 64))))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        // the elements within the limit are yielded, followed by an error
+        assert_eq!(body, "12 true");
+    }
+}
@@ -0,0 +1,253 @@
+use super::{BearerToken, BearerTokenRejection};
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    response::{IntoResponse, Response},
+};
+use http::{header, request::Parts, StatusCode};
+use jsonwebtoken::{errors::ErrorKind, DecodingKey, Validation};
+use serde::de::DeserializeOwned;
+use std::{fmt, sync::Arc};
+
+/// Extractor that verifies a JSON Web Token from an `Authorization: Bearer <token>` header and
+/// deserializes its claims.
+///
+/// The token is extracted with [`BearerToken`] and verified with the key and [`Validation`] of the
+/// [`JwtConfig`], which must be accessible from the state via [`FromRef`]. If the header is
+/// missing, or the token is invalid or expired, the request is rejected with `401 Unauthorized`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{Jwt, JwtConfig};
+/// use jsonwebtoken::{DecodingKey, Validation};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Claims {
+///     sub: String,
+///     exp: u64,
+/// }
+///
+/// async fn handler(Jwt(claims): Jwt<Claims>) -> String {
+///     format!("Hello, {}", claims.sub)
+/// }
+///
+/// let config = JwtConfig::new(DecodingKey::from_secret(b"secret"), Validation::default());
+///
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .with_state(config);
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Jwt<C>(pub C);
+
+#[async_trait]
+impl<S, C> FromRequestParts<S> for Jwt<C>
+where
+    S: Send + Sync,
+    JwtConfig: FromRef<S>,
+    C: DeserializeOwned,
+{
+    type Rejection = JwtRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let BearerToken(token) = BearerToken::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| match rejection {
+                BearerTokenRejection::Missing => JwtRejection::Missing,
+                _ => JwtRejection::Malformed,
+            })?;
+
+        let config = JwtConfig::from_ref(state);
+        let data = jsonwebtoken::decode::<C>(&token, &config.0.key, &config.0.validation).map_err(
+            |err| match err.kind() {
+                ErrorKind::ExpiredSignature => JwtRejection::Expired,
+                ErrorKind::InvalidSignature => JwtRejection::InvalidSignature,
+                _ => JwtRejection::Invalid(err),
+            },
+        )?;
+
+        Ok(Self(data.claims))
+    }
+}
+
+axum_core::__impl_deref!(Jwt);
+
+/// The key and validation settings used by [`Jwt`] to verify tokens.
+///
+/// Cloning a `JwtConfig` is cheap.
+#[derive(Clone)]
+pub struct JwtConfig(Arc<JwtConfigInner>);
+
+struct JwtConfigInner {
+    key: DecodingKey,
+    validation: Validation,
+}
+
+impl JwtConfig {
+    /// Create a new `JwtConfig`.
+    pub fn new(key: DecodingKey, validation: Validation) -> Self {
+        Self(Arc::new(JwtConfigInner { key, validation }))
+    }
+}
+
+impl fmt::Debug for JwtConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtConfig")
+            .field("validation", &self.0.validation)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Rejection used for [`Jwt`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum JwtRejection {
+    /// The `Authorization` header was missing.
+    Missing,
+    /// The `Authorization` header didn't contain a bearer token.
+    Malformed,
+    /// The token has expired.
+    Expired,
+    /// The signature of the token didn't match.
+    InvalidSignature,
+    /// The token was invalid for another reason, for example it couldn't be decoded or failed
+    /// validation.
+    Invalid(jsonwebtoken::errors::Error),
+}
+
+impl IntoResponse for JwtRejection {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            self.to_string(),
+        )
+            .into_response()
+    }
+}
+
+impl fmt::Display for JwtRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "Missing `Authorization` header"),
+            Self::Malformed => write!(f, "`Authorization` header didn't contain a bearer token"),
+            Self::Expired => write!(f, "Token has expired"),
+            Self::InvalidSignature => write!(f, "Token has an invalid signature"),
+            Self::Invalid(err) => write!(f, "Invalid token: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for JwtRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Missing | Self::Malformed | Self::Expired | Self::InvalidSignature => None,
+            Self::Invalid(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Claims {
+        sub: String,
+        exp: u64,
+    }
+
+    fn config() -> JwtConfig {
+        JwtConfig::new(DecodingKey::from_secret(b"secret"), Validation::default())
+    }
+
+    fn token(secret: &[u8], exp: u64) -> String {
+        let claims = Claims {
+            sub: "alice".to_owned(),
+            exp,
+        };
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(secret),
+        )
+        .unwrap();
+        format!("Bearer {token}")
+    }
+
+    fn in_an_hour() -> u64 {
+        jsonwebtoken::get_current_timestamp() + 3600
+    }
+
+    #[tokio::test]
+    async fn valid_token() {
+        let app = Router::new()
+            .route("/", get(|Jwt(claims): Jwt<Claims>| async { claims.sub }))
+            .with_state(config());
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("authorization", token(b"secret", in_an_hour()))
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "alice");
+    }
+
+    #[tokio::test]
+    async fn expired_token() {
+        let app = Router::new()
+            .route("/", get(|_: Jwt<Claims>| async {}))
+            .with_state(config());
+
+        let client = TestClient::new(app);
+
+        // further in the past than the default leeway of 60 seconds
+        let exp = jsonwebtoken::get_current_timestamp() - 3600;
+        let res = client
+            .get("/")
+            .header("authorization", token(b"secret", exp))
+            .await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.text().await, JwtRejection::Expired.to_string());
+    }
+
+    #[tokio::test]
+    async fn bad_signature() {
+        let app = Router::new()
+            .route("/", get(|_: Jwt<Claims>| async {}))
+            .with_state(config());
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("authorization", token(b"other secret", in_an_hour()))
+            .await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.text().await, JwtRejection::InvalidSignature.to_string());
+    }
+
+    #[tokio::test]
+    async fn missing_header() {
+        let app = Router::new()
+            .route("/", get(|_: Jwt<Claims>| async {}))
+            .with_state(config());
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.headers()["www-authenticate"], "Bearer");
+        assert_eq!(res.text().await, JwtRejection::Missing.to_string());
+    }
+}
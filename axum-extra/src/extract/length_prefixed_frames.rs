@@ -0,0 +1,175 @@
+use axum::{
+    async_trait,
+    extract::{rejection::BytesRejection, FromRequest, Request},
+};
+use axum_core::__composite_rejection as composite_rejection;
+use axum_core::__define_rejection as define_rejection;
+use bytes::{Buf, Bytes};
+
+/// Extractor that parses the request body as a sequence of length-prefixed frames.
+///
+/// Each frame consists of its length as a big-endian `u32`, followed by that many bytes. The
+/// frames don't copy the body, they're slices of the buffered body.
+///
+/// The request body is buffered in memory and limited by [`DefaultBodyLimit`], so no frame can
+/// be larger than that limit. The request is rejected with `400 Bad Request` if the body ends in
+/// the middle of a length prefix or if a frame is longer than the rest of the body. An empty
+/// body contains no frames.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::extract::LengthPrefixedFrames;
+///
+/// async fn handle_messages(LengthPrefixedFrames(frames): LengthPrefixedFrames) {
+///     for frame in frames {
+///         // frame is `Bytes`
+///     }
+/// }
+///
+/// let app = Router::new().route("/messages", post(handle_messages));
+/// # let _: Router = app;
+/// ```
+///
+/// [`DefaultBodyLimit`]: axum::extract::DefaultBodyLimit
+#[derive(Debug, Clone, Default)]
+pub struct LengthPrefixedFrames(pub Vec<Bytes>);
+
+#[async_trait]
+impl<S> FromRequest<S> for LengthPrefixedFrames
+where
+    S: Send + Sync,
+{
+    type Rejection = LengthPrefixedFramesRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let mut bytes = Bytes::from_request(req, state).await?;
+
+        let mut frames = Vec::new();
+        while bytes.has_remaining() {
+            if bytes.remaining() < 4 {
+                return Err(TruncatedFrameLength.into());
+            }
+
+            let len = bytes.get_u32() as usize;
+            if len > bytes.remaining() {
+                return Err(FrameLengthOutOfBounds.into());
+            }
+
+            frames.push(bytes.split_to(len));
+        }
+
+        Ok(Self(frames))
+    }
+}
+
+axum_core::__impl_deref!(LengthPrefixedFrames: Vec<Bytes>);
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Request body ended in the middle of a frame length"]
+    /// Rejection type for [`LengthPrefixedFrames`] used if the body ends with fewer than four
+    /// bytes where a frame length was expected.
+    pub struct TruncatedFrameLength;
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Frame length exceeds the remaining request body"]
+    /// Rejection type for [`LengthPrefixedFrames`] used if a frame is longer than the rest of
+    /// the body.
+    pub struct FrameLengthOutOfBounds;
+}
+
+composite_rejection! {
+    /// Rejection used for [`LengthPrefixedFrames`].
+    ///
+    /// Contains one variant for each way the [`LengthPrefixedFrames`] extractor
+    /// can fail.
+    pub enum LengthPrefixedFramesRejection {
+        TruncatedFrameLength,
+        FrameLengthOutOfBounds,
+        BytesRejection,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+    use http::StatusCode;
+
+    fn frame(data: &[u8]) -> Vec<u8> {
+        let mut frame = (data.len() as u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(data);
+        frame
+    }
+
+    #[tokio::test]
+    async fn multiple_frames() {
+        let app = Router::new().route(
+            "/",
+            post(
+                |LengthPrefixedFrames(frames): LengthPrefixedFrames| async move {
+                    frames
+                        .iter()
+                        .map(|frame| String::from_utf8(frame.to_vec()).unwrap())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                },
+            ),
+        );
+
+        let client = TestClient::new(app);
+
+        let body = [frame(b"foo"), frame(b""), frame(b"bar")].concat();
+        let res = client.post("/").body(body).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "foo,,bar");
+
+        let res = client.post("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "");
+    }
+
+    #[tokio::test]
+    async fn truncated_frame() {
+        let app = Router::new().route("/", post(|_: LengthPrefixedFrames| async {}));
+
+        let client = TestClient::new(app);
+
+        let body = [frame(b"foo"), vec![0, 0]].concat();
+        let res = client.post("/").body(body).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.text().await,
+            "Request body ended in the middle of a frame length"
+        );
+
+        let mut body = [frame(b"foo"), frame(b"bar")].concat();
+        body.pop();
+        let res = client.post("/").body(body).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.text().await,
+            "Frame length exceeds the remaining request body"
+        );
+    }
+
+    #[tokio::test]
+    async fn oversized_length() {
+        let app = Router::new().route("/", post(|_: LengthPrefixedFrames| async {}));
+
+        let client = TestClient::new(app);
+
+        let body = [u32::MAX.to_be_bytes().to_vec(), b"foo".to_vec()].concat();
+        let res = client.post("/").body(body).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.text().await,
+            "Frame length exceeds the remaining request body"
+        );
+    }
+}
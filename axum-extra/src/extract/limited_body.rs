@@ -0,0 +1,94 @@
+use axum::{
+    async_trait,
+    body::Body,
+    extract::{FromRequest, Request},
+    RequestExt,
+};
+use std::convert::Infallible;
+
+/// Extractor for the raw request body with the [default body limit] applied.
+///
+/// Extracting [`Body`] directly hands back the body as is, so the default body limit doesn't
+/// apply when streaming it. `LimitedBody` applies the limit lazily, as the body is read, so
+/// reading past it yields an error while the body can still be streamed.
+///
+/// To use a limit other than the default one, extract [`Body`] and call [`Body::limited`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{extract::DefaultBodyLimit, routing::post, Router};
+/// use axum_extra::extract::LimitedBody;
+/// use futures_util::StreamExt;
+///
+/// async fn upload(LimitedBody(body): LimitedBody) {
+///     let mut stream = body.into_data_stream();
+///     while let Some(chunk) = stream.next().await {
+///         // yields an error once more than 1024 bytes have been read
+///         let chunk = chunk.unwrap();
+///     }
+/// }
+///
+/// let app = Router::new()
+///     .route("/upload", post(upload))
+///     .layer(DefaultBodyLimit::max(1024));
+/// # let _: Router = app;
+/// ```
+///
+/// [default body limit]: axum::extract::DefaultBodyLimit
+#[derive(Debug, Default)]
+pub struct LimitedBody(pub Body);
+
+#[async_trait]
+impl<S> FromRequest<S> for LimitedBody
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(req.into_limited_body()))
+    }
+}
+
+axum_core::__impl_deref!(LimitedBody: Body);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{extract::DefaultBodyLimit, routing::post, Router};
+    use http::StatusCode;
+    use http_body_util::BodyExt;
+
+    async fn read(LimitedBody(body): LimitedBody) -> String {
+        match body.collect().await {
+            Ok(collected) => format!("read {} bytes", collected.to_bytes().len()),
+            Err(_) => "limit exceeded".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reading_past_the_default_limit_fails() {
+        let app = Router::new()
+            .route("/", post(read))
+            .layer(DefaultBodyLimit::max(4));
+        let client = TestClient::new(app);
+
+        let res = client.post("/").body("abcd").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "read 4 bytes");
+
+        let res = client.post("/").body("abcde").await;
+        assert_eq!(res.text().await, "limit exceeded");
+    }
+
+    #[tokio::test]
+    async fn body_limited() {
+        let body = Body::from("abcde").limited(4);
+        assert!(body.collect().await.is_err());
+
+        let body = Body::from("abcd").limited(4);
+        assert_eq!(body.collect().await.unwrap().to_bytes(), "abcd");
+    }
+}
@@ -0,0 +1,212 @@
+use super::accept_encoding::parse_q;
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+};
+use http::{header::ACCEPT_LANGUAGE, request::Parts};
+use std::{convert::Infallible, sync::Arc};
+
+/// Extractor that negotiates the locale of the response based on the `Accept-Language` header.
+///
+/// The locales the application supports are provided by [`SupportedLocales`], which must be
+/// accessible from the state via [`FromRef`]. Language ranges in the header are tried in order of
+/// their q-value and the first one that matches a supported locale wins:
+///
+/// - A supported locale that's equal to the range, ignoring case, is preferred.
+/// - Then the range is truncated from the end, so `en-US` matches a supported `en`.
+/// - Then a supported locale that starts with the range, so `en` matches a supported `en-GB`.
+/// - `*` matches the first supported locale.
+///
+/// If no range matches or the header is missing, the default locale is used. This extractor
+/// never rejects the request.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{Locale, SupportedLocales};
+///
+/// async fn handler(Locale(locale): Locale) {
+///     // render the response in `locale`
+/// }
+///
+/// let locales = SupportedLocales::new("en", ["en", "en-GB", "de", "fr"]);
+///
+/// let app = Router::new().route("/", get(handler)).with_state(locales);
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale(pub String);
+
+/// The locales supported by the application, used by [`Locale`].
+///
+/// Cloning is cheap.
+#[derive(Debug, Clone)]
+pub struct SupportedLocales {
+    default: Arc<str>,
+    locales: Arc<[String]>,
+}
+
+impl SupportedLocales {
+    /// Create a new `SupportedLocales`.
+    ///
+    /// `default` is used if negotiation fails. It doesn't have to be part of `locales`.
+    pub fn new<I>(default: impl Into<String>, locales: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            default: default.into().into(),
+            locales: locales.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Find the supported locale that best matches a value of the `Accept-Language` header.
+    pub fn negotiate(&self, accept_language: &str) -> &str {
+        let mut ranges = accept_language
+            .split(',')
+            .filter_map(parse_language_range)
+            .filter(|(_, q)| *q > 0)
+            .collect::<Vec<_>>();
+
+        // stable, so ranges with equal q-values keep their order
+        ranges.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        ranges
+            .into_iter()
+            .find_map(|(range, _)| self.find(range))
+            .unwrap_or(&self.default)
+    }
+
+    fn find(&self, range: &str) -> Option<&str> {
+        if range == "*" {
+            return self.locales.first().map(String::as_str);
+        }
+
+        let mut prefix = range;
+        loop {
+            if let Some(locale) = self.exact(prefix) {
+                return Some(locale);
+            }
+            match prefix.rsplit_once('-') {
+                Some((shorter, _)) => prefix = shorter,
+                None => break,
+            }
+        }
+
+        self.locales
+            .iter()
+            .find(|locale| {
+                locale.len() > range.len()
+                    && locale.as_bytes()[range.len()] == b'-'
+                    && locale[..range.len()].eq_ignore_ascii_case(range)
+            })
+            .map(String::as_str)
+    }
+
+    fn exact(&self, range: &str) -> Option<&str> {
+        self.locales
+            .iter()
+            .find(|locale| locale.eq_ignore_ascii_case(range))
+            .map(String::as_str)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Locale
+where
+    S: Send + Sync,
+    SupportedLocales: FromRef<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let locales = SupportedLocales::from_ref(state);
+
+        let locale = parts
+            .headers
+            .get_all(ACCEPT_LANGUAGE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Ok(Self(locales.negotiate(&locale).to_owned()))
+    }
+}
+
+axum_core::__impl_deref!(Locale: String);
+
+fn parse_language_range(item: &str) -> Option<(&str, u16)> {
+    let mut params = item.split(';');
+    let range = params.next()?.trim();
+    if range.is_empty()
+        || !range
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'*')
+    {
+        return None;
+    }
+
+    let mut q = 1000;
+    for param in params {
+        let (key, value) = param.trim().split_once('=')?;
+        if key.trim().eq_ignore_ascii_case("q") {
+            q = parse_q(value.trim())?;
+        }
+    }
+
+    Some((range, q))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    fn locales() -> SupportedLocales {
+        SupportedLocales::new("en", ["en", "en-GB", "de", "fr-CA"])
+    }
+
+    #[test]
+    fn exact_match() {
+        let locales = locales();
+        assert_eq!(locales.negotiate("de"), "de");
+        assert_eq!(locales.negotiate("EN-gb"), "en-GB");
+        assert_eq!(locales.negotiate("fr;q=0.5, de;q=0.8, ja"), "de");
+    }
+
+    #[test]
+    fn prefix_match() {
+        let locales = locales();
+        assert_eq!(locales.negotiate("de-AT"), "de");
+        assert_eq!(locales.negotiate("en-GB-oxendict"), "en-GB");
+        assert_eq!(locales.negotiate("fr"), "fr-CA");
+        assert_eq!(locales.negotiate("ja, *;q=0.1"), "en");
+    }
+
+    #[test]
+    fn fallback_to_default() {
+        let locales = SupportedLocales::new("en", ["de", "fr"]);
+        assert_eq!(locales.negotiate(""), "en");
+        assert_eq!(locales.negotiate("ja, zh;q=0.9"), "en");
+        assert_eq!(locales.negotiate("de;q=0"), "en");
+        assert_eq!(locales.negotiate("de;q=invalid"), "en");
+    }
+
+    #[tokio::test]
+    async fn extractor() {
+        let app = Router::new()
+            .route("/", get(|Locale(locale): Locale| async { locale }))
+            .with_state(locales());
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("accept-language", "de-CH").await;
+        assert_eq!(res.text().await, "de");
+
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "en");
+    }
+}
@@ -1,25 +1,136 @@
 //! Additional extractors.
 
+mod accept_encoding;
+mod api_version;
+mod bearer_token;
 mod cached;
+mod charset;
+mod client_certificate;
+mod combined;
+mod csrf_token;
+mod fetch_metadata;
+mod first_of;
+mod forwarded;
+mod geo_location;
+mod length_prefixed_frames;
+mod limited_body;
+mod locale;
+mod nonce;
 mod optional_path;
+mod origin;
+mod prefer;
+mod referer_check;
+mod representation;
+mod request_cache_control;
+mod state_cached;
+mod tenant_limited_bytes;
+mod user_agent;
+mod websocket_upgrade_check;
 mod with_rejection;
 
+#[cfg(feature = "basic-auth")]
+mod basic_auth;
+
+#[cfg(feature = "client-ip")]
+mod client_ip;
+
+#[cfg(feature = "feature-flags")]
+mod feature_flags;
+
 #[cfg(feature = "form")]
 mod form;
 
+#[cfg(feature = "if-range")]
+mod if_range;
+
+#[cfg(feature = "tracing")]
+mod instrumented;
+
 #[cfg(feature = "cookie")]
 pub mod cookie;
 
+#[cfg(feature = "json-array-stream")]
+mod json_array_stream;
+
 #[cfg(feature = "json-deserializer")]
 mod json_deserializer;
 
+#[cfg(feature = "jsonwebtoken")]
+mod jwt;
+
+#[cfg(feature = "json-lines")]
+mod nd_json;
+
+#[cfg(feature = "negotiated")]
+mod negotiated;
+
+#[cfg(feature = "query")]
+mod pagination;
+
+#[cfg(feature = "payload")]
+mod payload;
+
 #[cfg(feature = "query")]
 mod query;
 
 #[cfg(feature = "multipart")]
 pub mod multipart;
 
-pub use self::{cached::Cached, optional_path::OptionalPath, with_rejection::WithRejection};
+#[cfg(feature = "signed-query")]
+mod signed_query;
+
+#[cfg(feature = "sqlx")]
+mod tx;
+
+#[cfg(feature = "trace-context")]
+mod trace_context;
+
+#[cfg(feature = "upload-to-file")]
+mod upload_to_file;
+
+#[cfg(feature = "validator")]
+mod validated;
+
+pub use self::{
+    accept_encoding::{AcceptEncoding, Encoding, InvalidAcceptEncoding},
+    api_version::{ApiVersion, InvalidApiVersion},
+    bearer_token::{BearerToken, BearerTokenRejection},
+    cached::Cached,
+    charset::{Charset, InvalidAcceptCharset},
+    client_certificate::{ClientCertificate, NoClientCertificate, PeerCertificates},
+    combined::{Combined, CombinedRejection},
+    csrf_token::{CsrfConfig, CsrfToken, CsrfTokenRejection},
+    fetch_metadata::{FetchDest, FetchMetadata, FetchMode, FetchSite},
+    first_of::{FirstOf, FirstOfRejection, FirstOfTuple},
+    forwarded::{ForwardedElement, ForwardedHeader, ForwardedNode, InvalidForwardedHeader},
+    geo_location::{CloudFrontGeo, CloudflareGeo, GeoLocation, GeoProvider, VercelGeo},
+    length_prefixed_frames::{
+        FrameLengthOutOfBounds, LengthPrefixedFrames, LengthPrefixedFramesRejection,
+        TruncatedFrameLength,
+    },
+    limited_body::LimitedBody,
+    locale::{Locale, SupportedLocales},
+    nonce::{Nonce, NonceRejection, NonceStore},
+    optional_path::OptionalPath,
+    origin::{InvalidOrigin, Origin, OriginHeader},
+    prefer::Prefer,
+    referer_check::{RefererCheck, RefererCheckConfig, RefererCheckRejection},
+    representation::{NotAcceptable, Representation, SupportedRepresentations},
+    request_cache_control::RequestCacheControl,
+    state_cached::{CacheKey, StateCache, StateCached},
+    tenant_limited_bytes::{TenantLimitedBytes, TenantLimitedBytesRejection, TenantQuotas},
+    user_agent::{DeviceClass, UserAgentInfo},
+    websocket_upgrade_check::{WebSocketUpgradeCheck, WebSocketUpgradeCheckRejection},
+    with_rejection::WithRejection,
+};
+
+pub(crate) use self::accept_encoding::is_token;
+
+#[cfg(feature = "basic-auth")]
+pub use self::basic_auth::{BasicAuth, BasicAuthRejection};
+
+#[cfg(feature = "client-ip")]
+pub use self::client_ip::{ClientIp, TrustedProxies};
 
 #[cfg(feature = "cookie")]
 pub use self::cookie::CookieJar;
@@ -30,8 +141,41 @@ pub use self::cookie::PrivateCookieJar;
 #[cfg(feature = "cookie-signed")]
 pub use self::cookie::SignedCookieJar;
 
+#[cfg(feature = "feature-flags")]
+pub use self::feature_flags::{FeatureFlags, FeatureFlagsConfig, FeatureFlagsRejection};
+
 #[cfg(feature = "form")]
-pub use self::form::{Form, FormRejection};
+pub use self::form::{Form, FormMap, FormRejection};
+
+#[cfg(feature = "if-range")]
+pub use self::if_range::{EntityTag, IfRange, IfRangeHeader, InvalidIfRange};
+
+#[cfg(feature = "tracing")]
+pub use self::instrumented::Instrumented;
+
+#[cfg(feature = "json-array-stream")]
+pub use self::json_array_stream::{
+    JsonArrayBodyError, JsonArrayStream, JsonArrayStreamRejection, NotAJsonArray,
+};
+
+#[cfg(feature = "jsonwebtoken")]
+pub use self::jwt::{Jwt, JwtConfig, JwtRejection};
+
+#[cfg(feature = "json-lines")]
+pub use self::nd_json::{NdJson, NdJsonRejection};
+
+#[cfg(feature = "negotiated")]
+pub use self::negotiated::{
+    Negotiated, NegotiatedDataError, NegotiatedRejection, UnsupportedNegotiatedType,
+};
+
+#[cfg(feature = "query")]
+pub use self::pagination::{Pagination, PaginationRejection};
+
+#[cfg(feature = "payload")]
+pub use self::payload::{
+    Payload, PayloadFormError, PayloadJsonError, PayloadRejection, UnsupportedPayloadType,
+};
 
 #[cfg(feature = "query")]
 pub use self::query::{OptionalQuery, OptionalQueryRejection, Query, QueryRejection};
@@ -39,16 +183,39 @@ pub use self::query::{OptionalQuery, OptionalQueryRejection, Query, QueryRejecti
 #[cfg(feature = "multipart")]
 pub use self::multipart::Multipart;
 
+#[cfg(feature = "signed-query")]
+pub use self::signed_query::{SignedQuery, SignedQueryKey, SignedQueryRejection};
+
+#[cfg(feature = "sqlx")]
+pub use self::tx::{Tx, TxLayer, TxRejection, TxService};
+
+#[cfg(feature = "trace-context")]
+pub use self::trace_context::{InvalidTraceParent, TraceContext};
+
+#[cfg(feature = "upload-to-file")]
+pub use self::upload_to_file::{UploadToFile, UploadToFileRejection};
+
+#[cfg(feature = "validator")]
+pub use self::validated::{Validated, ValidatedRejection};
+
 #[cfg(feature = "json-deserializer")]
 pub use self::json_deserializer::{
     JsonDataError, JsonDeserializer, JsonDeserializerRejection, JsonSyntaxError,
     MissingJsonContentType,
 };
 
+#[cfg(feature = "deadline")]
+#[doc(no_inline)]
+pub use crate::middleware::Deadline;
+
 #[cfg(feature = "json-lines")]
 #[doc(no_inline)]
 pub use crate::json_lines::JsonLines;
 
+#[cfg(feature = "tee-body")]
+#[doc(no_inline)]
+pub use crate::body::Tee;
+
 #[cfg(feature = "typed-header")]
 #[doc(no_inline)]
 pub use crate::typed_header::TypedHeader;
@@ -414,6 +414,7 @@ mod tests {
     use super::*;
     use crate::test_helpers::*;
     use axum::{extract::DefaultBodyLimit, routing::post, Router};
+    use futures_util::StreamExt;
 
     #[tokio::test]
     async fn content_type_with_encoding() {
@@ -446,6 +447,58 @@ mod tests {
         client.post("/").multipart(form).await;
     }
 
+    #[tokio::test]
+    async fn streams_fields() {
+        async fn handle(mut multipart: Multipart) -> String {
+            let mut out = Vec::new();
+            while let Some(mut field) = multipart.next_field().await.unwrap() {
+                let name = field.name().unwrap().to_owned();
+                let file_name = field.file_name().map(ToOwned::to_owned);
+
+                let mut len = 0;
+                while let Some(chunk) = field.next().await {
+                    len += chunk.unwrap().len();
+                }
+
+                out.push(format!("{name} {file_name:?} {len}"));
+            }
+            out.join(", ")
+        }
+
+        let app = Router::new().route("/", post(handle));
+        let client = TestClient::new(app);
+
+        let form = reqwest::multipart::Form::new()
+            .text("description", "a file")
+            .part(
+                "file",
+                reqwest::multipart::Part::bytes(vec![0; 100_000]).file_name("data.bin"),
+            );
+
+        let res = client.post("/").multipart(form).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.text().await,
+            r#"description None 6, file Some("data.bin") 100000"#
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_boundary() {
+        let app = Router::new().route("/", post(|_: Multipart| async {}));
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("content-type", "multipart/form-data")
+            .body("")
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = client.post("/").body("").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
     // No need for this to be a #[test], we just want to make sure it compiles
     fn _multipart_from_request_limited() {
         async fn handler(_: Multipart) {}
@@ -0,0 +1,182 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{rejection::BytesRejection, FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+use std::fmt;
+
+/// Extractor that buffers a newline delimited JSON body and deserializes every line.
+///
+/// The body is buffered with the [default body limit] applied. Each non-empty line is
+/// deserialized into a `T` and the values are returned in order. If a line fails to deserialize
+/// the request is rejected with `400 Bad Request` and the (1-based) number of the first line that
+/// failed.
+///
+/// Use [`JsonLines`](crate::json_lines::JsonLines) instead to process the lines as a stream
+/// without buffering the whole body.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::extract::NdJson;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Event {
+///     name: String,
+/// }
+///
+/// async fn ingest(NdJson(events): NdJson<Event>) {
+///     for event in events {
+///         // ...
+///     }
+/// }
+///
+/// let app = Router::new().route("/events", post(ingest));
+/// # let _: Router = app;
+/// ```
+///
+/// [default body limit]: axum::extract::DefaultBodyLimit
+#[cfg_attr(docsrs, doc(cfg(feature = "json-lines")))]
+#[derive(Debug, Clone, Default)]
+pub struct NdJson<T>(pub Vec<T>);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for NdJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = NdJsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(NdJsonRejection::Body)?;
+
+        let mut values = Vec::new();
+        for (index, line) in bytes.split(|&b| b == b'\n').enumerate() {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+
+            let value =
+                serde_json::from_slice(line).map_err(|source| NdJsonRejection::InvalidLine {
+                    line: index + 1,
+                    source,
+                })?;
+            values.push(value);
+        }
+
+        Ok(Self(values))
+    }
+}
+
+/// Rejection used for [`NdJson`].
+#[cfg_attr(docsrs, doc(cfg(feature = "json-lines")))]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum NdJsonRejection {
+    /// Buffering the body failed.
+    Body(BytesRejection),
+    /// A line failed to deserialize.
+    InvalidLine {
+        /// The 1-based number of the line.
+        line: usize,
+        /// The error from `serde_json`.
+        source: serde_json::Error,
+    },
+}
+
+impl IntoResponse for NdJsonRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Body(inner) => inner.into_response(),
+            Self::InvalidLine { .. } => {
+                let body = self.to_string();
+                let status = StatusCode::BAD_REQUEST;
+                axum_core::__log_rejection!(
+                    rejection_type = Self,
+                    body_text = body,
+                    status = status,
+                );
+                (status, body).into_response()
+            }
+        }
+    }
+}
+
+impl fmt::Display for NdJsonRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Body(inner) => inner.fmt(f),
+            Self::InvalidLine { line, source } => {
+                write!(f, "Failed to deserialize line {line}: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NdJsonRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Body(inner) => Some(inner),
+            Self::InvalidLine { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct User {
+        id: i32,
+    }
+
+    #[tokio::test]
+    async fn valid_lines() {
+        let app = Router::new().route(
+            "/",
+            post(|NdJson(users): NdJson<User>| async move {
+                let ids = users.iter().map(|user| user.id.to_string());
+                ids.collect::<Vec<_>>().join(",")
+            }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .body("{\"id\":1}\n{\"id\":2}\r\n\n{\"id\":3}\n")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "1,2,3");
+    }
+
+    #[tokio::test]
+    async fn malformed_line() {
+        let app = Router::new().route("/", post(|_: NdJson<User>| async {}));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .body("{\"id\":1}\n{\"id\":false}\n{\"id\":3}")
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert!(res
+            .text()
+            .await
+            .starts_with("Failed to deserialize line 2: "));
+    }
+}
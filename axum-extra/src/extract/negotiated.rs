@@ -0,0 +1,211 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, Request},
+};
+use axum_core::__composite_rejection as composite_rejection;
+use axum_core::__define_rejection as define_rejection;
+use axum_core::extract::rejection::BytesRejection;
+use bytes::Bytes;
+use http::{header, HeaderMap};
+use serde::de::DeserializeOwned;
+
+/// Extractor that deserializes the request body as JSON or MessagePack, depending on the
+/// `Content-Type` header.
+///
+/// The body is deserialized with [`serde_json`] if the content type is `application/json` (or
+/// similar, such as `application/cloudevents+json`) and with [`rmp_serde`] if it's
+/// `application/msgpack` or `application/x-msgpack`. MessagePack support requires the `msgpack`
+/// feature.
+///
+/// The request will be rejected (and a [`NegotiatedRejection`] will be returned) if:
+///
+/// - The `Content-Type` header is missing or isn't one of the supported types.
+/// - Buffering the request body fails. The [default body limit] applies.
+/// - The body couldn't be deserialized into the target type.
+///
+/// [`rmp_serde`]: https://docs.rs/rmp-serde
+/// [default body limit]: axum::extract::DefaultBodyLimit
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::extract::Negotiated;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct CreateUser {
+///     email: String,
+///     password: String,
+/// }
+///
+/// async fn create_user(Negotiated(payload): Negotiated<CreateUser>) {
+///     // payload is `CreateUser`, regardless of which format the client sent
+/// }
+///
+/// let app = Router::new().route("/users", post(create_user));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "negotiated")))]
+pub struct Negotiated<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Negotiated<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = NegotiatedRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let format = Format::from_headers(req.headers()).ok_or(UnsupportedNegotiatedType)?;
+        let bytes = Bytes::from_request(req, state).await?;
+
+        let result: Result<T, axum::BoxError> = match format {
+            Format::Json => serde_json::from_slice(&bytes).map_err(Into::into),
+            #[cfg(feature = "msgpack")]
+            Format::MsgPack => rmp_serde::from_slice(&bytes).map_err(Into::into),
+        };
+
+        result
+            .map(Self)
+            .map_err(|err| NegotiatedDataError::from_err(err).into())
+    }
+}
+
+axum_core::__impl_deref!(Negotiated);
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Json,
+    #[cfg(feature = "msgpack")]
+    MsgPack,
+}
+
+impl Format {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let mime = headers
+            .get(header::CONTENT_TYPE)?
+            .to_str()
+            .ok()?
+            .parse::<mime::Mime>()
+            .ok()?;
+
+        if mime.type_() != "application" {
+            return None;
+        }
+
+        if mime.subtype() == "json" || mime.suffix().map_or(false, |name| name == "json") {
+            return Some(Self::Json);
+        }
+
+        #[cfg(feature = "msgpack")]
+        if mime.subtype() == "msgpack" || mime.subtype() == "x-msgpack" {
+            return Some(Self::MsgPack);
+        }
+
+        None
+    }
+}
+
+define_rejection! {
+    #[status = UNPROCESSABLE_ENTITY]
+    #[body = "Failed to deserialize the request body into the target type"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "negotiated")))]
+    /// Rejection type for [`Negotiated`].
+    ///
+    /// This rejection is used if the request body couldn't be deserialized into the target type.
+    pub struct NegotiatedDataError(Error);
+}
+
+define_rejection! {
+    #[status = UNSUPPORTED_MEDIA_TYPE]
+    #[body = "Expected request with a JSON or MessagePack `Content-Type`"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "negotiated")))]
+    /// Rejection type for [`Negotiated`] used if the `Content-Type` header is missing or not
+    /// supported.
+    pub struct UnsupportedNegotiatedType;
+}
+
+composite_rejection! {
+    /// Rejection used for [`Negotiated`].
+    ///
+    /// Contains one variant for each way the [`Negotiated`] extractor can fail.
+    #[cfg_attr(docsrs, doc(cfg(feature = "negotiated")))]
+    pub enum NegotiatedRejection {
+        NegotiatedDataError,
+        UnsupportedNegotiatedType,
+        BytesRejection,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+    use http::StatusCode;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Input {
+        foo: String,
+    }
+
+    #[tokio::test]
+    async fn json() {
+        let app = Router::new().route(
+            "/",
+            post(|Negotiated(input): Negotiated<Input>| async move { input.foo }),
+        );
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .header("content-type", "application/json")
+            .body(r#"{ "foo": "bar" }"#)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "bar");
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[tokio::test]
+    async fn msgpack() {
+        let body = rmp_serde::to_vec_named(&Input {
+            foo: "bar".to_owned(),
+        })
+        .unwrap();
+
+        let app = Router::new().route(
+            "/",
+            post(|Negotiated(input): Negotiated<Input>| async move { input.foo }),
+        );
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .header("content-type", "application/msgpack")
+            .body(body)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "bar");
+    }
+
+    #[tokio::test]
+    async fn unsupported_content_type() {
+        let app = Router::new().route("/", post(|_: Negotiated<Input>| async {}));
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .header("content-type", "text/plain")
+            .body("bar")
+            .await;
+
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+}
@@ -0,0 +1,287 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    response::{IntoResponse, Response},
+};
+use http::{request::Parts, StatusCode};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Extractor that protects against replayed requests using a nonce in the `X-Nonce` header.
+///
+/// Every request must carry a nonce that hasn't been used before. Nonces are remembered in a
+/// [`NonceStore`], which must be accessible from the state via [`FromRef`], for as long as its
+/// time to live. Checking whether a nonce was already used and remembering it happens atomically,
+/// so of several concurrent requests with the same nonce only one is accepted.
+///
+/// Requests are rejected with
+///
+/// - `400 Bad Request` if the header is missing or malformed. Nonces must be between 8 and 128
+///   characters long and only contain ASCII letters, digits, `-`, `_`, `+`, `/` and `=`, which
+///   covers hex, base64 and UUIDs.
+/// - `409 Conflict` if the nonce was already used.
+/// - `503 Service Unavailable` if the store is full of nonces that haven't expired yet.
+///
+/// The nonce only protects against replays within its time to live, so it's usually combined
+/// with a signed timestamp that limits how old requests can be.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::extract::{Nonce, NonceStore};
+/// use std::time::Duration;
+///
+/// async fn transfer(Nonce(nonce): Nonce) {
+///     // `nonce` hasn't been seen in the last five minutes
+/// }
+///
+/// let nonces = NonceStore::new(Duration::from_secs(5 * 60));
+///
+/// let app = Router::new()
+///     .route("/transfer", post(transfer))
+///     .with_state(nonces);
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nonce(pub String);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Nonce
+where
+    S: Send + Sync,
+    NonceStore: FromRef<S>,
+{
+    type Rejection = NonceRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let store = NonceStore::from_ref(state);
+
+        let nonce = parts
+            .headers
+            .get("x-nonce")
+            .ok_or(NonceRejection::Missing)?
+            .to_str()
+            .map_err(|_| NonceRejection::Invalid)?;
+        if !is_valid(nonce) {
+            return Err(NonceRejection::Invalid);
+        }
+
+        store.insert(nonce)?;
+        Ok(Self(nonce.to_owned()))
+    }
+}
+
+axum_core::__impl_deref!(Nonce: String);
+
+fn is_valid(nonce: &str) -> bool {
+    (8..=128).contains(&nonce.len())
+        && nonce
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'+' | b'/' | b'='))
+}
+
+/// Store of recently used nonces for [`Nonce`].
+///
+/// Nonces are removed once their time to live has passed, after which they can be used again.
+/// The number of nonces is bounded by [`NonceStore::max_entries`], so a flood of requests can't
+/// use up unbounded memory.
+///
+/// Cloning is cheap and clones share the same nonces.
+#[derive(Debug, Clone)]
+pub struct NonceStore {
+    inner: Arc<Mutex<Inner>>,
+    ttl: Duration,
+    max_entries: usize,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    seen: HashSet<String>,
+    // nonces in the order they were inserted, which is also the order they expire in
+    expiry: VecDeque<(Instant, String)>,
+}
+
+impl NonceStore {
+    /// Create a new `NonceStore` that remembers nonces for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            inner: Default::default(),
+            ttl,
+            max_entries: 100_000,
+        }
+    }
+
+    /// Set the maximum number of nonces that are remembered at the same time.
+    ///
+    /// Once the store is full, requests are rejected until old nonces expire, since forgetting
+    /// a nonce early would allow it to be replayed. Defaults to 100,000.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    fn insert(&self, nonce: &str) -> Result<(), NonceRejection> {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+
+        while let Some((expires_at, _)) = inner.expiry.front() {
+            if *expires_at > now {
+                break;
+            }
+            if let Some((_, expired)) = inner.expiry.pop_front() {
+                inner.seen.remove(&expired);
+            }
+        }
+
+        if inner.seen.contains(nonce) {
+            return Err(NonceRejection::Reused);
+        }
+        if inner.seen.len() >= self.max_entries {
+            return Err(NonceRejection::StoreFull);
+        }
+
+        inner.seen.insert(nonce.to_owned());
+        inner.expiry.push_back((now + self.ttl, nonce.to_owned()));
+        Ok(())
+    }
+}
+
+/// Rejection used for [`Nonce`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum NonceRejection {
+    /// The request didn't have an `X-Nonce` header.
+    Missing,
+    /// The `X-Nonce` header was malformed.
+    Invalid,
+    /// The nonce was already used.
+    Reused,
+    /// The store is full of nonces that haven't expired yet.
+    StoreFull,
+}
+
+impl IntoResponse for NonceRejection {
+    fn into_response(self) -> Response {
+        let body = self.to_string();
+        let status = match self {
+            Self::Missing | Self::Invalid => StatusCode::BAD_REQUEST,
+            Self::Reused => StatusCode::CONFLICT,
+            Self::StoreFull => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        axum_core::__log_rejection!(rejection_type = Self, body_text = body, status = status,);
+        (status, body).into_response()
+    }
+}
+
+impl fmt::Display for NonceRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "Missing `X-Nonce` header"),
+            Self::Invalid => write!(f, "Invalid `X-Nonce` header"),
+            Self::Reused => write!(f, "Nonce has already been used"),
+            Self::StoreFull => write!(f, "Too many requests, try again later"),
+        }
+    }
+}
+
+impl std::error::Error for NonceRejection {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+
+    #[tokio::test]
+    async fn fresh_nonce() {
+        let app = Router::new()
+            .route("/", post(|Nonce(nonce): Nonce| async move { nonce }))
+            .with_state(NonceStore::new(Duration::from_secs(60)));
+
+        let client = TestClient::new(app);
+
+        let res = client.post("/").header("x-nonce", "a1b2c3d4e5f6").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "a1b2c3d4e5f6");
+
+        let res = client.post("/").header("x-nonce", "f6e5d4c3b2a1").await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn reused_nonce() {
+        let app = Router::new()
+            .route("/", post(|Nonce(nonce): Nonce| async move { nonce }))
+            .with_state(NonceStore::new(Duration::from_secs(60)));
+
+        let client = TestClient::new(app);
+
+        let res = client.post("/").header("x-nonce", "a1b2c3d4e5f6").await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = client.post("/").header("x-nonce", "a1b2c3d4e5f6").await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn missing_or_invalid_nonce() {
+        let app = Router::new()
+            .route("/", post(|Nonce(nonce): Nonce| async move { nonce }))
+            .with_state(NonceStore::new(Duration::from_secs(60)));
+
+        let client = TestClient::new(app);
+
+        let res = client.post("/").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        for nonce in ["short", "not a valid nonce", &"a".repeat(129)] {
+            let res = client.post("/").header("x-nonce", nonce).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{nonce}");
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_reuse() {
+        let app = Router::new()
+            .route("/", post(|Nonce(nonce): Nonce| async move { nonce }))
+            .with_state(NonceStore::new(Duration::from_secs(60)));
+
+        let client = TestClient::new(app);
+
+        let requests = (0..10).map(|_| async {
+            client
+                .post("/")
+                .header("x-nonce", "a1b2c3d4e5f6")
+                .await
+                .status()
+        });
+        let statuses = futures_util::future::join_all(requests).await;
+
+        let accepted = statuses.iter().filter(|s| **s == StatusCode::OK).count();
+        let conflicts = statuses
+            .iter()
+            .filter(|s| **s == StatusCode::CONFLICT)
+            .count();
+        assert_eq!((accepted, conflicts), (1, 9));
+    }
+
+    #[test]
+    fn expiry_and_capacity() {
+        let store = NonceStore::new(Duration::ZERO).max_entries(1);
+        store.insert("a1b2c3d4").unwrap();
+        // the first nonce has expired, so it can be used again and doesn't take up space
+        store.insert("a1b2c3d4").unwrap();
+
+        let store = NonceStore::new(Duration::from_secs(60)).max_entries(1);
+        store.insert("a1b2c3d4").unwrap();
+        assert!(matches!(
+            store.insert("e5f6a7b8"),
+            Err(NonceRejection::StoreFull)
+        ));
+    }
+}
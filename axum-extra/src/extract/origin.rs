@@ -0,0 +1,188 @@
+use axum::{async_trait, extract::FromRequestParts};
+use axum_core::__define_rejection as define_rejection;
+use http::{header::ORIGIN, request::Parts, HeaderValue, Uri};
+use std::fmt;
+
+/// Extractor for the `Origin` header.
+///
+/// Contains `None` if the header is missing. Otherwise the header must either be the `null`
+/// origin, which browsers send for example for requests from sandboxed iframes and local files,
+/// or consist of only a scheme, host and optional port, such as `https://example.com:8443`.
+/// Other values are rejected with `400 Bad Request`.
+///
+/// This is useful for handling CORS manually or for checking where unsafe requests come from.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{http::StatusCode, routing::post, Router};
+/// use axum_extra::extract::{Origin, OriginHeader};
+///
+/// async fn handler(OriginHeader(origin): OriginHeader) -> Result<(), StatusCode> {
+///     match origin {
+///         Some(Origin::Uri(uri)) if uri.host() == Some("example.com") => Ok(()),
+///         // requests from the same origin made with `GET` or `HEAD` may not have the header
+///         None => Ok(()),
+///         _ => Err(StatusCode::FORBIDDEN),
+///     }
+/// }
+///
+/// let app = Router::new().route("/", post(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginHeader(pub Option<Origin>);
+
+/// The origin of a request, as sent in the `Origin` header.
+///
+/// See [`OriginHeader`] for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// The `null` origin, used for privacy sensitive and opaque origins.
+    Null,
+    /// An origin consisting of a scheme, host and optional port.
+    ///
+    /// The URI never has a path or query.
+    Uri(Uri),
+}
+
+impl Origin {
+    /// Parse an origin from the value of an `Origin` header.
+    pub fn from_header_value(value: &HeaderValue) -> Option<Self> {
+        let value = value.to_str().ok()?;
+        if value == "null" {
+            return Some(Self::Null);
+        }
+
+        let uri = value.parse::<Uri>().ok()?;
+        let scheme = uri.scheme_str()?;
+        let authority = uri.authority()?;
+        // anything after the authority, such as a path, makes the value longer
+        if value.len() != scheme.len() + "://".len() + authority.as_str().len()
+            || authority.as_str().contains('@')
+            || authority.host().is_empty()
+        {
+            return None;
+        }
+
+        Some(Self::Uri(uri))
+    }
+
+    /// Returns the URI of the origin, or `None` for the `null` origin.
+    pub fn as_uri(&self) -> Option<&Uri> {
+        match self {
+            Self::Null => None,
+            Self::Uri(uri) => Some(uri),
+        }
+    }
+
+    /// Returns `true` if this is the `null` origin.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::Uri(uri) => match (uri.scheme(), uri.authority()) {
+                (Some(scheme), Some(authority)) => write!(f, "{scheme}://{authority}"),
+                _ => uri.fmt(f),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for OriginHeader
+where
+    S: Send + Sync,
+{
+    type Rejection = InvalidOrigin;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.headers.get(ORIGIN) {
+            Some(value) => Origin::from_header_value(value)
+                .map(|origin| Self(Some(origin)))
+                .ok_or(InvalidOrigin),
+            None => Ok(Self(None)),
+        }
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Invalid `Origin` header"]
+    /// Rejection type for [`OriginHeader`] used if the `Origin` header is malformed.
+    pub struct InvalidOrigin;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use http::StatusCode;
+
+    #[tokio::test]
+    async fn valid_origin() {
+        let app = Router::new().route(
+            "/",
+            get(|OriginHeader(origin): OriginHeader| async move {
+                match origin {
+                    Some(origin) => origin.to_string(),
+                    None => "no origin".to_owned(),
+                }
+            }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("origin", "https://example.com:8443")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "https://example.com:8443");
+
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "no origin");
+    }
+
+    #[tokio::test]
+    async fn null_origin() {
+        let app = Router::new().route(
+            "/",
+            get(|OriginHeader(origin): OriginHeader| async move {
+                matches!(origin, Some(Origin::Null)).to_string()
+            }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("origin", "null").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "true");
+    }
+
+    #[tokio::test]
+    async fn malformed_origin() {
+        let app = Router::new().route("/", get(|_: OriginHeader| async {}));
+
+        let client = TestClient::new(app);
+
+        for origin in [
+            "example.com",
+            "https://example.com/path",
+            "https://user@example.com",
+            "https://",
+            "/relative",
+            "",
+        ] {
+            let res = client.get("/").header("origin", origin).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{origin}");
+            assert_eq!(res.text().await, "Invalid `Origin` header");
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    response::{IntoResponse, Response},
+};
+use http::{request::Parts, StatusCode};
+use std::fmt;
+
+/// Extractor for pagination parameters in the query string.
+///
+/// The page can be selected either with `limit` and `offset`, or with `per_page` and `page`,
+/// where `page` starts at 1. Other query parameters are ignored, so `Pagination` can be combined
+/// with [`Query`](super::Query) for filters.
+///
+/// If no limit is given `DEFAULT_LIMIT` (20 unless specified) is used. Limits greater than
+/// `MAX_LIMIT` (100 unless specified) are rejected with `400 Bad Request`, as are parameters that
+/// aren't valid numbers.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::Pagination;
+///
+/// // `GET /users?limit=10&offset=30` or `GET /users?per_page=10&page=4`
+/// async fn list_users(Pagination { limit, offset }: Pagination) {
+///     // ...
+/// }
+///
+/// // allow up to 1000 items per page, with a default of 50
+/// async fn list_events(pagination: Pagination<1000, 50>) {
+///     // ...
+/// }
+///
+/// let app = Router::new()
+///     .route("/users", get(list_users))
+///     .route("/events", get(list_events));
+/// # let _: Router = app;
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pagination<const MAX_LIMIT: u32 = 100, const DEFAULT_LIMIT: u32 = 20> {
+    /// The maximum number of items to return.
+    pub limit: u32,
+    /// The number of items to skip.
+    pub offset: u32,
+}
+
+#[async_trait]
+impl<S, const MAX_LIMIT: u32, const DEFAULT_LIMIT: u32> FromRequestParts<S>
+    for Pagination<MAX_LIMIT, DEFAULT_LIMIT>
+where
+    S: Send + Sync,
+{
+    type Rejection = PaginationRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let params: Vec<(String, String)> = serde_html_form::from_str(query)
+            .map_err(|_| PaginationRejection::InvalidQueryString)?;
+
+        let param = |name: &'static str| -> Result<Option<u32>, PaginationRejection> {
+            params
+                .iter()
+                .rev()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| {
+                    value
+                        .parse()
+                        .map_err(|_| PaginationRejection::InvalidParameter(name))
+                })
+                .transpose()
+        };
+
+        let limit = match param("limit")? {
+            Some(limit) => limit,
+            None => param("per_page")?.unwrap_or(DEFAULT_LIMIT.min(MAX_LIMIT)),
+        };
+        if limit > MAX_LIMIT {
+            return Err(PaginationRejection::LimitTooLarge { max: MAX_LIMIT });
+        }
+
+        let offset = match (param("offset")?, param("page")?) {
+            (Some(offset), _) => offset,
+            (None, Some(page)) => page
+                .checked_sub(1)
+                .and_then(|page| page.checked_mul(limit))
+                .ok_or(PaginationRejection::InvalidParameter("page"))?,
+            (None, None) => 0,
+        };
+
+        Ok(Self { limit, offset })
+    }
+}
+
+/// Rejection used for [`Pagination`].
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PaginationRejection {
+    /// The query string couldn't be parsed.
+    InvalidQueryString,
+    /// The query parameter with the given name wasn't a valid number.
+    InvalidParameter(&'static str),
+    /// The limit was greater than the maximum.
+    LimitTooLarge {
+        /// The maximum limit.
+        max: u32,
+    },
+}
+
+impl IntoResponse for PaginationRejection {
+    fn into_response(self) -> Response {
+        let body = self.to_string();
+        let status = StatusCode::BAD_REQUEST;
+        axum_core::__log_rejection!(rejection_type = Self, body_text = body, status = status,);
+        (status, body).into_response()
+    }
+}
+
+impl fmt::Display for PaginationRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidQueryString => write!(f, "Failed to parse the query string"),
+            Self::InvalidParameter(name) => write!(f, "Invalid `{name}` query parameter"),
+            Self::LimitTooLarge { max } => write!(f, "Limit must not be greater than {max}"),
+        }
+    }
+}
+
+impl std::error::Error for PaginationRejection {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    async fn handler(Pagination { limit, offset }: Pagination) -> String {
+        format!("{limit} {offset}")
+    }
+
+    async fn custom(Pagination { limit, offset }: Pagination<10, 5>) -> String {
+        format!("{limit} {offset}")
+    }
+
+    #[tokio::test]
+    async fn defaults() {
+        let app = Router::new()
+            .route("/", get(handler))
+            .route("/custom", get(custom));
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "20 0");
+
+        let res = client.get("/custom?filter=active").await;
+        assert_eq!(res.text().await, "5 0");
+    }
+
+    #[tokio::test]
+    async fn explicit_values() {
+        let app = Router::new().route("/", get(handler));
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/?limit=10&offset=30").await;
+        assert_eq!(res.text().await, "10 30");
+
+        let res = client.get("/?per_page=10&page=4").await;
+        assert_eq!(res.text().await, "10 30");
+
+        let res = client.get("/?page=2").await;
+        assert_eq!(res.text().await, "20 20");
+    }
+
+    #[tokio::test]
+    async fn over_max_is_rejected() {
+        let app = Router::new()
+            .route("/", get(handler))
+            .route("/custom", get(custom));
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/?limit=100").await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = client.get("/?limit=101").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "Limit must not be greater than 100");
+
+        let res = client.get("/custom?per_page=11").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "Limit must not be greater than 10");
+    }
+
+    #[tokio::test]
+    async fn invalid_input() {
+        let app = Router::new().route("/", get(handler));
+
+        let client = TestClient::new(app);
+
+        for query in ["limit=ten", "offset=-1", "page=0", "per_page=1.5"] {
+            let res = client.get(&format!("/?{query}")).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{query}");
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, Request},
+};
+use axum_core::__composite_rejection as composite_rejection;
+use axum_core::__define_rejection as define_rejection;
+use axum_core::extract::rejection::BytesRejection;
+use bytes::Bytes;
+use http::{header, HeaderMap};
+use serde::de::DeserializeOwned;
+
+/// Extractor that deserializes the request body as JSON or form data, depending on the
+/// `Content-Type` header.
+///
+/// The body is deserialized with [`serde_json`] if the content type is `application/json` (or
+/// similar, such as `application/merge-patch+json`) and with [`serde_html_form`] if it's
+/// `application/x-www-form-urlencoded`. This lets an endpoint accept both API clients and plain
+/// HTML forms with the same type.
+///
+/// The request will be rejected (and a [`PayloadRejection`] will be returned) if:
+///
+/// - The `Content-Type` header is missing or isn't one of the supported types, with
+///   `415 Unsupported Media Type`.
+/// - Buffering the request body fails. The [default body limit] applies.
+/// - The body couldn't be deserialized into the target type, with `400 Bad Request` and a message
+///   saying which format failed.
+///
+/// [default body limit]: axum::extract::DefaultBodyLimit
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::extract::Payload;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Subscribe {
+///     email: String,
+/// }
+///
+/// async fn subscribe(Payload(payload): Payload<Subscribe>) {
+///     // payload is `Subscribe`, whether it was submitted by a form or sent as JSON
+/// }
+///
+/// let app = Router::new().route("/subscribe", post(subscribe));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "payload")))]
+pub struct Payload<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Payload<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = PayloadRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let format = Format::from_headers(req.headers()).ok_or(UnsupportedPayloadType)?;
+        let bytes = Bytes::from_request(req, state).await?;
+
+        match format {
+            Format::Json => serde_json::from_slice(&bytes)
+                .map(Self)
+                .map_err(|err| PayloadJsonError::from_err(err).into()),
+            Format::Form => serde_html_form::from_bytes(&bytes)
+                .map(Self)
+                .map_err(|err| PayloadFormError::from_err(err).into()),
+        }
+    }
+}
+
+axum_core::__impl_deref!(Payload);
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Json,
+    Form,
+}
+
+impl Format {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let mime = headers
+            .get(header::CONTENT_TYPE)?
+            .to_str()
+            .ok()?
+            .parse::<mime::Mime>()
+            .ok()?;
+
+        if mime.type_() != "application" {
+            return None;
+        }
+
+        if mime.subtype() == "json" || mime.suffix().map_or(false, |name| name == "json") {
+            return Some(Self::Json);
+        }
+
+        if mime.subtype() == "x-www-form-urlencoded" {
+            return Some(Self::Form);
+        }
+
+        None
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to deserialize the JSON body into the target type"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "payload")))]
+    /// Rejection type for [`Payload`] used if a JSON body couldn't be deserialized into the
+    /// target type.
+    pub struct PayloadJsonError(Error);
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to deserialize the form body into the target type"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "payload")))]
+    /// Rejection type for [`Payload`] used if a form body couldn't be deserialized into the
+    /// target type.
+    pub struct PayloadFormError(Error);
+}
+
+define_rejection! {
+    #[status = UNSUPPORTED_MEDIA_TYPE]
+    #[body = "Expected request with `Content-Type: application/json` or `Content-Type: application/x-www-form-urlencoded`"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "payload")))]
+    /// Rejection type for [`Payload`] used if the `Content-Type` header is missing or not
+    /// supported.
+    pub struct UnsupportedPayloadType;
+}
+
+composite_rejection! {
+    /// Rejection used for [`Payload`].
+    ///
+    /// Contains one variant for each way the [`Payload`] extractor can fail.
+    #[cfg_attr(docsrs, doc(cfg(feature = "payload")))]
+    pub enum PayloadRejection {
+        PayloadJsonError,
+        PayloadFormError,
+        UnsupportedPayloadType,
+        BytesRejection,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+    use http::StatusCode;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Input {
+        name: String,
+        age: u32,
+    }
+
+    #[tokio::test]
+    async fn json() {
+        let app = Router::new().route(
+            "/",
+            post(|Payload(input): Payload<Input>| async move {
+                format!("{} is {}", input.name, input.age)
+            }),
+        );
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .header("content-type", "application/json")
+            .body(r#"{ "name": "Ferris", "age": 9 }"#)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "Ferris is 9");
+    }
+
+    #[tokio::test]
+    async fn form() {
+        let app = Router::new().route(
+            "/",
+            post(|Payload(input): Payload<Input>| async move {
+                format!("{} is {}", input.name, input.age)
+            }),
+        );
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body("name=Ferris&age=9")
+            .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "Ferris is 9");
+    }
+
+    #[tokio::test]
+    async fn invalid_body() {
+        let app = Router::new().route("/", post(|_: Payload<Input>| async {}));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/json")
+            .body(r#"{ "name": "Ferris" }"#)
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert!(res.text().await.contains("JSON"));
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body("name=Ferris&age=nine")
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert!(res.text().await.contains("form"));
+    }
+
+    #[tokio::test]
+    async fn unsupported_content_type() {
+        let app = Router::new().route("/", post(|_: Payload<Input>| async {}));
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .header("content-type", "text/plain")
+            .body("Ferris")
+            .await;
+
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+}
@@ -0,0 +1,182 @@
+use axum::{async_trait, extract::FromRequestParts};
+use http::{request::Parts, HeaderMap};
+use std::{convert::Infallible, time::Duration};
+
+/// Extractor for the preferences of the `Prefer` header, as defined in [RFC 7240].
+///
+/// This allows handlers to shape responses according to what the client prefers, for example
+/// by leaving out the representation of a created resource when the client sent
+/// `Prefer: return=minimal`.
+///
+/// The `return`, `respond-async` and `wait` preferences are supported. Unknown and malformed
+/// preferences, as well as parameters of preferences, are ignored, so this extractor never
+/// rejects the request. If the header is missing no preferences are set.
+///
+/// Note that preferences are only hints. Handlers that honor one should say so in the
+/// `Preference-Applied` response header.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{http::StatusCode, response::IntoResponse, routing::post, Router};
+/// use axum_extra::extract::Prefer;
+///
+/// async fn create_user(prefer: Prefer) -> impl IntoResponse {
+///     let user = create_user_in_db().await;
+///
+///     if prefer.return_minimal() {
+///         (StatusCode::CREATED, [("preference-applied", "return=minimal")], String::new())
+///     } else {
+///         (StatusCode::CREATED, [("vary", "prefer")], user)
+///     }
+/// }
+/// # async fn create_user_in_db() -> String { unimplemented!() }
+///
+/// let app = Router::new().route("/users", post(create_user));
+/// # let _: Router = app;
+/// ```
+///
+/// [RFC 7240]: https://datatracker.ietf.org/doc/html/rfc7240
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Prefer {
+    return_preference: Option<Return>,
+    respond_async: bool,
+    wait: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Return {
+    Minimal,
+    Representation,
+}
+
+impl Prefer {
+    /// Parse the preferences from the `Prefer` headers in `headers`.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let mut prefer = Self::default();
+
+        let preferences = headers
+            .get_all("prefer")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','));
+
+        for preference in preferences {
+            // parameters of preferences aren't used by any of the supported preferences
+            let preference = preference.split(';').next().unwrap_or_default();
+            let (name, value) = match preference.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (preference.trim(), None),
+            };
+
+            if name.eq_ignore_ascii_case("return") {
+                match value {
+                    Some(value) if value.eq_ignore_ascii_case("minimal") => {
+                        prefer.return_preference = Some(Return::Minimal);
+                    }
+                    Some(value) if value.eq_ignore_ascii_case("representation") => {
+                        prefer.return_preference = Some(Return::Representation);
+                    }
+                    _ => {}
+                }
+            } else if name.eq_ignore_ascii_case("respond-async") {
+                prefer.respond_async = true;
+            } else if name.eq_ignore_ascii_case("wait") {
+                if let Some(secs) = value.and_then(|value| value.parse().ok()) {
+                    prefer.wait = Some(Duration::from_secs(secs));
+                }
+            }
+        }
+
+        prefer
+    }
+
+    /// Returns `true` if the client sent `return=minimal`, meaning it prefers a minimal
+    /// response, such as an empty body, over the full representation of the resource.
+    pub fn return_minimal(&self) -> bool {
+        self.return_preference == Some(Return::Minimal)
+    }
+
+    /// Returns `true` if the client sent `return=representation`, meaning it prefers the
+    /// response to include the full representation of the resource.
+    pub fn return_representation(&self) -> bool {
+        self.return_preference == Some(Return::Representation)
+    }
+
+    /// Returns `true` if the client sent `respond-async`, meaning it prefers the server to
+    /// process the request asynchronously and respond with `202 Accepted`.
+    pub fn respond_async(&self) -> bool {
+        self.respond_async
+    }
+
+    /// Returns the value of the `wait` preference, which is how long the client is willing to
+    /// wait for the request to be processed.
+    pub fn wait(&self) -> Option<Duration> {
+        self.wait
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Prefer
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+    use http::HeaderValue;
+
+    fn parse(value: &'static str) -> Prefer {
+        let mut headers = HeaderMap::new();
+        headers.insert("prefer", HeaderValue::from_static(value));
+        Prefer::from_headers(&headers)
+    }
+
+    #[test]
+    fn return_minimal() {
+        let prefer = parse("return=minimal");
+        assert!(prefer.return_minimal());
+        assert!(!prefer.return_representation());
+        assert!(!prefer.respond_async());
+        assert_eq!(prefer.wait(), None);
+
+        assert!(parse("return=representation").return_representation());
+    }
+
+    #[test]
+    fn wait() {
+        let prefer = parse("respond-async, wait=10");
+        assert!(prefer.respond_async());
+        assert_eq!(prefer.wait(), Some(Duration::from_secs(10)));
+
+        let prefer = parse("wait=\"5\"; foo=bar, handling=lenient");
+        assert_eq!(prefer.wait(), Some(Duration::from_secs(5)));
+        assert!(!prefer.respond_async());
+
+        assert_eq!(parse("wait=soon"), Prefer::default());
+    }
+
+    #[tokio::test]
+    async fn extractor() {
+        let app = Router::new().route(
+            "/",
+            post(|prefer: Prefer| async move { prefer.return_minimal().to_string() }),
+        );
+        let client = TestClient::new(app);
+
+        let res = client.post("/").header("prefer", "return=minimal").await;
+        assert_eq!(res.text().await, "true");
+
+        let res = client.post("/").await;
+        assert_eq!(res.text().await, "false");
+    }
+}
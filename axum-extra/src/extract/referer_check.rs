@@ -0,0 +1,236 @@
+use super::Origin;
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    response::{IntoResponse, Response},
+};
+use http::{
+    header::{ORIGIN, REFERER},
+    request::Parts,
+    StatusCode, Uri,
+};
+use std::{fmt, sync::Arc};
+
+/// Extractor that checks where a request comes from using the `Origin` or `Referer` header.
+///
+/// This is meant as defense in depth against cross-site request forgery, in addition to
+/// [`CsrfToken`] or `SameSite` cookies. The host of the [`Origin`] header, or of the `Referer`
+/// header if there is no `Origin` header, is compared against the allowed hosts of a
+/// [`RefererCheckConfig`], which must be accessible from the state via [`FromRef`].
+///
+/// If the host isn't allowed, or the origin is `null`, the request is rejected with
+/// `403 Forbidden`. Requests without either header are also rejected, unless
+/// [`RefererCheckConfig::allow_missing`] is set.
+///
+/// Contains the host that was checked, or `None` if neither header was present.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::extract::{RefererCheck, RefererCheckConfig};
+///
+/// async fn transfer(_: RefererCheck) {
+///     // the request comes from an allowed host
+/// }
+///
+/// let config = RefererCheckConfig::new(["example.com", "www.example.com"]);
+///
+/// let app = Router::new()
+///     .route("/transfer", post(transfer))
+///     .with_state(config);
+/// # let _: Router = app;
+/// ```
+///
+/// [`CsrfToken`]: super::CsrfToken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefererCheck(pub Option<String>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for RefererCheck
+where
+    S: Send + Sync,
+    RefererCheckConfig: FromRef<S>,
+{
+    type Rejection = RefererCheckRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let config = RefererCheckConfig::from_ref(state);
+
+        let uri = if let Some(value) = parts.headers.get(ORIGIN) {
+            match Origin::from_header_value(value) {
+                Some(Origin::Uri(uri)) => uri,
+                Some(Origin::Null) | None => return Err(RefererCheckRejection::Forbidden),
+            }
+        } else if let Some(value) = parts.headers.get(REFERER) {
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| value.parse::<Uri>().ok())
+                .ok_or(RefererCheckRejection::Forbidden)?
+        } else if config.allow_missing {
+            return Ok(Self(None));
+        } else {
+            return Err(RefererCheckRejection::Missing);
+        };
+
+        let host = uri.host().ok_or(RefererCheckRejection::Forbidden)?;
+        let authority = uri.authority().map(|authority| authority.as_str());
+        let allowed = config.hosts.iter().any(|allowed| {
+            allowed.eq_ignore_ascii_case(host)
+                || authority.map_or(false, |authority| allowed.eq_ignore_ascii_case(authority))
+        });
+
+        if allowed {
+            Ok(Self(Some(host.to_owned())))
+        } else {
+            Err(RefererCheckRejection::Forbidden)
+        }
+    }
+}
+
+/// Configuration used by [`RefererCheck`].
+///
+/// Cloning is cheap.
+#[derive(Debug, Clone)]
+pub struct RefererCheckConfig {
+    hosts: Arc<[String]>,
+    allow_missing: bool,
+}
+
+impl RefererCheckConfig {
+    /// Create a new `RefererCheckConfig` that allows requests from `hosts`.
+    ///
+    /// Hosts are compared case-insensitively. A host without a port, such as `example.com`,
+    /// matches any port, while a host with a port, such as `example.com:8443`, only matches that
+    /// port.
+    pub fn new<I>(hosts: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            hosts: hosts.into_iter().map(Into::into).collect(),
+            allow_missing: false,
+        }
+    }
+
+    /// Set whether requests without an `Origin` or `Referer` header are allowed.
+    ///
+    /// Defaults to `false`, meaning such requests are rejected. Enabling this is more lenient
+    /// towards clients that strip the headers for privacy, at the cost of not checking their
+    /// requests.
+    pub fn allow_missing(mut self, allow_missing: bool) -> Self {
+        self.allow_missing = allow_missing;
+        self
+    }
+}
+
+/// Rejection used for [`RefererCheck`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RefererCheckRejection {
+    /// The request had neither an `Origin` nor a `Referer` header.
+    Missing,
+    /// The request came from a host that isn't allowed.
+    Forbidden,
+}
+
+impl IntoResponse for RefererCheckRejection {
+    fn into_response(self) -> Response {
+        let body = self.to_string();
+        let status = StatusCode::FORBIDDEN;
+        axum_core::__log_rejection!(rejection_type = Self, body_text = body, status = status,);
+        (status, body).into_response()
+    }
+}
+
+impl fmt::Display for RefererCheckRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "Missing `Origin` or `Referer` header"),
+            Self::Forbidden => write!(f, "Requests from this origin are not allowed"),
+        }
+    }
+}
+
+impl std::error::Error for RefererCheckRejection {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+
+    async fn handler(RefererCheck(host): RefererCheck) -> String {
+        host.unwrap_or_else(|| "none".to_owned())
+    }
+
+    #[tokio::test]
+    async fn allowed() {
+        let app = Router::new()
+            .route("/", post(handler))
+            .with_state(RefererCheckConfig::new(["example.com"]));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("referer", "https://example.com/transfer?amount=1")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "example.com");
+
+        let res = client
+            .post("/")
+            .header("origin", "https://EXAMPLE.com:8443")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn disallowed() {
+        let app = Router::new()
+            .route("/", post(handler))
+            .with_state(RefererCheckConfig::new(["example.com"]));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("referer", "https://evil.example.net/")
+            .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // the `Origin` header takes precedence
+        let res = client
+            .post("/")
+            .header("origin", "https://evil.example.net")
+            .header("referer", "https://example.com/")
+            .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let res = client.post("/").header("origin", "null").await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn missing() {
+        let app = Router::new()
+            .route("/", post(handler))
+            .with_state(RefererCheckConfig::new(["example.com"]));
+
+        let client = TestClient::new(app);
+        let res = client.post("/").await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert_eq!(res.text().await, "Missing `Origin` or `Referer` header");
+
+        let config = RefererCheckConfig::new(["example.com"]).allow_missing(true);
+        let app = Router::new().route("/", post(handler)).with_state(config);
+
+        let client = TestClient::new(app);
+        let res = client.post("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "none");
+    }
+}
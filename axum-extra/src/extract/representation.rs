@@ -0,0 +1,279 @@
+use super::accept_encoding::parse_q;
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+};
+use axum_core::__define_rejection as define_rejection;
+use http::{header::ACCEPT, request::Parts};
+use mime::Mime;
+use std::sync::Arc;
+
+/// Extractor that picks the representation of the response from the `Accept` header and the
+/// `format` query parameter.
+///
+/// The representations the endpoint can produce are provided by [`SupportedRepresentations`],
+/// which must be accessible from the state via [`FromRef`]. Each has a name and a [`Mime`]:
+///
+/// - If the query string has a `format` parameter, the representation with that name is used.
+///   This is handy for links and browsers, which can't set the `Accept` header.
+/// - Otherwise the `Accept` header is negotiated. The representation with the highest q-value
+///   wins, where each representation gets the q-value of the most specific media range that
+///   matches it. Ties are broken by the order of the supported representations.
+/// - If there's neither, the first supported representation is used.
+///
+/// If the `format` parameter names an unknown representation, or the `Accept` header doesn't
+/// accept any of the supported ones, the request is rejected with `406 Not Acceptable`.
+/// Malformed media ranges in the `Accept` header are ignored.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{Representation, SupportedRepresentations};
+///
+/// async fn report(Representation(mime): Representation) {
+///     match mime.subtype().as_str() {
+///         "csv" => { /* render as CSV */ }
+///         _ => { /* render as JSON */ }
+///     }
+/// }
+///
+/// let representations =
+///     SupportedRepresentations::new([("json", mime::APPLICATION_JSON), ("csv", mime::TEXT_CSV)]);
+///
+/// // `GET /report?format=csv` or `GET /report` with `Accept: text/csv` gets CSV
+/// let app = Router::new()
+///     .route("/report", get(report))
+///     .with_state(representations);
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Representation(pub Mime);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Representation
+where
+    S: Send + Sync,
+    SupportedRepresentations: FromRef<S>,
+{
+    type Rejection = NotAcceptable;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let representations = SupportedRepresentations::from_ref(state);
+
+        let format = parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(name, _)| *name == "format")
+            .map(|(_, value)| value);
+        if let Some(format) = format {
+            return representations
+                .get(format)
+                .cloned()
+                .map(Self)
+                .ok_or(NotAcceptable);
+        }
+
+        if !parts.headers.contains_key(ACCEPT) {
+            return representations
+                .representations
+                .first()
+                .map(|(_, mime)| Self(mime.clone()))
+                .ok_or(NotAcceptable);
+        }
+
+        let accept = parts
+            .headers
+            .get_all(ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+        representations
+            .negotiate(&accept)
+            .cloned()
+            .map(Self)
+            .ok_or(NotAcceptable)
+    }
+}
+
+axum_core::__impl_deref!(Representation: Mime);
+
+/// The representations an endpoint can produce, used by [`Representation`].
+///
+/// Cloning is cheap.
+#[derive(Debug, Clone)]
+pub struct SupportedRepresentations {
+    representations: Arc<[(String, Mime)]>,
+}
+
+impl SupportedRepresentations {
+    /// Create a new `SupportedRepresentations` from pairs of names, as used by the `format` query
+    /// parameter, and media types.
+    ///
+    /// The representations are given in the order the server prefers them.
+    pub fn new<I, K>(representations: I) -> Self
+    where
+        I: IntoIterator<Item = (K, Mime)>,
+        K: Into<String>,
+    {
+        Self {
+            representations: representations
+                .into_iter()
+                .map(|(name, mime)| (name.into(), mime))
+                .collect(),
+        }
+    }
+
+    /// Get the media type of the representation named `name`.
+    pub fn get(&self, name: &str) -> Option<&Mime> {
+        self.representations
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, mime)| mime)
+    }
+
+    /// Find the supported media type that best matches a value of the `Accept` header.
+    pub fn negotiate(&self, accept: &str) -> Option<&Mime> {
+        let ranges = accept
+            .split(',')
+            .filter_map(parse_media_range)
+            .collect::<Vec<_>>();
+
+        let mut best: Option<(&Mime, u16)> = None;
+        for (_, mime) in self.representations.iter() {
+            let q = ranges
+                .iter()
+                .filter_map(|(range, q)| specificity(range, mime).map(|s| (s, *q)))
+                .max_by_key(|(specificity, _)| *specificity)
+                .map_or(0, |(_, q)| q);
+            if q > 0 && best.map_or(true, |(_, best_q)| q > best_q) {
+                best = Some((mime, q));
+            }
+        }
+
+        best.map(|(mime, _)| mime)
+    }
+}
+
+/// How specifically `range` matches `mime`, or `None` if it doesn't match.
+fn specificity(range: &Mime, mime: &Mime) -> Option<u8> {
+    if range.type_() == mime::STAR {
+        Some(0)
+    } else if range.type_() != mime.type_() {
+        None
+    } else if range.subtype() == mime::STAR {
+        Some(1)
+    } else if range.subtype() == mime.subtype() && range.suffix() == mime.suffix() {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+fn parse_media_range(item: &str) -> Option<(Mime, u16)> {
+    let range = item.trim().parse::<Mime>().ok()?;
+    let q = match range.get_param("q") {
+        Some(q) => parse_q(q.as_str())?,
+        None => 1000,
+    };
+    Some((range, q))
+}
+
+define_rejection! {
+    #[status = NOT_ACCEPTABLE]
+    #[body = "None of the available representations are acceptable"]
+    /// Rejection type for [`Representation`] used if no supported representation is acceptable.
+    pub struct NotAcceptable;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use http::StatusCode;
+
+    fn representations() -> SupportedRepresentations {
+        SupportedRepresentations::new([
+            ("json", mime::APPLICATION_JSON),
+            ("html", mime::TEXT_HTML),
+            ("csv", mime::TEXT_CSV),
+        ])
+    }
+
+    #[tokio::test]
+    async fn query_override() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|Representation(mime): Representation| async move { mime.to_string() }),
+            )
+            .with_state(representations());
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/?page=2&format=csv")
+            .header("accept", "application/json")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "text/csv");
+
+        let res = client.get("/?format=xml").await;
+        assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+    }
+
+    #[tokio::test]
+    async fn accept_negotiation() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|Representation(mime): Representation| async move { mime.to_string() }),
+            )
+            .with_state(representations());
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("accept", "text/html;q=0.9, text/csv")
+            .await;
+        assert_eq!(res.text().await, "text/csv");
+
+        // ties are broken by the order of the supported representations
+        let res = client.get("/").header("accept", "text/*").await;
+        assert_eq!(res.text().await, "text/html");
+
+        // the more specific range wins
+        let res = client
+            .get("/")
+            .header("accept", "*/*;q=0.5, text/html;q=0.1")
+            .await;
+        assert_eq!(res.text().await, "application/json");
+
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "application/json");
+    }
+
+    #[tokio::test]
+    async fn not_acceptable() {
+        let app = Router::new()
+            .route("/", get(|_: Representation| async {}))
+            .with_state(representations());
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("accept", "image/png").await;
+        assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+
+        let res = client
+            .get("/")
+            .header("accept", "application/json;q=0, text/*;q=0")
+            .await;
+        assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+    }
+}
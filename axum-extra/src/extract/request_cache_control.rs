@@ -0,0 +1,168 @@
+use axum::{async_trait, extract::FromRequestParts};
+use http::{header::CACHE_CONTROL, request::Parts, HeaderMap};
+use std::{convert::Infallible, time::Duration};
+
+/// Extractor for the directives of the request's `Cache-Control` header.
+///
+/// This allows handlers to honor the caching preferences of clients, for example by bypassing an
+/// application level cache when the client sent `no-cache`.
+///
+/// The `no-cache`, `no-store`, `max-age` and `only-if-cached` directives are supported. Unknown
+/// and malformed directives are ignored, so this extractor never rejects the request. If the
+/// header is missing no directives are set.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::RequestCacheControl;
+///
+/// async fn handler(cache_control: RequestCacheControl) {
+///     if cache_control.no_cache() {
+///         // don't serve a cached response
+///     }
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestCacheControl {
+    no_cache: bool,
+    no_store: bool,
+    max_age: Option<Duration>,
+    only_if_cached: bool,
+}
+
+impl RequestCacheControl {
+    /// Parse the directives from the `Cache-Control` headers in `headers`.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let mut cache_control = Self::default();
+
+        let directives = headers
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','));
+
+        for directive in directives {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+
+            if name.eq_ignore_ascii_case("no-cache") {
+                cache_control.no_cache = true;
+            } else if name.eq_ignore_ascii_case("no-store") {
+                cache_control.no_store = true;
+            } else if name.eq_ignore_ascii_case("only-if-cached") {
+                cache_control.only_if_cached = true;
+            } else if name.eq_ignore_ascii_case("max-age") {
+                if let Some(secs) = value.and_then(|value| value.parse().ok()) {
+                    cache_control.max_age = Some(Duration::from_secs(secs));
+                }
+            }
+        }
+
+        cache_control
+    }
+
+    /// Returns `true` if the `no-cache` directive is set, meaning the client doesn't want a
+    /// cached response without it being revalidated.
+    pub fn no_cache(&self) -> bool {
+        self.no_cache
+    }
+
+    /// Returns `true` if the `no-store` directive is set, meaning the request and response
+    /// shouldn't be stored in any cache.
+    pub fn no_store(&self) -> bool {
+        self.no_store
+    }
+
+    /// Returns the value of the `max-age` directive, which is the maximum age of a cached
+    /// response the client is willing to accept.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// Returns `true` if the `only-if-cached` directive is set, meaning the client only wants a
+    /// response if one is cached.
+    pub fn only_if_cached(&self) -> bool {
+        self.only_if_cached
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for RequestCacheControl
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use http::HeaderValue;
+
+    fn parse(values: &[&'static str]) -> RequestCacheControl {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(CACHE_CONTROL, HeaderValue::from_static(value));
+        }
+        RequestCacheControl::from_headers(&headers)
+    }
+
+    #[test]
+    fn no_cache() {
+        let cache_control = parse(&["no-cache"]);
+        assert!(cache_control.no_cache());
+        assert!(!cache_control.no_store());
+        assert_eq!(cache_control.max_age(), None);
+    }
+
+    #[test]
+    fn max_age_zero() {
+        let cache_control = parse(&["max-age=0"]);
+        assert_eq!(cache_control.max_age(), Some(Duration::ZERO));
+        assert!(!cache_control.no_cache());
+    }
+
+    #[test]
+    fn multiple_directives() {
+        let cache_control = parse(&["No-Store, max-age=\"60\", unknown=1", "only-if-cached"]);
+        assert!(cache_control.no_store());
+        assert!(cache_control.only_if_cached());
+        assert_eq!(cache_control.max_age(), Some(Duration::from_secs(60)));
+        assert!(!cache_control.no_cache());
+    }
+
+    #[test]
+    fn missing_or_malformed() {
+        assert_eq!(parse(&[]), RequestCacheControl::default());
+        assert_eq!(parse(&["max-age=soon, ,"]), RequestCacheControl::default());
+    }
+
+    #[tokio::test]
+    async fn extractor() {
+        let app = Router::new().route(
+            "/",
+            get(|cache_control: RequestCacheControl| async move {
+                cache_control.no_cache().to_string()
+            }),
+        );
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("cache-control", "no-cache").await;
+        assert_eq!(res.text().await, "true");
+
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "false");
+    }
+}
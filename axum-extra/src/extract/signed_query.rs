@@ -0,0 +1,267 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use http::{request::Parts, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::Sha256;
+use std::{fmt, sync::Arc};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Extractor for a value signed with HMAC-SHA256 and carried in the query string.
+///
+/// This is useful for tamper-evident links, such as unsubscribe or download links sent by email.
+/// The query string must contain a `payload` parameter holding the URL safe base64 encoded JSON
+/// of the value and a `signature` parameter holding the URL safe base64 encoded signature of the
+/// JSON. Such a query string is created with [`SignedQueryKey::sign`]. Other query parameters
+/// are ignored.
+///
+/// The signature is verified in constant time with the [`SignedQueryKey`], which must be
+/// accessible from the state via [`FromRef`]. If the signature doesn't match the request is
+/// rejected with `403 Forbidden`. If either parameter is missing or malformed, or the payload
+/// can't be deserialized into `T`, the request is rejected with `400 Bad Request`.
+///
+/// Note that the signature doesn't expire. Include an expiry time in `T` and check it in the
+/// handler if links should only be valid for a limited time.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::{SignedQuery, SignedQueryKey};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Unsubscribe {
+///     user_id: u64,
+/// }
+///
+/// async fn unsubscribe(SignedQuery(params): SignedQuery<Unsubscribe>) {
+///     // `params.user_id` was created by us and hasn't been tampered with
+/// }
+///
+/// let key = SignedQueryKey::new(b"a secret key, at least 32 bytes long");
+///
+/// let app = Router::new()
+///     .route("/unsubscribe", get(unsubscribe))
+///     .with_state(key);
+/// # let _: Router = app;
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "signed-query")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SignedQuery<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for SignedQuery<T>
+where
+    S: Send + Sync,
+    SignedQueryKey: FromRef<S>,
+    T: DeserializeOwned,
+{
+    type Rejection = SignedQueryRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let key = SignedQueryKey::from_ref(state);
+
+        let query = parts.uri.query().unwrap_or_default();
+        let param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(param_name, _)| *param_name == name)
+                .and_then(|(_, value)| URL_SAFE_NO_PAD.decode(value).ok())
+                .ok_or(SignedQueryRejection::Malformed)
+        };
+        let payload = param("payload")?;
+        let signature = param("signature")?;
+
+        key.mac(&payload)
+            .verify_slice(&signature)
+            .map_err(|_| SignedQueryRejection::InvalidSignature)?;
+
+        serde_json::from_slice(&payload)
+            .map(Self)
+            .map_err(SignedQueryRejection::InvalidPayload)
+    }
+}
+
+axum_core::__impl_deref!(SignedQuery);
+
+/// The key used to sign and verify [`SignedQuery`] values.
+///
+/// Cloning is cheap.
+#[cfg_attr(docsrs, doc(cfg(feature = "signed-query")))]
+#[derive(Clone)]
+pub struct SignedQueryKey {
+    key: Arc<[u8]>,
+}
+
+impl SignedQueryKey {
+    /// Create a new `SignedQueryKey`.
+    ///
+    /// The key should be at least 32 random bytes.
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: key.as_ref().into(),
+        }
+    }
+
+    /// Sign `value` and return a query string, without the leading `?`, that [`SignedQuery`]
+    /// extracts `value` from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` can't be serialized to JSON.
+    pub fn sign<T>(&self, value: &T) -> Result<String, serde_json::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let payload = serde_json::to_vec(value)?;
+        let signature = self.mac(&payload).finalize().into_bytes();
+
+        Ok(format!(
+            "payload={}&signature={}",
+            URL_SAFE_NO_PAD.encode(&payload),
+            URL_SAFE_NO_PAD.encode(signature),
+        ))
+    }
+
+    fn mac(&self, payload: &[u8]) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any size");
+        mac.update(payload);
+        mac
+    }
+}
+
+impl fmt::Debug for SignedQueryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignedQueryKey").finish_non_exhaustive()
+    }
+}
+
+/// Rejection used for [`SignedQuery`].
+#[cfg_attr(docsrs, doc(cfg(feature = "signed-query")))]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SignedQueryRejection {
+    /// The `payload` or `signature` query parameter was missing or not valid base64.
+    Malformed,
+    /// The signature didn't match the payload.
+    InvalidSignature,
+    /// The payload couldn't be deserialized.
+    InvalidPayload(serde_json::Error),
+}
+
+impl IntoResponse for SignedQueryRejection {
+    fn into_response(self) -> Response {
+        let body = self.to_string();
+        let status = match self {
+            Self::Malformed | Self::InvalidPayload(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidSignature => StatusCode::FORBIDDEN,
+        };
+        axum_core::__log_rejection!(rejection_type = Self, body_text = body, status = status,);
+        (status, body).into_response()
+    }
+}
+
+impl fmt::Display for SignedQueryRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "Missing or malformed signed query parameters"),
+            Self::InvalidSignature => write!(f, "Invalid signature"),
+            Self::InvalidPayload(err) => write!(f, "Failed to deserialize the payload: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SignedQueryRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Malformed | Self::InvalidSignature => None,
+            Self::InvalidPayload(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Deserialize)]
+    struct Params {
+        user_id: u64,
+    }
+
+    fn key() -> SignedQueryKey {
+        SignedQueryKey::new(b"an insecure key only used in tests")
+    }
+
+    #[tokio::test]
+    async fn valid_signature() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|SignedQuery(params): SignedQuery<Params>| async move {
+                    params.user_id.to_string()
+                }),
+            )
+            .with_state(key());
+
+        let client = TestClient::new(app);
+
+        let query = key().sign(&json!({ "user_id": 42 })).unwrap();
+        let res = client.get(&format!("/?{query}&utm_source=email")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "42");
+    }
+
+    #[tokio::test]
+    async fn tampered_payload() {
+        let app = Router::new()
+            .route("/", get(|_: SignedQuery<Params>| async {}))
+            .with_state(key());
+
+        let client = TestClient::new(app);
+
+        let query = key().sign(&json!({ "user_id": 42 })).unwrap();
+        let (_, signature) = query.split_once('&').unwrap();
+        let payload = URL_SAFE_NO_PAD.encode(br#"{"user_id":1}"#);
+        let res = client
+            .get(&format!("/?payload={payload}&{signature}"))
+            .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let other_key = SignedQueryKey::new(b"another key");
+        let query = other_key.sign(&json!({ "user_id": 42 })).unwrap();
+        let res = client.get(&format!("/?{query}")).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn malformed() {
+        let app = Router::new()
+            .route("/", get(|_: SignedQuery<Params>| async {}))
+            .with_state(key());
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = client.get("/?payload=!!!&signature=!!!").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        // correctly signed, but not the expected shape
+        let query = key().sign(&json!({ "user": "bob" })).unwrap();
+        let res = client.get(&format!("/?{query}")).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,349 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+};
+use http::request::Parts;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+/// Cache results of other extractors across requests.
+///
+/// Where [`Cached`](super::Cached) caches a value for the duration of a single request,
+/// `StateCached` stores values in a [`StateCache`] held in your application state, so they are
+/// shared by all requests. Values are looked up by a key computed with [`CacheKey`], which should
+/// be much cheaper than running the extractor itself. The wrapped extractor only runs when there
+/// is no cached value for the key.
+///
+/// This is useful for expensive extractions whose result rarely changes, such as parsing a
+/// configuration header. It trades memory for CPU, bounded by the capacity of the cache.
+///
+/// Note that the cache is not locked while the wrapped extractor runs, so concurrent requests
+/// with the same key might both run it. Failed extractions are not cached.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{
+///     async_trait,
+///     extract::{FromRef, FromRequestParts},
+///     http::{request::Parts, HeaderValue, StatusCode},
+///     routing::get,
+///     Router,
+/// };
+/// use axum_extra::extract::{CacheKey, StateCache, StateCached};
+///
+/// #[derive(Clone)]
+/// struct ClientConfig { /* ... */ }
+///
+/// #[async_trait]
+/// impl<S> FromRequestParts<S> for ClientConfig
+/// where
+///     S: Send + Sync,
+/// {
+///     type Rejection = StatusCode;
+///
+///     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+///         // expensive parsing of the `x-client-config` header...
+///         # unimplemented!()
+///     }
+/// }
+///
+/// impl<S> CacheKey<S> for ClientConfig {
+///     type Key = HeaderValue;
+///
+///     fn cache_key(parts: &Parts, _state: &S) -> Option<Self::Key> {
+///         parts.headers.get("x-client-config").cloned()
+///     }
+/// }
+///
+/// #[derive(Clone)]
+/// struct AppState {
+///     config_cache: StateCache<HeaderValue, ClientConfig>,
+/// }
+///
+/// impl FromRef<AppState> for StateCache<HeaderValue, ClientConfig> {
+///     fn from_ref(state: &AppState) -> Self {
+///         state.config_cache.clone()
+///     }
+/// }
+///
+/// async fn handler(StateCached(config): StateCached<ClientConfig>) {
+///     // ...
+/// }
+///
+/// let state = AppState {
+///     config_cache: StateCache::new(1024),
+/// };
+///
+/// let app = Router::new().route("/", get(handler)).with_state(state);
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StateCached<T>(pub T);
+
+/// Compute the key used by [`StateCached`] to look up cached values.
+pub trait CacheKey<S> {
+    /// The type of the key.
+    type Key: Hash + Eq + Send + 'static;
+
+    /// Compute the key for the current request.
+    ///
+    /// Return `None` to skip the cache and always run the extractor.
+    fn cache_key(parts: &Parts, state: &S) -> Option<Self::Key>;
+}
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for StateCached<T>
+where
+    S: Send + Sync,
+    T: FromRequestParts<S> + CacheKey<S> + Clone + Send + 'static,
+    StateCache<T::Key, T>: FromRef<S>,
+{
+    type Rejection = T::Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(key) = T::cache_key(parts, state) else {
+            return T::from_request_parts(parts, state).await.map(Self);
+        };
+
+        let cache = StateCache::<T::Key, T>::from_ref(state);
+        if let Some(value) = cache.get(&key) {
+            return Ok(Self(value));
+        }
+
+        let value = T::from_request_parts(parts, state).await?;
+        cache.insert(key, value.clone());
+        Ok(Self(value))
+    }
+}
+
+axum_core::__impl_deref!(StateCached);
+
+/// Cache used by [`StateCached`].
+///
+/// Cloning the cache is cheap and the clones share the same entries.
+///
+/// The cache holds at most `capacity` entries. When it's full, the least recently used entry is
+/// evicted to make room for a new one.
+pub struct StateCache<K, V> {
+    inner: Arc<Mutex<Lru<K, V>>>,
+}
+
+struct Lru<K, V> {
+    entries: HashMap<Arc<K>, (V, u64)>,
+    // keys by the tick they were last used at, least recently used first
+    order: BTreeMap<u64, Arc<K>>,
+    capacity: usize,
+    tick: u64,
+}
+
+impl<K, V> Lru<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (value, last_used) = self.entries.get_mut(key)?;
+        if let Some(key) = self.order.remove(&*last_used) {
+            self.order.insert(tick, key);
+        }
+        *last_used = tick;
+        Some(value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.tick += 1;
+        let tick = self.tick;
+
+        if let Some((old_value, last_used)) = self.entries.get_mut(&key) {
+            *old_value = value;
+            if let Some(key) = self.order.remove(&*last_used) {
+                self.order.insert(tick, key);
+            }
+            *last_used = tick;
+            return;
+        }
+
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        let key = Arc::new(key);
+        self.order.insert(tick, Arc::clone(&key));
+        self.entries.insert(key, (value, tick));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+impl<K, V> StateCache<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    /// Create a new cache that holds at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Lru {
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+                capacity,
+                tick: 0,
+            })),
+        }
+    }
+
+    /// Get the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    /// Returns `true` if the cache contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all entries from the cache.
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().clear();
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        self.inner.lock().unwrap().get(key)
+    }
+
+    fn insert(&self, key: K, value: V) {
+        self.inner.lock().unwrap().insert(key, value);
+    }
+}
+
+impl<K, V> Clone for StateCache<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for StateCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateCache").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Expensive(String);
+
+    #[async_trait]
+    impl<S> FromRequestParts<S> for Expensive
+    where
+        S: Send + Sync,
+    {
+        type Rejection = Infallible;
+
+        async fn from_request_parts(
+            parts: &mut Parts,
+            _state: &S,
+        ) -> Result<Self, Self::Rejection> {
+            COUNTER.fetch_add(1, Ordering::SeqCst);
+            Ok(Self(parts.uri.path().to_owned()))
+        }
+    }
+
+    impl<S> CacheKey<S> for Expensive {
+        type Key = String;
+
+        fn cache_key(parts: &Parts, _state: &S) -> Option<Self::Key> {
+            Some(parts.uri.path().to_owned())
+        }
+    }
+
+    #[derive(Clone)]
+    struct AppState {
+        cache: StateCache<String, Expensive>,
+    }
+
+    impl FromRef<AppState> for StateCache<String, Expensive> {
+        fn from_ref(state: &AppState) -> Self {
+            state.cache.clone()
+        }
+    }
+
+    async fn extract(state: &AppState, path: &str) -> Expensive {
+        let (mut parts, _) = Request::get(path).body(()).unwrap().into_parts();
+        StateCached::<Expensive>::from_request_parts(&mut parts, state)
+            .await
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn runs_once_per_key_and_evicts_least_recently_used() {
+        let state = AppState {
+            cache: StateCache::new(2),
+        };
+
+        // two requests with the same key only run the extractor once
+        assert_eq!(extract(&state, "/a").await, Expensive("/a".to_owned()));
+        assert_eq!(extract(&state, "/a").await, Expensive("/a".to_owned()));
+        assert_eq!(COUNTER.load(Ordering::SeqCst), 1);
+
+        extract(&state, "/b").await;
+        assert_eq!(COUNTER.load(Ordering::SeqCst), 2);
+
+        // `/a` was used more recently than `/b` so `/b` gets evicted
+        extract(&state, "/a").await;
+        extract(&state, "/c").await;
+        assert_eq!(COUNTER.load(Ordering::SeqCst), 3);
+        assert_eq!(state.cache.len(), 2);
+
+        extract(&state, "/a").await;
+        assert_eq!(COUNTER.load(Ordering::SeqCst), 3);
+        extract(&state, "/b").await;
+        assert_eq!(COUNTER.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn replacing_a_value_marks_it_as_used() {
+        let cache = StateCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("a", 3);
+        cache.insert("c", 4);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"a"), Some(3));
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"c"), Some(4));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(cache.inner.lock().unwrap().order.is_empty());
+    }
+}
@@ -0,0 +1,338 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequest, FromRequestParts, Request},
+    response::{IntoResponse, Response},
+    RequestExt,
+};
+use bytes::Bytes;
+use http::StatusCode;
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use std::{collections::HashMap, fmt, hash::Hash, sync::Arc};
+
+/// Extractor that buffers the request body with a size limit that depends on the tenant making
+/// the request.
+///
+/// The tenant is extracted first with `T`, which must implement [`FromRequestParts`]. Its byte
+/// quota is then looked up in [`TenantQuotas`], which must be accessible from the state via
+/// [`FromRef`], and the body is buffered into [`Bytes`] with that limit. Tenants without a quota
+/// get the default limit of the `TenantQuotas`.
+///
+/// If the body exceeds the limit the request is rejected with `413 Payload Too Large`. The
+/// [default body limit] still applies on top of the quota, so it must be raised (or disabled) for
+/// quotas larger than it.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     async_trait,
+///     extract::{DefaultBodyLimit, FromRequestParts},
+///     http::{request::Parts, StatusCode},
+///     routing::post,
+///     Router,
+/// };
+/// use axum_extra::extract::{TenantLimitedBytes, TenantQuotas};
+///
+/// #[derive(Clone, PartialEq, Eq, Hash)]
+/// struct TenantId(String);
+///
+/// #[async_trait]
+/// impl<S> FromRequestParts<S> for TenantId
+/// where
+///     S: Send + Sync,
+/// {
+///     type Rejection = StatusCode;
+///
+///     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+///         parts
+///             .headers
+///             .get("x-tenant-id")
+///             .and_then(|value| value.to_str().ok())
+///             .map(|value| Self(value.to_owned()))
+///             .ok_or(StatusCode::BAD_REQUEST)
+///     }
+/// }
+///
+/// async fn upload(TenantLimitedBytes { tenant, bytes }: TenantLimitedBytes<TenantId>) {
+///     // ...
+/// }
+///
+/// let quotas = TenantQuotas::new(1024 * 1024)
+///     .with_quota(TenantId("big-customer".to_owned()), 100 * 1024 * 1024);
+///
+/// let app = Router::new()
+///     .route("/upload", post(upload))
+///     .layer(DefaultBodyLimit::max(100 * 1024 * 1024))
+///     .with_state(quotas);
+/// # let _: Router = app;
+/// ```
+///
+/// [default body limit]: axum::extract::DefaultBodyLimit
+#[derive(Debug, Clone)]
+pub struct TenantLimitedBytes<T> {
+    /// The tenant that made the request.
+    pub tenant: T,
+    /// The buffered request body.
+    pub bytes: Bytes,
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for TenantLimitedBytes<T>
+where
+    S: Send + Sync,
+    T: FromRequestParts<S> + Hash + Eq + Send + Sync + 'static,
+    TenantQuotas<T>: FromRef<S>,
+{
+    type Rejection = TenantLimitedBytesRejection<T::Rejection>;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let (mut parts, body) = req.with_limited_body().into_parts();
+
+        let tenant = T::from_request_parts(&mut parts, state)
+            .await
+            .map_err(TenantLimitedBytesRejection::Tenant)?;
+
+        let limit = TenantQuotas::<T>::from_ref(state).quota(&tenant);
+
+        let bytes = Limited::new(body, limit)
+            .collect()
+            .await
+            .map_err(|err| {
+                if is_length_limit_error(&*err) {
+                    TenantLimitedBytesRejection::PayloadTooLarge
+                } else {
+                    TenantLimitedBytesRejection::FailedToBufferBody(axum::Error::new(err))
+                }
+            })?
+            .to_bytes();
+
+        Ok(Self { tenant, bytes })
+    }
+}
+
+/// Check whether `err` was caused by a length limit, which might be either the tenant's quota or
+/// the default body limit that wraps the body in another `Limited` and an [`axum::Error`].
+fn is_length_limit_error(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if err.is::<LengthLimitError>() {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Per-tenant body size limits used by [`TenantLimitedBytes`].
+///
+/// Cloning is cheap.
+pub struct TenantQuotas<T> {
+    default: usize,
+    quotas: Arc<HashMap<T, usize>>,
+}
+
+impl<T> TenantQuotas<T>
+where
+    T: Hash + Eq,
+{
+    /// Create a new `TenantQuotas` where every tenant is limited to `default` bytes.
+    pub fn new(default: usize) -> Self {
+        Self {
+            default,
+            quotas: Default::default(),
+        }
+    }
+
+    /// Set the quota of a tenant, in bytes.
+    pub fn with_quota(mut self, tenant: T, limit: usize) -> Self
+    where
+        T: Clone,
+    {
+        Arc::make_mut(&mut self.quotas).insert(tenant, limit);
+        self
+    }
+
+    /// Get the quota of a tenant, in bytes.
+    pub fn quota(&self, tenant: &T) -> usize {
+        self.quotas.get(tenant).copied().unwrap_or(self.default)
+    }
+}
+
+impl<T> Clone for TenantQuotas<T> {
+    fn clone(&self) -> Self {
+        Self {
+            default: self.default,
+            quotas: Arc::clone(&self.quotas),
+        }
+    }
+}
+
+impl<T> fmt::Debug for TenantQuotas<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantQuotas")
+            .field("default", &self.default)
+            .field("quotas", &self.quotas)
+            .finish()
+    }
+}
+
+/// Rejection used for [`TenantLimitedBytes`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TenantLimitedBytesRejection<R> {
+    /// Extracting the tenant failed.
+    Tenant(R),
+    /// The body exceeded the tenant's quota.
+    PayloadTooLarge,
+    /// Buffering the body failed.
+    FailedToBufferBody(axum::Error),
+}
+
+impl<R> IntoResponse for TenantLimitedBytesRejection<R>
+where
+    R: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        match self {
+            Self::Tenant(inner) => inner.into_response(),
+            Self::PayloadTooLarge => {
+                (StatusCode::PAYLOAD_TOO_LARGE, self.to_string()).into_response()
+            }
+            Self::FailedToBufferBody(_) => {
+                (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+            }
+        }
+    }
+}
+
+impl<R> fmt::Display for TenantLimitedBytesRejection<R>
+where
+    R: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tenant(inner) => inner.fmt(f),
+            Self::PayloadTooLarge => write!(f, "Request body exceeded the tenant's quota"),
+            Self::FailedToBufferBody(err) => write!(f, "Failed to buffer the request body: {err}"),
+        }
+    }
+}
+
+impl<R> std::error::Error for TenantLimitedBytesRejection<R>
+where
+    R: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Tenant(inner) => Some(inner),
+            Self::PayloadTooLarge => None,
+            Self::FailedToBufferBody(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{extract::DefaultBodyLimit, routing::post, Router};
+    use http::request::Parts;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct TenantId(String);
+
+    #[async_trait]
+    impl<S> FromRequestParts<S> for TenantId
+    where
+        S: Send + Sync,
+    {
+        type Rejection = StatusCode;
+
+        async fn from_request_parts(
+            parts: &mut Parts,
+            _state: &S,
+        ) -> Result<Self, Self::Rejection> {
+            parts
+                .headers
+                .get("x-tenant-id")
+                .and_then(|value| value.to_str().ok())
+                .map(|value| Self(value.to_owned()))
+                .ok_or(StatusCode::BAD_REQUEST)
+        }
+    }
+
+    async fn handler(TenantLimitedBytes { tenant, bytes }: TenantLimitedBytes<TenantId>) -> String {
+        format!("{}: {}", tenant.0, bytes.len())
+    }
+
+    #[tokio::test]
+    async fn limits_per_tenant() {
+        let quotas = TenantQuotas::new(4)
+            .with_quota(TenantId("small".to_owned()), 2)
+            .with_quota(TenantId("large".to_owned()), 8);
+        let app = Router::new().route("/", post(handler)).with_state(quotas);
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("x-tenant-id", "large")
+            .body("abcdef")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "large: 6");
+
+        let res = client
+            .post("/")
+            .header("x-tenant-id", "small")
+            .body("abcdef")
+            .await;
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let res = client
+            .post("/")
+            .header("x-tenant-id", "small")
+            .body("ab")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "small: 2");
+    }
+
+    #[tokio::test]
+    async fn default_quota_and_tenant_rejection() {
+        let quotas = TenantQuotas::<TenantId>::new(4);
+        let app = Router::new().route("/", post(handler)).with_state(quotas);
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("x-tenant-id", "other")
+            .body("abcdef")
+            .await;
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let res = client.post("/").body("ab").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+    #[tokio::test]
+    async fn default_body_limit_smaller_than_quota() {
+        let quotas = TenantQuotas::new(8);
+        let app = Router::new()
+            .route("/", post(handler))
+            .layer(DefaultBodyLimit::max(4))
+            .with_state(quotas);
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("x-tenant-id", "other")
+            .body("abcdef")
+            .await;
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
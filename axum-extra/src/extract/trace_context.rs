@@ -0,0 +1,263 @@
+use axum::{async_trait, extract::FromRequestParts};
+use axum_core::__define_rejection as define_rejection;
+use http::{request::Parts, HeaderMap, HeaderName};
+use std::fmt;
+
+const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
+const TRACESTATE: HeaderName = HeaderName::from_static("tracestate");
+
+const FLAG_SAMPLED: u8 = 0x01;
+
+/// Extractor for the [W3C Trace Context] of a request.
+///
+/// The context is parsed from the `traceparent` and `tracestate` headers. If there's no
+/// `traceparent` header a new root context, with a random trace id and parent id, is created
+/// instead. If the `traceparent` header is malformed the request is rejected with
+/// `400 Bad Request`.
+///
+/// This allows propagating the context to outgoing requests without depending on a full
+/// OpenTelemetry setup. The [`Display`](fmt::Display) implementation formats the context as a
+/// `traceparent` header value.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::TraceContext;
+///
+/// async fn handler(ctx: TraceContext) {
+///     // forward the context to another service
+///     let traceparent = ctx.to_string();
+///     let tracestate = ctx.trace_state();
+///     // ...
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+///
+/// [W3C Trace Context]: https://www.w3.org/TR/trace-context/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    version: u8,
+    trace_id: u128,
+    parent_id: u64,
+    flags: u8,
+    trace_state: Option<String>,
+    remote: bool,
+}
+
+impl TraceContext {
+    /// The id of the whole trace.
+    pub fn trace_id(&self) -> u128 {
+        self.trace_id
+    }
+
+    /// The id of the caller's span.
+    pub fn parent_id(&self) -> u64 {
+        self.parent_id
+    }
+
+    /// The trace flags.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Whether the caller may have recorded the trace.
+    pub fn is_sampled(&self) -> bool {
+        self.flags & FLAG_SAMPLED != 0
+    }
+
+    /// The vendor specific `tracestate` header, if any.
+    ///
+    /// Multiple `tracestate` headers are combined into one, separated by commas.
+    pub fn trace_state(&self) -> Option<&str> {
+        self.trace_state.as_deref()
+    }
+
+    /// Whether the context was sent by the client, rather than being created because the request
+    /// didn't have a `traceparent` header.
+    pub fn is_remote(&self) -> bool {
+        self.remote
+    }
+
+    fn new_root() -> Self {
+        Self {
+            version: 0,
+            trace_id: rand::random::<u128>().max(1),
+            parent_id: rand::random::<u64>().max(1),
+            flags: 0,
+            trace_state: None,
+            remote: false,
+        }
+    }
+
+    fn from_headers(headers: &HeaderMap) -> Result<Self, InvalidTraceParent> {
+        let Some(traceparent) = headers.get(TRACEPARENT) else {
+            return Ok(Self::new_root());
+        };
+
+        let mut ctx = traceparent
+            .to_str()
+            .ok()
+            .and_then(parse_traceparent)
+            .ok_or(InvalidTraceParent)?;
+
+        let trace_state = headers
+            .get_all(TRACESTATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<_>>();
+        if !trace_state.is_empty() {
+            ctx.trace_state = Some(trace_state.join(","));
+        }
+
+        Ok(ctx)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for TraceContext
+where
+    S: Send + Sync,
+{
+    type Rejection = InvalidTraceParent;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::from_headers(&parts.headers)
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02x}-{:032x}-{:016x}-{:02x}",
+            self.version, self.trace_id, self.parent_id, self.flags
+        )
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Invalid `traceparent` header"]
+    /// Rejection type for [`TraceContext`] used if the `traceparent` header is malformed.
+    pub struct InvalidTraceParent;
+}
+
+/// Parse a `traceparent` header value of the form `{version}-{trace-id}-{parent-id}-{flags}`.
+fn parse_traceparent(value: &str) -> Option<TraceContext> {
+    let mut fields = value.trim().split('-');
+
+    let version = u8::from_str_radix(hex(fields.next()?, 2)?, 16).ok()?;
+    let trace_id = u128::from_str_radix(hex(fields.next()?, 32)?, 16).ok()?;
+    let parent_id = u64::from_str_radix(hex(fields.next()?, 16)?, 16).ok()?;
+    let flags = u8::from_str_radix(hex(fields.next()?, 2)?, 16).ok()?;
+
+    // version `ff` is forbidden and future versions may append more fields, but version `00`
+    // must not have any
+    if version == 0xff || (version == 0 && fields.next().is_some()) {
+        return None;
+    }
+
+    // all zero ids are invalid
+    if trace_id == 0 || parent_id == 0 {
+        return None;
+    }
+
+    Some(TraceContext {
+        version,
+        trace_id,
+        parent_id,
+        flags,
+        trace_state: None,
+        remote: true,
+    })
+}
+
+/// Check that `field` consists of exactly `len` lowercase hex digits.
+fn hex(field: &str, len: usize) -> Option<&str> {
+    let valid = field.len() == len
+        && field
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    valid.then_some(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use http::StatusCode;
+
+    #[tokio::test]
+    async fn valid_header() {
+        let app = Router::new().route(
+            "/",
+            get(|ctx: TraceContext| async move {
+                format!(
+                    "{} {} {:?}",
+                    ctx,
+                    ctx.is_remote(),
+                    ctx.trace_state().unwrap_or_default()
+                )
+            }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .header("tracestate", "rojo=00f067aa0ba902b7")
+            .header("tracestate", "congo=t61rcWkgMzE")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.text().await,
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01 true \
+             \"rojo=00f067aa0ba902b7,congo=t61rcWkgMzE\""
+        );
+    }
+
+    #[tokio::test]
+    async fn malformed_header() {
+        let app = Router::new().route("/", get(|_: TraceContext| async {}));
+
+        let client = TestClient::new(app);
+
+        for traceparent in [
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        ] {
+            let res = client.get("/").header("traceparent", traceparent).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{traceparent}");
+        }
+    }
+
+    #[tokio::test]
+    async fn absent_header() {
+        let ctx = TraceContext::from_headers(&HeaderMap::new()).unwrap();
+        assert!(!ctx.is_remote());
+        assert_ne!(ctx.trace_id(), 0);
+        assert_ne!(ctx.parent_id(), 0);
+        assert_eq!(ctx.trace_state(), None);
+
+        // the generated context round-trips through the header format
+        let formatted = ctx.to_string();
+        let parsed = parse_traceparent(&formatted).unwrap();
+        assert_eq!(parsed.trace_id(), ctx.trace_id());
+        assert_eq!(parsed.parent_id(), ctx.parent_id());
+    }
+}
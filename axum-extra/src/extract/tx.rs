@@ -0,0 +1,368 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts, Request},
+    response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+use http::{request::Parts, StatusCode};
+use sqlx::{Database, Pool, Transaction};
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Extractor for a database transaction that's committed if the request succeeds.
+///
+/// The transaction is started from a [`Pool`], which must be accessible from the state via
+/// [`FromRef`], the first time `Tx` is extracted. After the handler has run, [`TxLayer`] commits
+/// the transaction if the response has a `2xx` status and rolls it back otherwise. If committing
+/// fails the response is replaced with `500 Internal Server Error`.
+///
+/// `Tx` dereferences to the [`Transaction`], so queries can be executed with `&mut **tx`.
+///
+/// `Tx` can only be extracted once per request and requires [`TxLayer`] to be added to the
+/// router. Otherwise the request is rejected with `500 Internal Server Error`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::extract::{Tx, TxLayer};
+/// use sqlx::{Sqlite, SqlitePool};
+///
+/// async fn create_user(mut tx: Tx<Sqlite>) {
+///     sqlx::query("INSERT INTO users (name) VALUES ('alice')")
+///         .execute(&mut **tx)
+///         .await
+///         .unwrap();
+///
+///     // the transaction is committed after the handler returns
+/// }
+///
+/// # async {
+/// let pool = SqlitePool::connect("sqlite://app.db").await.unwrap();
+///
+/// let app = Router::new()
+///     .route("/users", post(create_user))
+///     .layer(TxLayer::<Sqlite>::new())
+///     .with_state(pool);
+/// # let _: Router = app;
+/// # };
+/// ```
+pub struct Tx<DB>
+where
+    DB: Database,
+{
+    guard: OwnedMutexGuard<Option<Transaction<'static, DB>>>,
+}
+
+#[async_trait]
+impl<S, DB> FromRequestParts<S> for Tx<DB>
+where
+    S: Send + Sync,
+    DB: Database,
+    Pool<DB>: FromRef<S>,
+{
+    type Rejection = TxRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let TxSlot(slot) = parts
+            .extensions
+            .get::<TxSlot<DB>>()
+            .ok_or(TxRejection::MissingLayer)?;
+
+        let mut guard = Arc::clone(slot)
+            .try_lock_owned()
+            .map_err(|_| TxRejection::AlreadyExtracted)?;
+
+        if guard.is_none() {
+            let tx = Pool::<DB>::from_ref(state)
+                .begin()
+                .await
+                .map_err(TxRejection::Begin)?;
+            *guard = Some(tx);
+        }
+
+        Ok(Self { guard })
+    }
+}
+
+impl<DB> Deref for Tx<DB>
+where
+    DB: Database,
+{
+    type Target = Transaction<'static, DB>;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().expect("transaction has been started")
+    }
+}
+
+impl<DB> DerefMut for Tx<DB>
+where
+    DB: Database,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().expect("transaction has been started")
+    }
+}
+
+impl<DB> fmt::Debug for Tx<DB>
+where
+    DB: Database,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tx").finish_non_exhaustive()
+    }
+}
+
+/// Request extension shared between [`Tx`] and [`TxService`].
+struct TxSlot<DB>(Arc<Mutex<Option<Transaction<'static, DB>>>>)
+where
+    DB: Database;
+
+impl<DB> Clone for TxSlot<DB>
+where
+    DB: Database,
+{
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+/// Rejection used for [`Tx`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TxRejection {
+    /// [`TxLayer`] hasn't been added to the router.
+    MissingLayer,
+    /// `Tx` was extracted more than once for the same request.
+    AlreadyExtracted,
+    /// Starting the transaction failed.
+    Begin(sqlx::Error),
+}
+
+impl IntoResponse for TxRejection {
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+    }
+}
+
+impl fmt::Display for TxRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLayer => write!(f, "Missing `TxLayer`"),
+            Self::AlreadyExtracted => write!(f, "`Tx` can only be extracted once per request"),
+            Self::Begin(err) => write!(f, "Failed to start transaction: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for TxRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingLayer | Self::AlreadyExtracted => None,
+            Self::Begin(err) => Some(err),
+        }
+    }
+}
+
+/// Layer that applies [`TxService`], which is required by [`Tx`].
+pub struct TxLayer<DB> {
+    _db: PhantomData<fn() -> DB>,
+}
+
+impl<DB> TxLayer<DB> {
+    /// Create a new `TxLayer`.
+    pub fn new() -> Self {
+        Self { _db: PhantomData }
+    }
+}
+
+impl<DB> Default for TxLayer<DB> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<DB> Clone for TxLayer<DB> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<DB> fmt::Debug for TxLayer<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TxLayer").finish()
+    }
+}
+
+impl<S, DB> Layer<S> for TxLayer<DB> {
+    type Service = TxService<S, DB>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TxService {
+            inner,
+            _db: PhantomData,
+        }
+    }
+}
+
+/// Middleware that commits or rolls back the transaction started by [`Tx`].
+///
+/// Created with [`TxLayer`].
+pub struct TxService<S, DB> {
+    inner: S,
+    _db: PhantomData<fn() -> DB>,
+}
+
+impl<S, DB> Clone for TxService<S, DB>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _db: PhantomData,
+        }
+    }
+}
+
+impl<S, DB> fmt::Debug for TxService<S, DB>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TxService")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<S, DB> Service<Request> for TxService<S, DB>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    DB: Database,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        let slot = TxSlot::<DB>(Default::default());
+        req.extensions_mut().insert(slot.clone());
+
+        Box::pin(async move {
+            let res = inner.call(req).await?;
+
+            // if `Tx` is still alive, for example because it was moved into a spawned task, the
+            // transaction is rolled back when it's dropped
+            let tx = match slot.0.try_lock() {
+                Ok(mut guard) => guard.take(),
+                Err(_) => None,
+            };
+
+            let Some(tx) = tx else {
+                return Ok(res);
+            };
+
+            if res.status().is_success() {
+                if let Err(err) = tx.commit().await {
+                    return Ok((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        format!("Failed to commit transaction: {err}"),
+                    )
+                        .into_response());
+                }
+            } else {
+                // the response is returned regardless and the connection discards the
+                // transaction if rolling back fails
+                let _ = tx.rollback().await;
+            }
+
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+    use sqlx::{sqlite::SqlitePoolOptions, Sqlite, SqlitePool};
+
+    async fn insert(mut tx: Tx<Sqlite>, status: StatusCode) -> StatusCode {
+        sqlx::query("INSERT INTO items (name) VALUES ('item')")
+            .execute(&mut **tx)
+            .await
+            .unwrap();
+        status
+    }
+
+    async fn count(pool: &SqlitePool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM items")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn commits_on_success_and_rolls_back_on_error() {
+        // a single connection so every query sees the same in-memory database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE items (name TEXT NOT NULL)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let app = Router::new()
+            .route("/ok", post(|tx: Tx<Sqlite>| insert(tx, StatusCode::OK)))
+            .route(
+                "/error",
+                post(|tx: Tx<Sqlite>| insert(tx, StatusCode::INTERNAL_SERVER_ERROR)),
+            )
+            .layer(TxLayer::<Sqlite>::new())
+            .with_state(pool.clone());
+        let client = TestClient::new(app);
+
+        let res = client.post("/ok").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(count(&pool).await, 1);
+
+        let res = client.post("/error").await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(count(&pool).await, 1);
+    }
+
+    #[tokio::test]
+    async fn missing_layer() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let app = Router::new()
+            .route("/", post(|_: Tx<Sqlite>| async {}))
+            .with_state(pool);
+        let client = TestClient::new(app);
+
+        let res = client.post("/").await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(res.text().await, "Missing `TxLayer`");
+    }
+}
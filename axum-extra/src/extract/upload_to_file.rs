@@ -0,0 +1,189 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use futures_util::StreamExt;
+use http::{header::CONTENT_LENGTH, StatusCode};
+use std::{
+    fmt,
+    io::{self, SeekFrom},
+};
+use tempfile::NamedTempFile;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+/// Extractor that streams the request body into a temporary file.
+///
+/// This allows accepting uploads that are too large to be buffered in memory. The body is written
+/// to a [`NamedTempFile`] chunk by chunk as it's received, and the file is deleted when the
+/// [`NamedTempFile`] is dropped, unless it's [persisted].
+///
+/// The body may be at most `LIMIT` bytes (1 GiB unless specified) long. Requests with a larger
+/// body are rejected with `413 Payload Too Large`, and the partially written file is deleted.
+/// The [`DefaultBodyLimit`] isn't applied, since it's meant to limit memory use.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::extract::UploadToFile;
+///
+/// // accept uploads up to 10 GiB
+/// async fn upload(UploadToFile { file, len }: UploadToFile<{ 10 * 1024 * 1024 * 1024 }>) {
+///     // move the upload to its final destination
+///     file.persist(format!("/uploads/{len}.bin")).unwrap();
+/// }
+///
+/// let app = Router::new().route("/upload", post(upload));
+/// # let _: Router = app;
+/// ```
+///
+/// [persisted]: NamedTempFile::persist
+/// [`DefaultBodyLimit`]: axum::extract::DefaultBodyLimit
+#[cfg_attr(docsrs, doc(cfg(feature = "upload-to-file")))]
+#[derive(Debug)]
+pub struct UploadToFile<const LIMIT: u64 = 1_073_741_824> {
+    /// The temporary file containing the body, positioned at its start.
+    pub file: NamedTempFile,
+    /// The length of the body in bytes.
+    pub len: u64,
+}
+
+#[async_trait]
+impl<S, const LIMIT: u64> FromRequest<S> for UploadToFile<LIMIT>
+where
+    S: Send + Sync,
+{
+    type Rejection = UploadToFileRejection;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let content_length = req
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        if content_length.map_or(false, |len| len > LIMIT) {
+            return Err(UploadToFileRejection::TooLarge { limit: LIMIT });
+        }
+
+        let file = NamedTempFile::new().map_err(UploadToFileRejection::Io)?;
+        let mut writer = file
+            .as_file()
+            .try_clone()
+            .map(tokio::fs::File::from_std)
+            .map_err(UploadToFileRejection::Io)?;
+
+        let mut stream = req.into_body().into_data_stream();
+        let mut len = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(UploadToFileRejection::Body)?;
+
+            len += chunk.len() as u64;
+            if len > LIMIT {
+                return Err(UploadToFileRejection::TooLarge { limit: LIMIT });
+            }
+
+            writer
+                .write_all(&chunk)
+                .await
+                .map_err(UploadToFileRejection::Io)?;
+        }
+        writer.flush().await.map_err(UploadToFileRejection::Io)?;
+        // the writer shares its offset with `file`, so rewind it for reading
+        writer
+            .seek(SeekFrom::Start(0))
+            .await
+            .map_err(UploadToFileRejection::Io)?;
+
+        Ok(Self { file, len })
+    }
+}
+
+/// Rejection used for [`UploadToFile`].
+#[cfg_attr(docsrs, doc(cfg(feature = "upload-to-file")))]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum UploadToFileRejection {
+    /// Reading the request body failed.
+    Body(axum::Error),
+    /// Creating or writing the temporary file failed.
+    Io(io::Error),
+    /// The body was longer than the limit.
+    TooLarge {
+        /// The limit in bytes.
+        limit: u64,
+    },
+}
+
+impl IntoResponse for UploadToFileRejection {
+    fn into_response(self) -> Response {
+        let body = self.to_string();
+        let status = match &self {
+            Self::Body(_) => StatusCode::BAD_REQUEST,
+            Self::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::TooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+        };
+        axum_core::__log_rejection!(rejection_type = Self, body_text = body, status = status,);
+        (status, body).into_response()
+    }
+}
+
+impl fmt::Display for UploadToFileRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Body(err) => write!(f, "Failed to read the request body: {err}"),
+            Self::Io(err) => write!(f, "Failed to write the request body to a file: {err}"),
+            Self::TooLarge { limit } => write!(f, "Request body is larger than {limit} bytes"),
+        }
+    }
+}
+
+impl std::error::Error for UploadToFileRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Body(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::TooLarge { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use bytes::Bytes;
+    use std::{convert::Infallible, io::Read};
+
+    fn chunked_request(chunks: &[&'static str]) -> Request {
+        let chunks = chunks
+            .iter()
+            .map(|chunk| Ok::<_, Infallible>(Bytes::from_static(chunk.as_bytes())))
+            .collect::<Vec<_>>();
+        Request::new(Body::from_stream(futures_util::stream::iter(chunks)))
+    }
+
+    #[tokio::test]
+    async fn writes_chunks_to_file() {
+        let req = chunked_request(&["hello ", "multi-chunk ", "world"]);
+
+        let res = UploadToFile::<1024>::from_request(req, &()).await;
+        let UploadToFile { mut file, len } = res.unwrap();
+
+        assert_eq!(len, 23);
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "hello multi-chunk world");
+    }
+
+    #[tokio::test]
+    async fn rejects_bodies_over_the_limit() {
+        let req = chunked_request(&["hello ", "world"]);
+
+        let res = UploadToFile::<8>::from_request(req, &()).await;
+        let err = res.unwrap_err();
+
+        assert!(matches!(err, UploadToFileRejection::TooLarge { limit: 8 }));
+        assert_eq!(err.into_response().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
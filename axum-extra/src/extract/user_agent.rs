@@ -0,0 +1,187 @@
+use axum::{async_trait, extract::FromRequestParts};
+use http::{header::USER_AGENT, request::Parts};
+use std::convert::Infallible;
+
+/// Substrings that identify crawlers and other automated clients.
+const BOT_MARKERS: &[&str] = &[
+    "bot",
+    "crawler",
+    "spider",
+    "slurp",
+    "headless",
+    "curl/",
+    "wget/",
+    "python-requests",
+    "go-http-client",
+];
+
+/// Substrings that identify mobile devices, including tablets.
+const MOBILE_MARKERS: &[&str] = &["mobi", "android", "iphone", "ipad", "ipod", "windows phone"];
+
+/// Extractor that classifies the client based on the `User-Agent` header.
+///
+/// The classification is deliberately coarse and based on a small set of rules, so it's suitable
+/// for adapting responses, such as serving a lighter page to mobile devices or skipping
+/// analytics for crawlers, but not for anything security related since the header is controlled
+/// by the client.
+///
+/// User agents that look like neither a bot nor a mobile device are classified as
+/// [`DeviceClass::Desktop`]. If the header is missing or isn't valid UTF-8 the client is
+/// classified as [`DeviceClass::Unknown`]. This extractor never rejects the request.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::extract::UserAgentInfo;
+///
+/// async fn handler(user_agent: UserAgentInfo) {
+///     if user_agent.is_mobile() {
+///         // ...
+///     }
+/// }
+///
+/// let app = Router::new().route("/", get(handler));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UserAgentInfo {
+    class: DeviceClass,
+}
+
+/// The coarse classification of a client made by [`UserAgentInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DeviceClass {
+    /// A crawler or other automated client.
+    Bot,
+    /// A mobile phone or tablet.
+    Mobile,
+    /// Any other client.
+    Desktop,
+    /// The `User-Agent` header was missing or not valid UTF-8.
+    Unknown,
+}
+
+impl UserAgentInfo {
+    /// Classify a `User-Agent` header value.
+    pub fn from_user_agent(user_agent: &str) -> Self {
+        let user_agent = user_agent.to_ascii_lowercase();
+        let contains_any = |markers: &[&str]| markers.iter().any(|m| user_agent.contains(m));
+
+        let class = if contains_any(BOT_MARKERS) {
+            DeviceClass::Bot
+        } else if contains_any(MOBILE_MARKERS) {
+            DeviceClass::Mobile
+        } else {
+            DeviceClass::Desktop
+        };
+        Self { class }
+    }
+
+    /// Returns the classification of the client.
+    pub fn class(&self) -> DeviceClass {
+        self.class
+    }
+
+    /// Returns `true` if the client is a crawler or other automated client.
+    pub fn is_bot(&self) -> bool {
+        self.class == DeviceClass::Bot
+    }
+
+    /// Returns `true` if the client is a mobile phone or tablet.
+    pub fn is_mobile(&self) -> bool {
+        self.class == DeviceClass::Mobile
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for UserAgentInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let user_agent = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok());
+
+        Ok(match user_agent {
+            Some(user_agent) => Self::from_user_agent(user_agent),
+            None => Self {
+                class: DeviceClass::Unknown,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    #[tokio::test]
+    async fn bot() {
+        let app = Router::new().route(
+            "/",
+            get(|user_agent: UserAgentInfo| async move { format!("{:?}", user_agent.class()) }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header(
+                "user-agent",
+                "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+            )
+            .await;
+        assert_eq!(res.text().await, "Bot");
+    }
+
+    #[tokio::test]
+    async fn mobile() {
+        let app = Router::new().route(
+            "/",
+            get(|user_agent: UserAgentInfo| async move { format!("{:?}", user_agent.class()) }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header(
+                "user-agent",
+                "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) AppleWebKit/605.1.15 \
+                 (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1",
+            )
+            .await;
+        assert_eq!(res.text().await, "Mobile");
+
+        let res = client
+            .get("/")
+            .header(
+                "user-agent",
+                "Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+            )
+            .await;
+        assert_eq!(res.text().await, "Desktop");
+    }
+
+    #[tokio::test]
+    async fn missing_header() {
+        let app = Router::new().route(
+            "/",
+            get(|user_agent: UserAgentInfo| async move { format!("{:?}", user_agent.class()) }),
+        );
+
+        let client = TestClient::new(app);
+
+        // the test client doesn't send a `User-Agent` header by default
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "Unknown");
+    }
+}
@@ -0,0 +1,233 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, FromRequestParts, Request},
+    response::{IntoResponse, Response},
+};
+use http::{header, request::Parts, HeaderValue, StatusCode};
+use serde_json::{Map, Value};
+use std::{fmt, ops::Deref};
+use validator::{Validate, ValidationErrors};
+
+/// Extractor that validates the value extracted by another extractor.
+///
+/// `Validated` runs the wrapped extractor, such as [`Json`] or [`Form`], and then calls
+/// [`Validate::validate`] on the extracted value. If validation fails, the request is rejected
+/// with `422 Unprocessable Entity` and a JSON body mapping each invalid field to its error
+/// messages, like this:
+///
+/// ```json
+/// { "email": ["must be a valid email address"] }
+/// ```
+///
+/// If a validation rule doesn't have a message its code is used instead. Only errors for fields
+/// of the top-level struct are included.
+///
+/// The wrapped extractor must dereference to the value that's validated, which is the case for
+/// all the extractors in axum and axum-extra that wrap a deserialized value.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Json, Router};
+/// use axum_extra::extract::Validated;
+/// use serde::Deserialize;
+/// use validator::Validate;
+///
+/// #[derive(Deserialize, Validate)]
+/// struct CreateUser {
+///     #[validate(email(message = "must be a valid email address"))]
+///     email: String,
+///     #[validate(length(min = 8))]
+///     password: String,
+/// }
+///
+/// async fn create_user(Validated(Json(payload)): Validated<Json<CreateUser>>) {
+///     // `payload` has been validated
+/// }
+///
+/// let app = Router::new().route("/users", post(create_user));
+/// # let _: Router = app;
+/// ```
+///
+/// [`Json`]: axum::Json
+/// [`Form`]: axum::Form
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "validator")))]
+pub struct Validated<E>(pub E);
+
+#[async_trait]
+impl<E, S> FromRequest<S> for Validated<E>
+where
+    S: Send + Sync,
+    E: FromRequest<S> + Deref,
+    E::Target: Validate,
+{
+    type Rejection = ValidatedRejection<E::Rejection>;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let value = E::from_request(req, state)
+            .await
+            .map_err(ValidatedRejection::Inner)?;
+        validate(value)
+    }
+}
+
+#[async_trait]
+impl<E, S> FromRequestParts<S> for Validated<E>
+where
+    S: Send + Sync,
+    E: FromRequestParts<S> + Deref,
+    E::Target: Validate,
+{
+    type Rejection = ValidatedRejection<E::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let value = E::from_request_parts(parts, state)
+            .await
+            .map_err(ValidatedRejection::Inner)?;
+        validate(value)
+    }
+}
+
+axum_core::__impl_deref!(Validated);
+
+fn validate<E, R>(value: E) -> Result<Validated<E>, ValidatedRejection<R>>
+where
+    E: Deref,
+    E::Target: Validate,
+{
+    value.validate().map_err(ValidatedRejection::Invalid)?;
+    Ok(Validated(value))
+}
+
+/// Rejection used for [`Validated`].
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "validator")))]
+pub enum ValidatedRejection<R> {
+    /// The inner extractor failed.
+    Inner(R),
+    /// The extracted value failed validation.
+    Invalid(ValidationErrors),
+}
+
+impl<R> IntoResponse for ValidatedRejection<R>
+where
+    R: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        match self {
+            Self::Inner(inner) => inner.into_response(),
+            Self::Invalid(errors) => {
+                let mut fields = Map::new();
+                for (field, errors) in errors.field_errors() {
+                    let messages = errors
+                        .iter()
+                        .map(|error| match &error.message {
+                            Some(message) => Value::from(message.to_string()),
+                            None => Value::from(error.code.to_string()),
+                        })
+                        .collect();
+                    fields.insert(field.into(), Value::Array(messages));
+                }
+
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    [(
+                        header::CONTENT_TYPE,
+                        HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+                    )],
+                    Value::Object(fields).to_string(),
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+impl<R> fmt::Display for ValidatedRejection<R>
+where
+    R: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inner(inner) => inner.fmt(f),
+            Self::Invalid(errors) => write!(f, "Validation failed: {errors}"),
+        }
+    }
+}
+
+impl<R> std::error::Error for ValidatedRejection<R>
+where
+    R: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Inner(inner) => Some(inner),
+            Self::Invalid(errors) => Some(errors),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Json, Router};
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Validate)]
+    struct Input {
+        #[validate(length(min = 3, message = "must be at least 3 characters"))]
+        name: String,
+        #[validate(range(min = 18))]
+        age: u32,
+    }
+
+    #[tokio::test]
+    async fn valid() {
+        let app = Router::new().route(
+            "/",
+            post(|Validated(Json(input)): Validated<Json<Input>>| async move { input.name }),
+        );
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .json(&serde_json::json!({ "name": "alice", "age": 30 }))
+            .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "alice");
+    }
+
+    #[tokio::test]
+    async fn invalid() {
+        let app = Router::new().route("/", post(|_: Validated<Json<Input>>| async {}));
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .json(&serde_json::json!({ "name": "al", "age": 12 }))
+            .await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(res.headers()["content-type"], "application/json");
+        assert_eq!(
+            res.json::<Value>().await,
+            serde_json::json!({
+                "name": ["must be at least 3 characters"],
+                "age": ["range"],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn inner_rejection() {
+        let app = Router::new().route("/", post(|_: Validated<Json<Input>>| async {}));
+
+        let client = TestClient::new(app);
+        let res = client.post("/").body("not json").await;
+
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+}
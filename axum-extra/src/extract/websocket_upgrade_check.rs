@@ -0,0 +1,255 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    response::{IntoResponse, Response},
+};
+use http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode};
+use std::fmt;
+
+/// Extractor that checks that a request is a valid WebSocket handshake, without performing the
+/// upgrade.
+///
+/// The request must have the following headers, as required by [RFC 6455]:
+///
+/// - `Upgrade: websocket`, otherwise the request is rejected with `426 Upgrade Required`.
+/// - `Connection` containing the `upgrade` token, otherwise the request is rejected with
+///   `400 Bad Request`.
+/// - `Sec-WebSocket-Version: 13`, otherwise the request is rejected with `426 Upgrade Required`
+///   and a `Sec-WebSocket-Version` header listing the supported version.
+/// - `Sec-WebSocket-Key` with a base64 encoded 16 byte value, otherwise the request is rejected
+///   with `400 Bad Request`.
+///
+/// This allows rejecting requests that aren't WebSocket handshakes early, for example in
+/// middleware created with [`axum::middleware::from_extractor`] that guards WebSocket routes.
+/// Use axum's `WebSocketUpgrade` extractor to actually accept the connection.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{middleware::from_extractor, routing::get, Router};
+/// use axum_extra::extract::WebSocketUpgradeCheck;
+///
+/// async fn ws_handler() {
+///     // accept the connection with `WebSocketUpgrade`
+/// }
+///
+/// let app = Router::new()
+///     .route("/ws", get(ws_handler))
+///     .route_layer(from_extractor::<WebSocketUpgradeCheck>());
+/// # let _: Router = app;
+/// ```
+///
+/// [RFC 6455]: https://datatracker.ietf.org/doc/html/rfc6455#section-4.2.1
+#[derive(Debug, Clone)]
+pub struct WebSocketUpgradeCheck {
+    key: HeaderValue,
+}
+
+impl WebSocketUpgradeCheck {
+    /// Check that `headers` contain a valid WebSocket handshake.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, WebSocketUpgradeCheckRejection> {
+        if !header_contains(headers, header::UPGRADE, "websocket") {
+            return Err(WebSocketUpgradeCheckRejection::InvalidUpgradeHeader);
+        }
+
+        if !header_contains(headers, header::CONNECTION, "upgrade") {
+            return Err(WebSocketUpgradeCheckRejection::InvalidConnectionHeader);
+        }
+
+        if headers
+            .get(header::SEC_WEBSOCKET_VERSION)
+            .map_or(true, |version| version != "13")
+        {
+            return Err(WebSocketUpgradeCheckRejection::UnsupportedVersion);
+        }
+
+        match headers.get(header::SEC_WEBSOCKET_KEY) {
+            Some(key) if is_valid_key(key.as_bytes()) => Ok(Self { key: key.clone() }),
+            _ => Err(WebSocketUpgradeCheckRejection::InvalidKey),
+        }
+    }
+
+    /// Returns the value of the `Sec-WebSocket-Key` header.
+    pub fn key(&self) -> &HeaderValue {
+        &self.key
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for WebSocketUpgradeCheck
+where
+    S: Send + Sync,
+{
+    type Rejection = WebSocketUpgradeCheckRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::from_headers(&parts.headers)
+    }
+}
+
+/// Returns `true` if any of the comma separated tokens in the headers named `name` is `token`,
+/// ignoring case.
+fn header_contains(headers: &HeaderMap, name: header::HeaderName, token: &str) -> bool {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|value| value.trim().eq_ignore_ascii_case(token))
+}
+
+/// Returns `true` if `key` is the base64 encoding of 16 bytes.
+fn is_valid_key(key: &[u8]) -> bool {
+    // 16 bytes encode to 22 characters followed by two `=` of padding
+    key.len() == 24
+        && key.ends_with(b"==")
+        && key[..22]
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || *b == b'+' || *b == b'/')
+}
+
+/// Rejection used for [`WebSocketUpgradeCheck`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WebSocketUpgradeCheckRejection {
+    /// The `Upgrade` header was missing or didn't contain `websocket`.
+    InvalidUpgradeHeader,
+    /// The `Connection` header was missing or didn't contain `upgrade`.
+    InvalidConnectionHeader,
+    /// The `Sec-WebSocket-Version` header was missing or wasn't `13`.
+    UnsupportedVersion,
+    /// The `Sec-WebSocket-Key` header was missing or malformed.
+    InvalidKey,
+}
+
+impl IntoResponse for WebSocketUpgradeCheckRejection {
+    fn into_response(self) -> Response {
+        let body = self.to_string();
+        let status = match self {
+            Self::InvalidUpgradeHeader | Self::UnsupportedVersion => StatusCode::UPGRADE_REQUIRED,
+            Self::InvalidConnectionHeader | Self::InvalidKey => StatusCode::BAD_REQUEST,
+        };
+        axum_core::__log_rejection!(rejection_type = Self, body_text = body, status = status,);
+
+        let mut res = (status, body).into_response();
+        match self {
+            Self::InvalidUpgradeHeader => {
+                let headers = res.headers_mut();
+                headers.insert(header::UPGRADE, HeaderValue::from_static("websocket"));
+                headers.insert(header::CONNECTION, HeaderValue::from_static("upgrade"));
+            }
+            Self::UnsupportedVersion => {
+                res.headers_mut().insert(
+                    header::SEC_WEBSOCKET_VERSION,
+                    HeaderValue::from_static("13"),
+                );
+            }
+            Self::InvalidConnectionHeader | Self::InvalidKey => {}
+        }
+        res
+    }
+}
+
+impl fmt::Display for WebSocketUpgradeCheckRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUpgradeHeader => write!(f, "`Upgrade` header did not include `websocket`"),
+            Self::InvalidConnectionHeader => {
+                write!(f, "`Connection` header did not include `upgrade`")
+            }
+            Self::UnsupportedVersion => {
+                write!(f, "`Sec-WebSocket-Version` header did not equal 13")
+            }
+            Self::InvalidKey => write!(f, "`Sec-WebSocket-Key` header missing or invalid"),
+        }
+    }
+}
+
+impl std::error::Error for WebSocketUpgradeCheckRejection {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    const HANDSHAKE: [(&str, &str); 4] = [
+        ("upgrade", "websocket"),
+        ("connection", "keep-alive, Upgrade"),
+        ("sec-websocket-version", "13"),
+        ("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ=="),
+    ];
+
+    /// Send the handshake with the header `name` set to `value`, or removed if `value` is `None`.
+    async fn send(name: &str, value: Option<&str>) -> Response {
+        let app =
+            Router::new().route(
+                "/",
+                get(|check: WebSocketUpgradeCheck| async move {
+                    check.key().to_str().unwrap().to_owned()
+                }),
+            );
+
+        let mut req = Request::get("/");
+        for (header_name, header_value) in HANDSHAKE {
+            if header_name != name {
+                req = req.header(header_name, header_value);
+            } else if let Some(value) = value {
+                req = req.header(header_name, value);
+            }
+        }
+
+        app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    async fn text(res: Response) -> String {
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn valid_handshake() {
+        let res = send("", None).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(text(res).await, "dGhlIHNhbXBsZSBub25jZQ==");
+    }
+
+    #[tokio::test]
+    async fn invalid_upgrade() {
+        for value in [None, Some("h2c")] {
+            let res = send("upgrade", value).await;
+            assert_eq!(res.status(), StatusCode::UPGRADE_REQUIRED);
+            assert_eq!(res.headers()[header::UPGRADE], "websocket");
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_connection() {
+        for value in [None, Some("keep-alive")] {
+            let res = send("connection", value).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn unsupported_version() {
+        for value in [None, Some("8")] {
+            let res = send("sec-websocket-version", value).await;
+            assert_eq!(res.status(), StatusCode::UPGRADE_REQUIRED);
+            assert_eq!(res.headers()[header::SEC_WEBSOCKET_VERSION], "13");
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_key() {
+        for value in [None, Some("too short=="), Some("dGhlIHNhbXBsZSBub25jZQ")] {
+            let res = send("sec-websocket-key", value).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(
+                text(res).await,
+                "`Sec-WebSocket-Key` header missing or invalid"
+            );
+        }
+    }
+}
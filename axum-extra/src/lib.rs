@@ -10,20 +10,52 @@
 //! Name | Description | Default?
 //! ---|---|---
 //! `async-read-body` | Enables the `AsyncReadBody` body | No
+//! `basic-auth` | Enables the `BasicAuth` extractor | No
+//! `byte-accounting` | Enables the `ByteAccounting` middleware | No
+//! `cbor` | Enables the `Cbor` extractor and response | No
+//! `client-ip` | Enables the `ClientIp` extractor | No
+//! `coalesce` | Enables the `Coalesce` middleware | No
+//! `compression-br` | Enables `br` in the `CompressResponse` middleware | No
+//! `compression-deflate` | Enables `deflate` in the `CompressResponse` middleware | No
+//! `compression-gzip` | Enables `gzip` in the `CompressResponse` middleware | No
 //! `cookie` | Enables the `CookieJar` extractor | No
 //! `cookie-private` | Enables the `PrivateCookieJar` extractor | No
 //! `cookie-signed` | Enables the `SignedCookieJar` extractor | No
 //! `cookie-key-expansion` | Enables the `Key::derive_from` method | No
+//! `deadline` | Enables the `DeadlineLayer` middleware and `Deadline` extractor | No
 //! `erased-json` | Enables the `ErasedJson` response | No
-//! `form` | Enables the `Form` extractor | No
+//! `feature-flags` | Enables the `FeatureFlags` extractor | No
+//! `form` | Enables the `Form` and `FormMap` extractors | No
+//! `global-concurrency-limit` | Enables the `GlobalConcurrencyLimit` middleware | No
+//! `if-range` | Enables the `IfRangeHeader` extractor | No
+//! `json-array-stream` | Enables the `JsonArrayStream` extractor | No
 //! `json-deserializer` | Enables the `JsonDeserializer` extractor | No
-//! `json-lines` | Enables the `JsonLines` extractor and response | No
+//! `jsonwebtoken` | Enables the `Jwt` extractor | No
+//! `json-lines` | Enables the `JsonLines` and `NdJson` extractors and the `JsonLines` response | No
+//! `keyed-mutex` | Enables the `KeyedMutex` middleware | No
+//! `mirror` | Enables the `Mirror` middleware | No
+//! `msgpack` | Enables MessagePack support in the `Negotiated` extractor | No
 //! `multipart` | Enables the `Multipart` extractor | No
+//! `negotiated` | Enables the `Negotiated` extractor | No
+//! `payload` | Enables the `Payload` extractor | No
 //! `protobuf` | Enables the `Protobuf` extractor and response | No
-//! `query` | Enables the `Query` extractor | No
-//! `tracing` | Log rejections from built-in extractors | Yes
+//! `query` | Enables the `Query` and `Pagination` extractors | No
+//! `rate-limit` | Enables the `RateLimit` middleware | No
+//! `response-body-timeout` | Enables the `ResponseBodyTimeout` middleware | No
+//! `response-pacer` | Enables the `ResponsePacer` middleware | No
+//! `retry` | Enables the `Retry` middleware | No
+//! `signed-query` | Enables the `SignedQuery` extractor | No
+//! `slow-request-log` | Enables the `SlowRequestLog` middleware | No
+//! `sqlx` | Enables the `Tx` extractor | No
+//! `tee-body` | Enables the `TeeBody` body and `Tee` extractor | No
+//! `trace-context` | Enables the `TraceContext` extractor | No
+//! `tracing` | Log rejections from built-in extractors and enables the `Instrumented` extractor | Yes
 //! `typed-routing` | Enables the `TypedPath` routing utilities | No
 //! `typed-header` | Enables the `TypedHeader` extractor and response  | No
+//! `upload-to-file` | Enables the `UploadToFile` extractor | No
+//! `validator` | Enables the `Validated` extractor | No
+//! `verify-digest` | Enables the `VerifyDigest` middleware | No
+//! `xml` | Enables the `Xml` extractor | No
 //!
 //! [`axum`]: https://crates.io/crates/axum
 
@@ -97,6 +129,12 @@ pub use typed_header::TypedHeader;
 #[cfg(feature = "protobuf")]
 pub mod protobuf;
 
+#[cfg(feature = "cbor")]
+pub mod cbor;
+
+#[cfg(feature = "xml")]
+pub mod xml;
+
 /// _not_ public API
 #[cfg(feature = "typed-routing")]
 #[doc(hidden)]
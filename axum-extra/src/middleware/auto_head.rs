@@ -0,0 +1,158 @@
+use axum::{body::Body, extract::Request, response::Response};
+use futures_util::future::BoxFuture;
+use http::{header, HeaderValue, Method};
+use http_body::Body as _;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`AutoHead`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{extract::Request, response::Response};
+/// use axum_extra::middleware::AutoHeadLayer;
+/// use std::convert::Infallible;
+/// use tower::{service_fn, Layer};
+///
+/// let svc = service_fn(|_req: Request| async move {
+///     // only handles `GET`, `HEAD` requests are answered from the `GET` response
+///     Ok::<_, Infallible>(Response::new("Hello, World!".into()))
+/// });
+///
+/// let svc = AutoHeadLayer::new().layer(svc);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AutoHeadLayer {
+    _priv: (),
+}
+
+impl AutoHeadLayer {
+    /// Create a new `AutoHeadLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for AutoHeadLayer {
+    type Service = AutoHead<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AutoHead::new(inner)
+    }
+}
+
+/// Middleware that answers `HEAD` requests by calling the inner service with `GET`.
+///
+/// The method of `HEAD` requests is changed to `GET` before calling the inner service, and the
+/// body of the response is dropped. The headers are kept as they are, so the response looks
+/// exactly like the response to a `GET` request without the body. If the response doesn't have a
+/// `Content-Length` header but the size of the body is known, the header is added with the size
+/// the body would have had.
+///
+/// This allows services that only handle `GET` to support `HEAD` as well. Note that axum's
+/// [`Router`] already does this for routes added with [`get`], so this is mostly useful for
+/// other services.
+///
+/// [`Router`]: axum::Router
+/// [`get`]: axum::routing::get
+#[derive(Debug, Clone, Copy)]
+pub struct AutoHead<S> {
+    inner: S,
+}
+
+impl<S> AutoHead<S> {
+    /// Create a new `AutoHead` middleware.
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S> Service<Request> for AutoHead<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request) -> Self::Future {
+        let is_head = req.method() == Method::HEAD;
+        if is_head {
+            *req.method_mut() = Method::GET;
+        }
+
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let res = future.await?;
+            if !is_head {
+                return Ok(res);
+            }
+
+            let (mut parts, body) = res.into_parts();
+            if !parts.headers.contains_key(header::CONTENT_LENGTH) {
+                if let Some(len) = body.size_hint().exact() {
+                    parts
+                        .headers
+                        .insert(header::CONTENT_LENGTH, HeaderValue::from(len));
+                }
+            }
+            Ok(Response::from_parts(parts, Body::empty()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::StatusCode;
+    use http_body_util::BodyExt;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+
+    async fn handler(req: Request) -> Result<Response, Infallible> {
+        let res = if req.method() == Method::GET {
+            Response::builder()
+                .header("x-custom", "foo")
+                .body(Body::from("Hello, World!"))
+                .unwrap()
+        } else {
+            Response::builder()
+                .status(StatusCode::METHOD_NOT_ALLOWED)
+                .body(Body::empty())
+                .unwrap()
+        };
+        Ok(res)
+    }
+
+    #[tokio::test]
+    async fn head_uses_get_response() {
+        let req = Request::head("/").body(Body::empty()).unwrap();
+        let svc = AutoHeadLayer::new().layer(service_fn(handler));
+        let res = svc.oneshot(req).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["x-custom"], "foo");
+        assert_eq!(res.headers()[header::CONTENT_LENGTH], "13");
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_is_untouched() {
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        let svc = AutoHeadLayer::new().layer(service_fn(handler));
+        let res = svc.oneshot(req).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "Hello, World!");
+    }
+}
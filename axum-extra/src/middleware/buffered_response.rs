@@ -0,0 +1,81 @@
+use axum::{body::Body, response::Response};
+use bytes::{Bytes, BytesMut};
+use futures_util::stream::{self, StreamExt};
+use http::{HeaderMap, StatusCode, Version};
+use http_body::Body as _;
+use http_body_util::BodyExt;
+
+/// A response with its body buffered, so it can be sent several times.
+///
+/// Response extensions aren't kept, since they can't be cloned.
+pub(super) struct BufferedResponse {
+    status: StatusCode,
+    version: Version,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl BufferedResponse {
+    pub(super) async fn from_response(res: Response) -> Result<Self, axum::Error> {
+        let (parts, body) = res.into_parts();
+        let body = body.collect().await?.to_bytes();
+        Ok(Self {
+            status: parts.status,
+            version: parts.version,
+            headers: parts.headers,
+            body,
+        })
+    }
+
+    /// Buffers the response if its body is at most `limit` bytes long.
+    ///
+    /// Larger responses are returned unbuffered, with the part of the body that was already read
+    /// put back in front of the rest.
+    #[cfg_attr(not(feature = "coalesce"), allow(dead_code))]
+    pub(super) async fn from_response_limited(
+        res: Response,
+        limit: usize,
+    ) -> Result<Self, BufferError> {
+        let (parts, mut body) = res.into_parts();
+        if body.size_hint().lower() > limit as u64 {
+            return Err(BufferError::TooLarge(Response::from_parts(parts, body)));
+        }
+
+        let mut buf = BytesMut::new();
+        while let Some(frame) = body.frame().await {
+            let Ok(data) = frame.map_err(BufferError::Body)?.into_data() else {
+                continue;
+            };
+
+            if buf.len() + data.len() > limit {
+                let read = stream::iter([Ok::<_, axum::Error>(buf.freeze()), Ok(data)]);
+                let body = Body::from_stream(read.chain(body.into_data_stream()));
+                return Err(BufferError::TooLarge(Response::from_parts(parts, body)));
+            }
+            buf.extend_from_slice(&data);
+        }
+
+        Ok(Self {
+            status: parts.status,
+            version: parts.version,
+            headers: parts.headers,
+            body: buf.freeze(),
+        })
+    }
+
+    pub(super) fn to_response(&self) -> Response {
+        let mut res = Response::new(Body::from(self.body.clone()));
+        *res.status_mut() = self.status;
+        *res.version_mut() = self.version;
+        *res.headers_mut() = self.headers.clone();
+        res
+    }
+}
+
+#[cfg_attr(not(feature = "coalesce"), allow(dead_code))]
+pub(super) enum BufferError {
+    /// The body was larger than the limit.
+    TooLarge(Response),
+    /// Reading the body failed.
+    Body(axum::Error),
+}
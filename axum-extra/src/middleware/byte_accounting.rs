@@ -0,0 +1,296 @@
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    response::Response,
+};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use http_body::{Body as HttpBody, Frame, SizeHint};
+use std::{
+    fmt,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{ready, Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+type Callback = Arc<dyn Fn(&ByteCounts) + Send + Sync>;
+
+/// The number of body bytes transferred for a single request, as reported by [`ByteAccounting`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ByteCounts {
+    /// The route that matched the request, if any.
+    pub route: Option<MatchedPath>,
+    /// The number of bytes read from the request body.
+    pub request_bytes: u64,
+    /// The number of bytes sent in the response body.
+    pub response_bytes: u64,
+}
+
+/// Layer that applies the [`ByteAccounting`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::post, Router};
+/// use axum_extra::middleware::{ByteAccountingLayer, ByteCounts};
+///
+/// let app = Router::new()
+///     .route("/upload", post(|body: String| async move { /* ... */ }))
+///     .layer(ByteAccountingLayer::new(|counts: &ByteCounts| {
+///         let route = counts.route.as_ref().map_or("unknown", |route| route.as_str());
+///         println!(
+///             "{route}: received {} bytes, sent {} bytes",
+///             counts.request_bytes, counts.response_bytes,
+///         );
+///     }));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone)]
+pub struct ByteAccountingLayer {
+    callback: Callback,
+}
+
+impl ByteAccountingLayer {
+    /// Create a new `ByteAccountingLayer` that reports the byte counts of each request to
+    /// `callback`.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&ByteCounts) + Send + Sync + 'static,
+    {
+        Self {
+            callback: Arc::new(callback),
+        }
+    }
+}
+
+impl fmt::Debug for ByteAccountingLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ByteAccountingLayer")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for ByteAccountingLayer {
+    type Service = ByteAccounting<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ByteAccounting {
+            inner,
+            callback: Arc::clone(&self.callback),
+        }
+    }
+}
+
+/// Middleware that counts the request and response body bytes of each request.
+///
+/// Both bodies are wrapped so the data frames passing through them are counted. Once the
+/// response body has been sent, or is dropped before that, the totals are reported to a callback
+/// together with the [`MatchedPath`] of the request. This can be used to enforce quotas or to
+/// bill per route.
+///
+/// The request body count only includes the bytes that were actually read, so it's lower than
+/// the size of the request body if the handler didn't read all of it. To see the matched route
+/// the middleware must be added with [`Router::layer`] or [`Router::route_layer`].
+///
+/// Created with [`ByteAccountingLayer`].
+///
+/// [`Router::layer`]: axum::Router::layer
+/// [`Router::route_layer`]: axum::Router::route_layer
+#[derive(Clone)]
+pub struct ByteAccounting<S> {
+    inner: S,
+    callback: Callback,
+}
+
+impl<S> fmt::Debug for ByteAccounting<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ByteAccounting")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Service<Request> for ByteAccounting<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let route = req.extensions().get::<MatchedPath>().cloned();
+        let request_bytes = Arc::new(AtomicU64::new(0));
+
+        let counted = Arc::clone(&request_bytes);
+        let req = req.map(|body| {
+            Body::new(CountingRequestBody {
+                inner: body,
+                counted,
+            })
+        });
+
+        let future = self.inner.call(req);
+        let callback = Arc::clone(&self.callback);
+
+        Box::pin(async move {
+            let res = future.await?;
+            Ok(res.map(|body| {
+                Body::new(CountingResponseBody {
+                    inner: body,
+                    response_bytes: 0,
+                    report: Some(Report {
+                        route,
+                        request_bytes,
+                        callback,
+                    }),
+                })
+            }))
+        })
+    }
+}
+
+struct CountingRequestBody {
+    inner: Body,
+    counted: Arc<AtomicU64>,
+}
+
+impl HttpBody for CountingRequestBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let frame = ready!(Pin::new(&mut this.inner).poll_frame(cx));
+        if let Some(Ok(frame)) = &frame {
+            if let Some(data) = frame.data_ref() {
+                this.counted.fetch_add(data.len() as u64, Ordering::Relaxed);
+            }
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+struct Report {
+    route: Option<MatchedPath>,
+    request_bytes: Arc<AtomicU64>,
+    callback: Callback,
+}
+
+struct CountingResponseBody {
+    inner: Body,
+    response_bytes: u64,
+    // taken once the counts have been reported
+    report: Option<Report>,
+}
+
+impl CountingResponseBody {
+    fn report(&mut self) {
+        if let Some(report) = self.report.take() {
+            (report.callback)(&ByteCounts {
+                route: report.route,
+                request_bytes: report.request_bytes.load(Ordering::Relaxed),
+                response_bytes: self.response_bytes,
+            });
+        }
+    }
+}
+
+impl HttpBody for CountingResponseBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let frame = ready!(Pin::new(&mut this.inner).poll_frame(cx));
+        match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    this.response_bytes += data.len() as u64;
+                }
+            }
+            Some(Err(_)) | None => this.report(),
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for CountingResponseBody {
+    fn drop(&mut self) {
+        // the body might not be polled to the end, for example if the client disconnected or the
+        // server stopped polling because `is_end_stream` returned `true`
+        self.report();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Router};
+    use http_body_util::BodyExt;
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn reports_request_and_response_bytes() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+
+        let app = Router::new()
+            .route(
+                "/users/:id",
+                post(|body: String| async move { body.repeat(2) }),
+            )
+            .layer(ByteAccountingLayer::new({
+                let reported = Arc::clone(&reported);
+                move |counts: &ByteCounts| reported.lock().unwrap().push(counts.clone())
+            }));
+
+        let req = Request::post("/users/1").body(Body::from("hello")).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "hellohello");
+
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), 1);
+        let counts = &reported[0];
+        assert_eq!(counts.route.as_ref().unwrap().as_str(), "/users/:id");
+        assert_eq!(counts.request_bytes, 5);
+        assert_eq!(counts.response_bytes, 10);
+    }
+}
@@ -0,0 +1,424 @@
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+use http::StatusCode;
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`CircuitBreaker`] middleware.
+///
+/// All services created by the same layer, and clones of them, share one breaker.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::CircuitBreakerLayer;
+/// use std::time::Duration;
+///
+/// let layer = CircuitBreakerLayer::new()
+///     // open the circuit if at least 25% of the requests in the last 30 seconds failed...
+///     .failure_rate_threshold(0.25)
+///     .window(Duration::from_secs(30))
+///     // ...and at least 20 requests were made in that time
+///     .minimum_requests(20)
+///     // fail fast for 10 seconds before trying again
+///     .cooldown(Duration::from_secs(10));
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     .layer(layer);
+/// # let _: Router = app;
+/// ```
+#[derive(Clone)]
+pub struct CircuitBreakerLayer {
+    config: Config,
+    state: Arc<Mutex<State>>,
+}
+
+impl CircuitBreakerLayer {
+    /// Create a new `CircuitBreakerLayer` with the default configuration.
+    ///
+    /// By default the circuit opens when at least half of the requests in the last 10 seconds
+    /// failed, provided at least 10 requests were made, and stays open for 5 seconds.
+    pub fn new() -> Self {
+        Self {
+            config: Config::default(),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// Set the failure rate, between `0.0` and `1.0`, at or above which the circuit opens.
+    pub fn failure_rate_threshold(mut self, threshold: f64) -> Self {
+        self.config.failure_rate_threshold = threshold;
+        self
+    }
+
+    /// Set the minimum number of requests in the window before the circuit can open.
+    pub fn minimum_requests(mut self, minimum_requests: usize) -> Self {
+        self.config.minimum_requests = minimum_requests;
+        self
+    }
+
+    /// Set the duration of the rolling window the failure rate is computed over.
+    pub fn window(mut self, window: Duration) -> Self {
+        self.config.window = window;
+        self
+    }
+
+    /// Set how long the circuit stays open before a trial request is let through.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.config.cooldown = cooldown;
+        self
+    }
+}
+
+impl Default for CircuitBreakerLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for CircuitBreakerLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreakerLayer")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for CircuitBreakerLayer {
+    type Service = CircuitBreaker<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CircuitBreaker {
+            inner,
+            config: self.config,
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+/// Middleware that stops calling the inner service while it's failing.
+///
+/// Responses with a `5xx` status count as failures. While the circuit is closed the outcome of
+/// every request is recorded, and once the failure rate over the rolling window reaches the
+/// threshold the circuit opens. While open, requests are rejected with
+/// `503 Service Unavailable` without calling the inner service.
+///
+/// After the cooldown the circuit is half-open and a single trial request is let through. If it
+/// succeeds the circuit closes again, otherwise it opens for another cooldown. Other requests
+/// made while the trial request is in flight are rejected. If the trial request is cancelled before
+/// the inner service responds, the next request becomes the trial request.
+///
+/// Created with [`CircuitBreakerLayer`].
+pub struct CircuitBreaker<S> {
+    inner: S,
+    config: Config,
+    state: Arc<Mutex<State>>,
+}
+
+impl<S> CircuitBreaker<S> {
+    /// Create a new `CircuitBreaker` middleware with the default configuration.
+    ///
+    /// Use [`CircuitBreakerLayer`] to configure the breaker or share it between several services.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            config: Config::default(),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+}
+
+impl<S> Clone for CircuitBreaker<S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            config: self.config,
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<S> fmt::Debug for CircuitBreaker<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("inner", &self.inner)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Service<Request> for CircuitBreaker<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        let Some(trial) = self.state.lock().unwrap().try_acquire() else {
+            let res = (StatusCode::SERVICE_UNAVAILABLE, "Circuit breaker is open");
+            return Box::pin(async move { Ok(res.into_response()) });
+        };
+
+        let config = self.config;
+        let state = Arc::clone(&self.state);
+        let mut guard = TrialGuard {
+            state: Arc::clone(&self.state),
+            armed: trial,
+        };
+        Box::pin(async move {
+            let res = inner.call(req).await;
+
+            let failed = match &res {
+                Ok(res) => res.status().is_server_error(),
+                Err(_) => true,
+            };
+            state.lock().unwrap().record(&config, trial, failed);
+            guard.armed = false;
+
+            res
+        })
+    }
+}
+
+/// Reopens a half-open circuit if the trial request is dropped before its outcome is recorded, so
+/// that the next request becomes the trial request instead of the circuit staying half-open.
+struct TrialGuard {
+    state: Arc<Mutex<State>>,
+    armed: bool,
+}
+
+impl Drop for TrialGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        if let Ok(mut state) = self.state.lock() {
+            if matches!(state.circuit, Circuit::HalfOpen) {
+                state.circuit = Circuit::Open {
+                    until: Instant::now(),
+                };
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Config {
+    failure_rate_threshold: f64,
+    minimum_requests: usize,
+    window: Duration,
+    cooldown: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            minimum_requests: 10,
+            window: Duration::from_secs(10),
+            cooldown: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    circuit: Circuit,
+    // outcomes of the requests made while the circuit was closed, `true` meaning failed
+    outcomes: VecDeque<(Instant, bool)>,
+}
+
+#[derive(Default)]
+enum Circuit {
+    #[default]
+    Closed,
+    Open {
+        until: Instant,
+    },
+    HalfOpen,
+}
+
+impl State {
+    /// Returns `None` if the request must be rejected and `Some(true)` if it's the trial request
+    /// of a half-open circuit.
+    fn try_acquire(&mut self) -> Option<bool> {
+        match self.circuit {
+            Circuit::Closed => Some(false),
+            Circuit::Open { until } if Instant::now() >= until => {
+                self.circuit = Circuit::HalfOpen;
+                Some(true)
+            }
+            Circuit::Open { .. } | Circuit::HalfOpen => None,
+        }
+    }
+
+    fn record(&mut self, config: &Config, trial: bool, failed: bool) {
+        let now = Instant::now();
+
+        if trial {
+            if failed {
+                self.open(config, now);
+            } else {
+                self.circuit = Circuit::Closed;
+            }
+            return;
+        }
+
+        // the circuit was opened by another request while this one was in flight
+        if !matches!(self.circuit, Circuit::Closed) {
+            return;
+        }
+
+        self.outcomes.push_back((now, failed));
+        while let Some((at, _)) = self.outcomes.front() {
+            if now.duration_since(*at) <= config.window {
+                break;
+            }
+            self.outcomes.pop_front();
+        }
+
+        let total = self.outcomes.len();
+        if total == 0 || total < config.minimum_requests {
+            return;
+        }
+        let failures = self.outcomes.iter().filter(|(_, failed)| *failed).count();
+        if failures as f64 / total as f64 >= config.failure_rate_threshold {
+            self.open(config, now);
+        }
+    }
+
+    fn open(&mut self, config: &Config, now: Instant) {
+        self.circuit = Circuit::Open {
+            until: now + config.cooldown,
+        };
+        self.outcomes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicBool, AtomicU16, AtomicUsize, Ordering},
+    };
+    use tower::{service_fn, ServiceExt};
+
+    #[tokio::test]
+    async fn opens_after_failures_and_recovers() {
+        let status = Arc::new(AtomicU16::new(500));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = service_fn({
+            let status = Arc::clone(&status);
+            let calls = Arc::clone(&calls);
+            move |_: Request| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                let status = StatusCode::from_u16(status.load(Ordering::SeqCst)).unwrap();
+                async move { Ok::<_, Infallible>(status.into_response()) }
+            }
+        });
+        let svc = CircuitBreakerLayer::new()
+            .minimum_requests(3)
+            .cooldown(Duration::from_millis(100))
+            .layer(svc);
+
+        let call = || svc.clone().oneshot(Request::new(Body::empty()));
+
+        // trip the breaker
+        for _ in 0..3 {
+            let res = call().await.unwrap();
+            assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // fail fast during the cooldown, without calling the inner service
+        let res = call().await.unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // a failed trial request opens the circuit again
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let res = call().await.unwrap();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let res = call().await.unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        // a successful trial request closes it
+        status.store(200, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        for _ in 0..3 {
+            let res = call().await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 7);
+    }
+
+    #[tokio::test]
+    async fn dropped_trial_request() {
+        let hang = Arc::new(AtomicBool::new(false));
+        let svc = service_fn({
+            let hang = Arc::clone(&hang);
+            move |_: Request| {
+                let hang = hang.load(Ordering::SeqCst);
+                async move {
+                    if hang {
+                        std::future::pending::<()>().await;
+                    }
+                    Ok::<_, Infallible>(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+                }
+            }
+        });
+        let svc = CircuitBreakerLayer::new()
+            .minimum_requests(1)
+            .cooldown(Duration::from_millis(100))
+            .layer(svc);
+
+        let call = || svc.clone().oneshot(Request::new(Body::empty()));
+
+        let res = call().await.unwrap();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        // the trial request is cancelled before the inner service responds
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        hang.store(true, Ordering::SeqCst);
+        let trial = tokio::time::timeout(Duration::from_millis(10), call()).await;
+        assert!(trial.is_err());
+
+        // so the next request is let through as the trial request
+        hang.store(false, Ordering::SeqCst);
+        let res = call().await.unwrap();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let res = call().await.unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
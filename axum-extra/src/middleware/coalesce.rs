@@ -0,0 +1,367 @@
+use super::buffered_response::{BufferError, BufferedResponse};
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use http::{Method, StatusCode};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`Coalesce`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{extract::Request, routing::get, Router};
+/// use axum_extra::middleware::CoalesceLayer;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* something expensive */ }))
+///     .layer(CoalesceLayer::new(|req: &Request| {
+///         Some((req.method().clone(), req.uri().clone()))
+///     }));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone)]
+pub struct CoalesceLayer<F> {
+    key: F,
+    max_response_size: usize,
+}
+
+impl<F> CoalesceLayer<F> {
+    /// Create a new `CoalesceLayer` that computes the key of each request with `key`.
+    ///
+    /// Requests are only coalesced if `key` returns `Some`.
+    pub fn new(key: F) -> Self {
+        Self {
+            key,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+        }
+    }
+
+    /// Set the size in bytes of the largest response body that is shared.
+    ///
+    /// Defaults to 2 MB.
+    pub fn max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+}
+
+const DEFAULT_MAX_RESPONSE_SIZE: usize = 2_097_152;
+
+impl<F> fmt::Debug for CoalesceLayer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoalesceLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S, F, K> Layer<S> for CoalesceLayer<F>
+where
+    F: Fn(&Request) -> Option<K> + Clone,
+{
+    type Service = Coalesce<S, F, K>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Coalesce::new(inner, self.key.clone()).max_response_size(self.max_response_size)
+    }
+}
+
+/// Middleware that shares the response of an in-flight request with identical concurrent
+/// requests.
+///
+/// When a `GET` request arrives while another `GET` request with the same key is being handled,
+/// it waits for the first request's response instead of calling the inner service again. The key
+/// is computed by a user supplied function, for example from the method and URI. This reduces the
+/// load caused by many clients requesting the same resource at the same time.
+///
+/// To share the response its body is buffered into memory. Response extensions aren't kept, not
+/// even for the request that called the inner service. If the body is larger than the maximum
+/// response size, the request that called the inner service gets the response unbuffered and the
+/// waiting requests call the inner service themselves. Once the response is ready, the next
+/// request with the same key calls the inner service again, so nothing is cached beyond that.
+///
+/// Requests with other methods, and requests for which the key function returns `None`, are
+/// passed through as is.
+pub struct Coalesce<S, F, K> {
+    inner: S,
+    key: F,
+    max_response_size: usize,
+    in_flight: Arc<Mutex<HashMap<K, SharedResponse>>>,
+}
+
+type SharedResponse = Shared<BoxFuture<'static, Outcome>>;
+
+#[derive(Clone)]
+enum Outcome {
+    Buffered(Arc<BufferedResponse>),
+    // the response is only sent to the request that called the inner service
+    TooLarge,
+    Failed,
+}
+
+/// Removes the in-flight entry of a key when the request that called the inner service is done,
+/// even if the inner service panicked.
+struct InFlightGuard<K> {
+    in_flight: Arc<Mutex<HashMap<K, SharedResponse>>>,
+    key: K,
+}
+
+impl<K> Drop for InFlightGuard<K>
+where
+    K: Hash + Eq,
+{
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+impl<S, F, K> Coalesce<S, F, K> {
+    /// Create a new `Coalesce` middleware.
+    pub fn new(inner: S, key: F) -> Self {
+        Self {
+            inner,
+            key,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            in_flight: Default::default(),
+        }
+    }
+
+    /// Set the size in bytes of the largest response body that is shared.
+    ///
+    /// Defaults to 2 MB.
+    pub fn max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+}
+
+impl<S, F, K> Clone for Coalesce<S, F, K>
+where
+    S: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            key: self.key.clone(),
+            max_response_size: self.max_response_size,
+            in_flight: Arc::clone(&self.in_flight),
+        }
+    }
+}
+
+impl<S, F, K> fmt::Debug for Coalesce<S, F, K>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Coalesce")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F, K> Service<Request> for Coalesce<S, F, K>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    F: Fn(&Request) -> Option<K>,
+    K: Hash + Eq + Clone + Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        let key = if req.method() == Method::GET {
+            (self.key)(&req)
+        } else {
+            None
+        };
+        let Some(key) = key else {
+            return Box::pin(inner.call(req));
+        };
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if let Some(shared) = in_flight.get(&key) {
+            let shared = shared.clone();
+            drop(in_flight);
+            return Box::pin(async move {
+                match shared.await {
+                    Outcome::Buffered(buffered) => Ok(buffered.to_response()),
+                    Outcome::TooLarge => inner.call(req).await,
+                    Outcome::Failed => Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response()),
+                }
+            });
+        }
+
+        // the unbuffered response, if it's too large to be shared
+        let too_large = Arc::new(Mutex::new(None));
+        let shared = {
+            let too_large = Arc::clone(&too_large);
+            let max_response_size = self.max_response_size;
+            async move {
+                let res = match inner.call(req).await {
+                    Ok(res) => res,
+                    Err(err) => match err {},
+                };
+
+                match BufferedResponse::from_response_limited(res, max_response_size).await {
+                    Ok(buffered) => Outcome::Buffered(Arc::new(buffered)),
+                    Err(BufferError::TooLarge(res)) => {
+                        *too_large.lock().unwrap() = Some(res);
+                        Outcome::TooLarge
+                    }
+                    Err(BufferError::Body(_)) => Outcome::Failed,
+                }
+            }
+            .boxed()
+            .shared()
+        };
+
+        in_flight.insert(key.clone(), shared.clone());
+        drop(in_flight);
+
+        let guard = InFlightGuard {
+            in_flight: Arc::clone(&self.in_flight),
+            key,
+        };
+        Box::pin(async move {
+            let outcome = shared.await;
+            drop(guard);
+            Ok(match outcome {
+                Outcome::Buffered(buffered) => buffered.to_response(),
+                Outcome::TooLarge => too_large
+                    .lock()
+                    .unwrap()
+                    .take()
+                    .unwrap_or_else(|| StatusCode::INTERNAL_SERVER_ERROR.into_response()),
+                Outcome::Failed => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use http_body_util::BodyExt;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    use tower::{service_fn, ServiceExt};
+
+    #[tokio::test]
+    async fn concurrent_identical_requests_call_inner_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = service_fn({
+            let calls = Arc::clone(&calls);
+            move |req: Request| {
+                let calls = Arc::clone(&calls);
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Ok::<_, Infallible>(format!("hello {}", req.uri()).into_response())
+                }
+            }
+        });
+        let svc = CoalesceLayer::new(|req: &Request| Some(req.uri().clone())).layer(svc);
+
+        let get = || Request::get("/foo").body(Body::empty()).unwrap();
+        let (a, b) = tokio::join!(svc.clone().oneshot(get()), svc.clone().oneshot(get()));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        for res in [a.unwrap(), b.unwrap()] {
+            assert_eq!(res.status(), StatusCode::OK);
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            assert_eq!(body, "hello /foo");
+        }
+
+        // the in-flight request is done so the next one calls the inner service again
+        svc.clone().oneshot(get()).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // other methods aren't coalesced
+        let post = || Request::post("/foo").body(Body::empty()).unwrap();
+        let (a, b) = tokio::join!(svc.clone().oneshot(post()), svc.clone().oneshot(post()));
+        a.unwrap();
+        b.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn panics_dont_poison_the_key() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = service_fn({
+            let calls = Arc::clone(&calls);
+            move |_: Request| {
+                let calls = Arc::clone(&calls);
+                async move {
+                    if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        panic!("oops");
+                    }
+                    Ok::<_, Infallible>("hello".into_response())
+                }
+            }
+        });
+        let svc = CoalesceLayer::new(|req: &Request| Some(req.uri().clone())).layer(svc);
+
+        let get = || Request::get("/foo").body(Body::empty()).unwrap();
+        let panicked = tokio::spawn(svc.clone().oneshot(get())).await;
+        assert!(panicked.is_err());
+        assert!(svc.in_flight.lock().unwrap().is_empty());
+
+        let res = svc.clone().oneshot(get()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn large_responses_arent_shared() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = service_fn({
+            let calls = Arc::clone(&calls);
+            move |_: Request| {
+                let calls = Arc::clone(&calls);
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Ok::<_, Infallible>("hello world".into_response())
+                }
+            }
+        });
+        let svc = CoalesceLayer::new(|req: &Request| Some(req.uri().clone()))
+            .max_response_size(4)
+            .layer(svc);
+
+        let get = || Request::get("/foo").body(Body::empty()).unwrap();
+        let (a, b) = tokio::join!(svc.clone().oneshot(get()), svc.clone().oneshot(get()));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        for res in [a.unwrap(), b.unwrap()] {
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            assert_eq!(body, "hello world");
+        }
+    }
+}
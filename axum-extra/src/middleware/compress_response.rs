@@ -0,0 +1,299 @@
+use crate::extract::{AcceptEncoding, Encoding};
+use axum::{
+    body::Body,
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+use http::{header, HeaderMap, HeaderValue, StatusCode};
+use http_body::Body as _;
+use http_body_util::BodyExt;
+use std::{
+    io::{self, Write},
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// The encodings supported by the enabled features, in the order the server prefers them.
+const SUPPORTED: &[Encoding] = &[
+    #[cfg(feature = "compression-br")]
+    Encoding::Br,
+    #[cfg(feature = "compression-gzip")]
+    Encoding::Gzip,
+    #[cfg(feature = "compression-deflate")]
+    Encoding::Deflate,
+];
+
+/// Layer that applies the [`CompressResponse`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::CompressResponseLayer;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     // don't compress responses smaller than 4 KiB
+///     .layer(CompressResponseLayer::new().min_size(4096));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CompressResponseLayer {
+    min_size: u64,
+}
+
+impl CompressResponseLayer {
+    /// Create a new `CompressResponseLayer`.
+    pub fn new() -> Self {
+        Self { min_size: 1024 }
+    }
+
+    /// Set the size in bytes below which response bodies aren't compressed.
+    ///
+    /// Compressing small bodies barely saves any bytes and isn't worth the CPU time. Defaults to
+    /// 1024 bytes.
+    pub fn min_size(mut self, min_size: u64) -> Self {
+        self.min_size = min_size;
+        self
+    }
+}
+
+impl Default for CompressResponseLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for CompressResponseLayer {
+    type Service = CompressResponse<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CompressResponse {
+            inner,
+            min_size: self.min_size,
+        }
+    }
+}
+
+/// Middleware that compresses response bodies according to the `Accept-Encoding` request header.
+///
+/// The encoding the client prefers among the supported ones is picked with [`AcceptEncoding`].
+/// Each encoding is enabled by a feature:
+///
+/// Encoding | Feature
+/// ---|---
+/// `br` | `compression-br`
+/// `gzip` | `compression-gzip`
+/// `deflate` | `compression-deflate`
+///
+/// If the client considers several encodings equally good, they're preferred in the order of
+/// the table. The response gets `Content-Encoding` and `Vary: Accept-Encoding` headers.
+///
+/// Responses are left alone if
+///
+/// - they already have a `Content-Encoding` header,
+/// - their `Content-Type` is already compressed, such as images, audio, video and archives,
+/// - the size of their body isn't known up front, such as server-sent events and other streams,
+/// - their body is smaller than [`CompressResponseLayer::min_size`],
+/// - or their status is informational, `204 No Content` or `304 Not Modified`.
+///
+/// The body is buffered in memory and compressed at once, which is why streaming bodies are
+/// never compressed.
+///
+/// Created with [`CompressResponseLayer`].
+#[derive(Debug, Clone)]
+pub struct CompressResponse<S> {
+    inner: S,
+    min_size: u64,
+}
+
+impl<S> Service<Request> for CompressResponse<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        // malformed headers are treated as not accepting any encoding
+        let accept_encoding = AcceptEncoding::from_headers(req.headers()).ok();
+        let min_size = self.min_size;
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let res = future.await?;
+
+            let size = res.body().size_hint().exact();
+            if !is_compressible(res.status(), res.headers())
+                || size.map_or(true, |size| size < min_size)
+            {
+                return Ok(res);
+            }
+
+            let (mut parts, body) = res.into_parts();
+            parts
+                .headers
+                .append(header::VARY, HeaderValue::from_static("accept-encoding"));
+
+            let encoding =
+                accept_encoding.and_then(|accept_encoding| accept_encoding.prefers(SUPPORTED));
+            let Some(encoding) = encoding else {
+                return Ok(Response::from_parts(parts, body));
+            };
+
+            let compressed = match body.collect().await {
+                Ok(collected) => compress(encoding, &collected.to_bytes()).ok(),
+                Err(_) => None,
+            };
+            let Some(compressed) = compressed else {
+                return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            };
+
+            parts.headers.remove(header::CONTENT_LENGTH);
+            parts.headers.insert(
+                header::CONTENT_ENCODING,
+                HeaderValue::from_static(encoding.as_str()),
+            );
+            Ok(Response::from_parts(parts, Body::from(compressed)))
+        })
+    }
+}
+
+fn is_compressible(status: StatusCode, headers: &HeaderMap) -> bool {
+    if status.is_informational()
+        || status == StatusCode::NO_CONTENT
+        || status == StatusCode::NOT_MODIFIED
+        || headers.contains_key(header::CONTENT_ENCODING)
+    {
+        return false;
+    }
+
+    let Some(mime) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<mime::Mime>().ok())
+    else {
+        return true;
+    };
+
+    match mime.type_().as_str() {
+        "image" => mime.subtype() == "svg",
+        "audio" | "video" => false,
+        "font" => !matches!(mime.subtype().as_str(), "woff" | "woff2"),
+        "application" => !matches!(
+            mime.subtype().as_str(),
+            "gzip" | "x-gzip" | "zip" | "zstd" | "x-7z-compressed" | "x-bzip2" | "x-xz" | "br"
+        ),
+        _ => true,
+    }
+}
+
+fn compress(encoding: Encoding, data: &[u8]) -> io::Result<Vec<u8>> {
+    match encoding {
+        #[cfg(feature = "compression-gzip")]
+        Encoding::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()
+        }
+        // `deflate` in HTTP means the zlib format
+        #[cfg(feature = "compression-deflate")]
+        Encoding::Deflate => {
+            let mut encoder =
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()
+        }
+        #[cfg(feature = "compression-br")]
+        Encoding::Br => {
+            // a quality of 4 is a good tradeoff between speed and size for dynamic responses
+            let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 4, 22);
+            encoder.write_all(data)?;
+            Ok(encoder.into_inner())
+        }
+        // only encodings in `SUPPORTED` are ever picked
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    #[cfg(feature = "compression-gzip")]
+    #[tokio::test]
+    async fn compresses_large_responses() {
+        use std::io::Read;
+
+        let app = Router::new()
+            .route("/", get(|| async { "hello world ".repeat(200) }))
+            .layer(CompressResponseLayer::new());
+
+        let client = TestClient::new(app);
+        let res = client.get("/").header("accept-encoding", "gzip").await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["content-encoding"], "gzip");
+        assert_eq!(res.headers()["vary"], "accept-encoding");
+
+        let compressed = res.bytes().await;
+        assert!(compressed.len() < 2400);
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(&*compressed)
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, "hello world ".repeat(200));
+    }
+
+    #[tokio::test]
+    async fn skips_small_and_compressed_responses() {
+        let app = Router::new()
+            .route("/small", get(|| async { "hello world" }))
+            .route(
+                "/image",
+                get(|| async { ([(header::CONTENT_TYPE, "image/png")], vec![0; 4096]) }),
+            )
+            .layer(CompressResponseLayer::new());
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/small").header("accept-encoding", "gzip").await;
+        assert!(res.headers().get("content-encoding").is_none());
+        assert_eq!(res.text().await, "hello world");
+
+        let res = client.get("/image").header("accept-encoding", "gzip").await;
+        assert!(res.headers().get("content-encoding").is_none());
+        assert_eq!(res.bytes().await.len(), 4096);
+    }
+
+    #[tokio::test]
+    async fn respects_accept_encoding() {
+        let app = Router::new()
+            .route("/", get(|| async { "hello world ".repeat(200) }))
+            .layer(CompressResponseLayer::new());
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert!(res.headers().get("content-encoding").is_none());
+        assert_eq!(res.headers()["vary"], "accept-encoding");
+        assert_eq!(res.text().await, "hello world ".repeat(200));
+
+        let res = client
+            .get("/")
+            .header("accept-encoding", "gzip;q=0, br;q=0, deflate;q=0")
+            .await;
+        assert!(res.headers().get("content-encoding").is_none());
+    }
+}
@@ -0,0 +1,230 @@
+use axum::{extract::Request, response::Response};
+use futures_util::future::BoxFuture;
+use http::{header, HeaderValue, StatusCode};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`ContentTypeGuard`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{http::HeaderValue, routing::get, Router};
+/// use axum_extra::middleware::ContentTypeGuardLayer;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     .layer(
+///         ContentTypeGuardLayer::new()
+///             // only these types are sent as is, everything else is sent as `text/plain`
+///             .allow("application/json")
+///             .allow("text/html")
+///             .default_content_type(HeaderValue::from_static("text/plain; charset=utf-8")),
+///     );
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContentTypeGuardLayer {
+    config: Arc<Config>,
+}
+
+#[derive(Debug, Clone)]
+struct Config {
+    default_content_type: HeaderValue,
+    // lowercase `type/subtype`, without parameters
+    allowed: Vec<String>,
+}
+
+impl ContentTypeGuardLayer {
+    /// Create a new `ContentTypeGuardLayer`.
+    ///
+    /// By default, responses without a `Content-Type` get `application/octet-stream` and all
+    /// other responses are left alone.
+    pub fn new() -> Self {
+        Self {
+            config: Arc::new(Config {
+                default_content_type: HeaderValue::from_static("application/octet-stream"),
+                allowed: Vec::new(),
+            }),
+        }
+    }
+
+    /// Set the `Content-Type` used for responses without one, or with one that isn't allowed.
+    pub fn default_content_type(mut self, content_type: HeaderValue) -> Self {
+        Arc::make_mut(&mut self.config).default_content_type = content_type;
+        self
+    }
+
+    /// Add a content type to the allowlist.
+    ///
+    /// Once the allowlist isn't empty, only responses whose `Content-Type` is in it are left
+    /// alone, and all other responses get the default content type. Content types are compared
+    /// case-insensitively and without parameters, so allowing `text/html` also allows
+    /// `text/html; charset=utf-8`.
+    pub fn allow(mut self, content_type: &str) -> Self {
+        Arc::make_mut(&mut self.config)
+            .allowed
+            .push(essence(content_type));
+        self
+    }
+}
+
+impl Default for ContentTypeGuardLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for ContentTypeGuardLayer {
+    type Service = ContentTypeGuard<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ContentTypeGuard {
+            inner,
+            config: Arc::clone(&self.config),
+        }
+    }
+}
+
+/// Middleware that makes sure every response has an explicit `Content-Type`.
+///
+/// Browsers may try to guess the type of responses without a `Content-Type` header, known as
+/// [MIME sniffing], which can lead to content being interpreted as HTML or JavaScript. This
+/// middleware sets a safe default, `application/octet-stream` unless configured otherwise, on
+/// such responses. Responses with a status that doesn't have a body, such as
+/// `204 No Content` or `304 Not Modified`, are left alone.
+///
+/// If an allowlist is configured with [`ContentTypeGuardLayer::allow`], responses whose
+/// `Content-Type` isn't in it are also changed to the default content type.
+///
+/// This works well together with `X-Content-Type-Options: nosniff`, as set by
+/// [`SecurityHeaders`](super::SecurityHeaders).
+///
+/// Created with [`ContentTypeGuardLayer`].
+///
+/// [MIME sniffing]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types#mime_sniffing
+#[derive(Debug, Clone)]
+pub struct ContentTypeGuard<S> {
+    inner: S,
+    config: Arc<Config>,
+}
+
+impl<S> Service<Request> for ContentTypeGuard<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let future = self.inner.call(req);
+        let config = Arc::clone(&self.config);
+
+        Box::pin(async move {
+            let mut res = future.await?;
+            if !has_body(res.status()) {
+                return Ok(res);
+            }
+
+            let keep = match res.headers().get(header::CONTENT_TYPE) {
+                Some(content_type) => {
+                    config.allowed.is_empty()
+                        || content_type.to_str().map_or(false, |content_type| {
+                            config.allowed.contains(&essence(content_type))
+                        })
+                }
+                None => false,
+            };
+
+            if !keep {
+                res.headers_mut()
+                    .insert(header::CONTENT_TYPE, config.default_content_type.clone());
+            }
+            Ok(res)
+        })
+    }
+}
+
+fn has_body(status: StatusCode) -> bool {
+    !(status.is_informational()
+        || status == StatusCode::NO_CONTENT
+        || status == StatusCode::NOT_MODIFIED)
+}
+
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    #[tokio::test]
+    async fn sets_default_when_missing() {
+        let app = Router::new()
+            .route("/none", get(|| async { Response::new("data".into()) }))
+            .route("/html", get(|| async { axum::response::Html("<p>hi</p>") }))
+            .route("/empty", get(|| async { StatusCode::NO_CONTENT }))
+            .layer(ContentTypeGuardLayer::new());
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/none").await;
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "application/octet-stream"
+        );
+
+        let res = client.get("/html").await;
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+
+        let res = client.get("/empty").await;
+        assert!(!res.headers().contains_key(header::CONTENT_TYPE));
+    }
+
+    #[tokio::test]
+    async fn allowlist() {
+        let layer = ContentTypeGuardLayer::new()
+            .allow("Text/HTML")
+            .default_content_type(HeaderValue::from_static("text/plain"));
+        let app = Router::new()
+            .route("/none", get(|| async { Response::new("data".into()) }))
+            .route("/html", get(|| async { axum::response::Html("<p>hi</p>") }))
+            .route("/json", get(|| async { axum::Json("hi") }))
+            .layer(layer);
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/html").await;
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+
+        let res = client.get("/json").await;
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "text/plain");
+
+        let res = client.get("/none").await;
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "text/plain");
+    }
+}
@@ -0,0 +1,379 @@
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request},
+    response::{IntoResponse, Response},
+};
+use axum_core::__define_rejection as define_rejection;
+use futures_util::future::BoxFuture;
+use http::{request::Parts, HeaderName, StatusCode};
+use std::{
+    task::{Context, Poll},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+const X_REQUEST_DEADLINE: HeaderName = HeaderName::from_static("x-request-deadline");
+const X_TIMEOUT_MS: HeaderName = HeaderName::from_static("x-timeout-ms");
+const GRPC_TIMEOUT: HeaderName = HeaderName::from_static("grpc-timeout");
+
+/// The point in time by which the current request should be handled.
+///
+/// This is inserted as a request extension by [`DeadlineLayer`] and can be extracted in handlers,
+/// or read from the extensions by other extractors and middleware, to bound their own work. For
+/// example by passing [`Deadline::remaining`] as a timeout to calls to other services.
+///
+/// Extracting `Deadline` without [`DeadlineLayer`] being added rejects the request with
+/// `500 Internal Server Error`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::{Deadline, DeadlineLayer};
+/// use std::time::Duration;
+///
+/// async fn handler(deadline: Deadline) {
+///     let result = tokio::time::timeout(deadline.remaining(), async {
+///         // call another service...
+///     })
+///     .await;
+/// }
+///
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .layer(DeadlineLayer::new(Duration::from_secs(10)));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Deadline(pub Instant);
+
+impl Deadline {
+    /// Get the time left until the deadline, or zero if it has passed.
+    pub fn remaining(&self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+
+    /// Whether the deadline has passed.
+    pub fn has_passed(&self) -> bool {
+        self.0 <= Instant::now()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Deadline
+where
+    S: Send + Sync,
+{
+    type Rejection = MissingDeadline;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Self>()
+            .copied()
+            .ok_or(MissingDeadline)
+    }
+}
+
+define_rejection! {
+    #[status = INTERNAL_SERVER_ERROR]
+    #[body = "Missing request deadline. Is `DeadlineLayer` added?"]
+    /// Rejection type for [`Deadline`] used if [`DeadlineLayer`] hasn't been added.
+    pub struct MissingDeadline;
+}
+
+/// Layer that applies [`DeadlineService`], which inserts a [`Deadline`] into the request
+/// extensions.
+///
+/// See [`Deadline`] for an example.
+#[derive(Debug, Clone, Copy)]
+pub struct DeadlineLayer {
+    budget: Duration,
+    abort: bool,
+}
+
+impl DeadlineLayer {
+    /// Create a new `DeadlineLayer` that gives each request `budget` to complete.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            abort: false,
+        }
+    }
+
+    /// Stop calling the inner service and respond with `504 Gateway Timeout` once the deadline
+    /// has passed.
+    ///
+    /// By default the deadline is only inserted into the extensions and not enforced.
+    pub fn abort_on_deadline(mut self, abort: bool) -> Self {
+        self.abort = abort;
+        self
+    }
+}
+
+impl<S> Layer<S> for DeadlineLayer {
+    type Service = DeadlineService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DeadlineService {
+            inner,
+            layer: *self,
+        }
+    }
+}
+
+/// Middleware that inserts a [`Deadline`] into the request extensions.
+///
+/// The deadline is the time the request was received plus a fixed budget. Clients can request an
+/// earlier deadline with any of these headers:
+///
+/// - `X-Request-Deadline`, holding the deadline as milliseconds since the Unix epoch.
+/// - `X-Timeout-Ms`, holding the time the client is willing to wait in milliseconds.
+/// - `grpc-timeout`, holding the time the client is willing to wait in the format used by
+///   [gRPC], such as `100m` for 100 milliseconds.
+///
+/// The earliest of the requested deadlines is used if it's earlier than the budget allows, so
+/// clients can shorten the budget but not extend it. Malformed values are ignored.
+///
+/// Created with [`DeadlineLayer`].
+///
+/// [gRPC]: https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-HTTP2.md#requests
+#[derive(Debug, Clone)]
+pub struct DeadlineService<S> {
+    inner: S,
+    layer: DeadlineLayer,
+}
+
+impl<S> Service<Request> for DeadlineService<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        let now = Instant::now();
+        let mut deadline = now.checked_add(self.layer.budget);
+        if let Some(requested) = requested_deadline(&req, now) {
+            deadline = Some(deadline.map_or(requested, |deadline| deadline.min(requested)));
+        }
+
+        // a budget too large to represent is the same as no deadline
+        let Some(deadline) = deadline else {
+            return Box::pin(inner.call(req));
+        };
+
+        req.extensions_mut().insert(Deadline(deadline));
+
+        if !self.layer.abort {
+            return Box::pin(inner.call(req));
+        }
+
+        Box::pin(async move {
+            if deadline <= Instant::now() {
+                return Ok(deadline_exceeded());
+            }
+
+            let deadline = tokio::time::Instant::from_std(deadline);
+            match tokio::time::timeout_at(deadline, inner.call(req)).await {
+                Ok(result) => result,
+                Err(_) => Ok(deadline_exceeded()),
+            }
+        })
+    }
+}
+
+fn requested_deadline(req: &Request, now: Instant) -> Option<Instant> {
+    let header = |name| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+    };
+
+    let timeouts = [
+        header(X_TIMEOUT_MS)
+            .and_then(|value| value.parse().ok())
+            .map(Duration::from_millis),
+        header(GRPC_TIMEOUT).and_then(parse_grpc_timeout),
+    ];
+
+    timeouts
+        .into_iter()
+        .flatten()
+        .filter_map(|timeout| now.checked_add(timeout))
+        .chain(header(X_REQUEST_DEADLINE).and_then(|value| absolute_deadline(value, now)))
+        .min()
+}
+
+/// Parse a deadline in milliseconds since the Unix epoch.
+fn absolute_deadline(value: &str, now: Instant) -> Option<Instant> {
+    let millis = value.parse::<u64>().ok()?;
+
+    let requested = UNIX_EPOCH.checked_add(Duration::from_millis(millis))?;
+    match requested.duration_since(SystemTime::now()) {
+        Ok(remaining) => now.checked_add(remaining),
+        // the deadline has already passed
+        Err(_) => Some(now),
+    }
+}
+
+/// Parse a `grpc-timeout` value, which is at most 8 digits followed by a unit.
+fn parse_grpc_timeout(value: &str) -> Option<Duration> {
+    if value.len() < 2 || value.len() > 9 || !value.is_char_boundary(value.len() - 1) {
+        return None;
+    }
+
+    let (amount, unit) = value.split_at(value.len() - 1);
+    if !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount = amount.parse::<u64>().ok()?;
+
+    let timeout = match unit {
+        "H" => Duration::from_secs(amount * 60 * 60),
+        "M" => Duration::from_secs(amount * 60),
+        "S" => Duration::from_secs(amount),
+        "m" => Duration::from_millis(amount),
+        "u" => Duration::from_micros(amount),
+        "n" => Duration::from_nanos(amount),
+        _ => return None,
+    };
+    Some(timeout)
+}
+
+fn deadline_exceeded() -> Response {
+    (StatusCode::GATEWAY_TIMEOUT, "Request deadline exceeded").into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    #[tokio::test]
+    async fn passed_deadline() {
+        let app = Router::new()
+            .route("/", get(|| async {}))
+            .layer(DeadlineLayer::new(Duration::from_secs(10)).abort_on_deadline(true));
+        let client = TestClient::new(app);
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let passed = now - Duration::from_secs(1);
+        let res = client
+            .get("/")
+            .header("x-request-deadline", passed.as_millis().to_string())
+            .await;
+        assert_eq!(res.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn aborts_when_deadline_passes() {
+        let app = Router::new()
+            .route("/", get(|| tokio::time::sleep(Duration::from_secs(10))))
+            .layer(DeadlineLayer::new(Duration::from_millis(10)).abort_on_deadline(true));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn propagates_deadline_to_handler() {
+        async fn handler(deadline: Deadline) -> String {
+            deadline.remaining().as_secs().to_string()
+        }
+
+        let app = Router::new()
+            .route("/", get(handler))
+            .layer(DeadlineLayer::new(Duration::from_secs(60)));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "59");
+
+        let in_ten_seconds =
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap() + Duration::from_millis(10_500);
+        let res = client
+            .get("/")
+            .header("x-request-deadline", in_ten_seconds.as_millis().to_string())
+            .await;
+        assert_eq!(res.text().await, "10");
+    }
+
+    #[tokio::test]
+    async fn short_client_timeout() {
+        let app = Router::new()
+            .route("/", get(|| tokio::time::sleep(Duration::from_secs(10))))
+            .layer(DeadlineLayer::new(Duration::from_secs(60)).abort_on_deadline(true));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("x-timeout-ms", "10").await;
+        assert_eq!(res.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let res = client.get("/").header("grpc-timeout", "10m").await;
+        assert_eq!(res.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn client_timeout_is_clamped() {
+        async fn handler(deadline: Deadline) -> String {
+            deadline.remaining().as_secs().to_string()
+        }
+
+        let app = Router::new()
+            .route("/", get(handler))
+            .layer(DeadlineLayer::new(Duration::from_secs(60)));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("x-timeout-ms", "3600000").await;
+        assert_eq!(res.text().await, "59");
+
+        let res = client.get("/").header("grpc-timeout", "1H").await;
+        assert_eq!(res.text().await, "59");
+
+        let res = client.get("/").header("grpc-timeout", "10500m").await;
+        assert_eq!(res.text().await, "10");
+
+        // malformed values are ignored
+        let res = client.get("/").header("x-timeout-ms", "soon").await;
+        assert_eq!(res.text().await, "59");
+    }
+
+    #[test]
+    fn grpc_timeout() {
+        assert_eq!(parse_grpc_timeout("2S"), Some(Duration::from_secs(2)));
+        assert_eq!(
+            parse_grpc_timeout("99999999n"),
+            Some(Duration::from_nanos(99999999))
+        );
+        assert_eq!(parse_grpc_timeout("100000000m"), None);
+        assert_eq!(parse_grpc_timeout("1s"), None);
+        assert_eq!(parse_grpc_timeout("+1S"), None);
+        assert_eq!(parse_grpc_timeout("S"), None);
+    }
+
+    #[tokio::test]
+    async fn missing_layer() {
+        let app = Router::new().route("/", get(|_: Deadline| async {}));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
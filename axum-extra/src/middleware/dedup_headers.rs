@@ -0,0 +1,218 @@
+use axum::{extract::Request, response::Response};
+use futures_util::future::BoxFuture;
+use http::{header, HeaderName};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`DedupHeaders`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{http::HeaderName, routing::get, Router};
+/// use axum_extra::middleware::DedupHeadersLayer;
+///
+/// let dedup_headers = DedupHeadersLayer::new()
+///     .header(HeaderName::from_static("x-request-id"))
+///     // keep the value that was added first instead of the last one
+///     .keep_first(true);
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     .layer(dedup_headers);
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+pub struct DedupHeadersLayer {
+    config: Arc<Config>,
+}
+
+#[derive(Debug, Clone)]
+struct Config {
+    headers: Vec<HeaderName>,
+    keep_first: bool,
+}
+
+impl DedupHeadersLayer {
+    /// Create a new `DedupHeadersLayer` with the default set of single-valued headers.
+    ///
+    /// The defaults are `Content-Type`, `Content-Length`, `Location`, `ETag`, `Last-Modified`,
+    /// `Date`, `Expires`, `Retry-After` and `Server`.
+    pub fn new() -> Self {
+        [
+            header::CONTENT_TYPE,
+            header::CONTENT_LENGTH,
+            header::LOCATION,
+            header::ETAG,
+            header::LAST_MODIFIED,
+            header::DATE,
+            header::EXPIRES,
+            header::RETRY_AFTER,
+            header::SERVER,
+        ]
+        .into_iter()
+        .fold(Self::empty(), Self::header)
+    }
+
+    /// Create a new `DedupHeadersLayer` without any headers.
+    pub fn empty() -> Self {
+        Self {
+            config: Arc::new(Config {
+                headers: Vec::new(),
+                keep_first: false,
+            }),
+        }
+    }
+
+    /// Add a header to the set of headers that are deduplicated.
+    pub fn header(mut self, name: HeaderName) -> Self {
+        let config = Arc::make_mut(&mut self.config);
+        if !config.headers.contains(&name) {
+            config.headers.push(name);
+        }
+        self
+    }
+
+    /// Set whether the first value of a duplicated header is kept, instead of the last one.
+    ///
+    /// Defaults to `false`, meaning the last value is kept, which is usually the one added by
+    /// the layer closest to the client.
+    pub fn keep_first(mut self, keep_first: bool) -> Self {
+        Arc::make_mut(&mut self.config).keep_first = keep_first;
+        self
+    }
+}
+
+impl Default for DedupHeadersLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for DedupHeadersLayer {
+    type Service = DedupHeaders<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DedupHeaders {
+            inner,
+            config: Arc::clone(&self.config),
+        }
+    }
+}
+
+/// Middleware that collapses duplicated single-valued response headers into one.
+///
+/// Headers such as `Content-Type` must only appear once, but handlers or middleware that append
+/// headers instead of inserting them can send the same header multiple times, which clients
+/// handle inconsistently. For each header in the configured set that the response has more than
+/// once, this middleware keeps only the last value, or the first value if
+/// [`DedupHeadersLayer::keep_first`] is set.
+///
+/// Headers that aren't in the set are left alone, since many headers, such as `Set-Cookie` or
+/// `Vary`, can legitimately appear multiple times.
+///
+/// Created with [`DedupHeadersLayer`].
+#[derive(Debug, Clone)]
+pub struct DedupHeaders<S> {
+    inner: S,
+    config: Arc<Config>,
+}
+
+impl<S> Service<Request> for DedupHeaders<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let future = self.inner.call(req);
+        let config = Arc::clone(&self.config);
+
+        Box::pin(async move {
+            let mut res = future.await?;
+            let headers = res.headers_mut();
+            for name in &config.headers {
+                if headers.get_all(name).iter().count() < 2 {
+                    continue;
+                }
+
+                let mut values = headers.get_all(name).iter();
+                let kept = if config.keep_first {
+                    values.next()
+                } else {
+                    values.next_back()
+                };
+                if let Some(kept) = kept.cloned() {
+                    // `insert` replaces all values of the header
+                    headers.insert(name, kept);
+                }
+            }
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, response::AppendHeaders, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn keeps_last_value() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    AppendHeaders([
+                        (header::CONTENT_TYPE, "text/plain"),
+                        (header::CONTENT_TYPE, "application/json"),
+                        (header::SET_COOKIE, "a=1"),
+                        (header::SET_COOKIE, "b=2"),
+                    ])
+                }),
+            )
+            .layer(DedupHeadersLayer::new());
+
+        let res = app.oneshot(Request::new(Body::empty())).await.unwrap();
+
+        let content_types = res.headers().get_all(header::CONTENT_TYPE);
+        assert_eq!(
+            content_types.iter().collect::<Vec<_>>(),
+            ["application/json"]
+        );
+        // not in the set
+        assert_eq!(res.headers().get_all(header::SET_COOKIE).iter().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn keeps_first_value() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    AppendHeaders([
+                        (header::CONTENT_TYPE, "text/plain"),
+                        (header::CONTENT_TYPE, "application/json"),
+                    ])
+                }),
+            )
+            .layer(DedupHeadersLayer::new().keep_first(true));
+
+        let res = app.oneshot(Request::new(Body::empty())).await.unwrap();
+
+        let content_types = res.headers().get_all(header::CONTENT_TYPE);
+        assert_eq!(content_types.iter().collect::<Vec<_>>(), ["text/plain"]);
+    }
+}
@@ -0,0 +1,157 @@
+use axum::response::{IntoResponse, Response};
+use futures_util::future::{ready, Either, Ready};
+use http::{header, HeaderValue, Request, StatusCode};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`Drain`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::DrainLayer;
+/// use std::sync::{
+///     atomic::{AtomicBool, Ordering},
+///     Arc,
+/// };
+///
+/// let draining = Arc::new(AtomicBool::new(false));
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     .layer(DrainLayer::new(Arc::clone(&draining)));
+///
+/// # async {
+/// // when receiving SIGTERM, stop accepting new requests so the load balancer moves traffic
+/// // elsewhere, while requests already in flight complete
+/// tokio::signal::ctrl_c().await.unwrap();
+/// draining.store(true, Ordering::SeqCst);
+/// # };
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+pub struct DrainLayer {
+    draining: Arc<AtomicBool>,
+}
+
+impl DrainLayer {
+    /// Create a new `DrainLayer` that rejects new requests once `draining` is set to `true`.
+    pub fn new(draining: Arc<AtomicBool>) -> Self {
+        Self { draining }
+    }
+}
+
+impl<S> Layer<S> for DrainLayer {
+    type Service = Drain<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Drain {
+            inner,
+            draining: Arc::clone(&self.draining),
+        }
+    }
+}
+
+/// Middleware that rejects new requests while the server is draining.
+///
+/// The middleware is tied to a shared [`AtomicBool`] flag that's typically set when the process
+/// receives a signal to shut down. While the flag is set, new requests are rejected with
+/// `503 Service Unavailable` and a `Connection: close` header, so clients and load balancers
+/// retry them elsewhere, without calling the inner service. Requests that were already passed to
+/// the inner service aren't affected and complete normally.
+///
+/// Created with [`DrainLayer`].
+#[derive(Debug, Clone)]
+pub struct Drain<S> {
+    inner: S,
+    draining: Arc<AtomicBool>,
+}
+
+impl<S> Drain<S> {
+    /// Create a new `Drain` middleware that rejects new requests once `draining` is set to
+    /// `true`.
+    pub fn new(inner: S, draining: Arc<AtomicBool>) -> Self {
+        Self { inner, draining }
+    }
+}
+
+impl<S, B> Service<Request<B>> for Drain<S>
+where
+    S: Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        if !self.draining.load(Ordering::SeqCst) {
+            return Either::Right(self.inner.call(req));
+        }
+
+        let res = (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CONNECTION, HeaderValue::from_static("close"))],
+            "Server is shutting down",
+        );
+        Either::Left(ready(Ok(res.into_response())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use std::convert::Infallible;
+    use tokio::sync::oneshot;
+    use tower::{service_fn, ServiceExt};
+
+    #[tokio::test]
+    async fn rejects_new_requests_while_draining() {
+        let (release_tx, release_rx) = oneshot::channel::<()>();
+        let release_rx = Arc::new(std::sync::Mutex::new(Some(release_rx)));
+
+        let svc = service_fn(move |_: Request<Body>| {
+            let release_rx = release_rx.lock().unwrap().take();
+            async move {
+                // the first request waits until it's released
+                if let Some(release_rx) = release_rx {
+                    release_rx.await.unwrap();
+                }
+                Ok::<_, Infallible>(StatusCode::OK.into_response())
+            }
+        });
+
+        let draining = Arc::new(AtomicBool::new(false));
+        let svc = DrainLayer::new(Arc::clone(&draining)).layer(svc);
+
+        let in_flight = tokio::spawn(svc.clone().oneshot(Request::new(Body::empty())));
+        tokio::task::yield_now().await;
+
+        draining.store(true, Ordering::SeqCst);
+
+        let res = svc
+            .clone()
+            .oneshot(Request::new(Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(res.headers()[header::CONNECTION], "close");
+
+        // the request that was started before draining still completes
+        release_tx.send(()).unwrap();
+        let res = in_flight.await.unwrap().unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
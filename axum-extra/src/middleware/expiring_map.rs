@@ -0,0 +1,124 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// A map whose entries expire after a fixed time to live, holding a bounded number of entries.
+///
+/// When the map is full, expired entries are removed first and then the entry closest to
+/// expiring.
+pub(super) struct ExpiringMap<K, V> {
+    ttl: Duration,
+    max_entries: usize,
+    entries: HashMap<K, Entry<V>>,
+    // breaks ties between entries that expire at the same instant
+    next_seq: u64,
+}
+
+struct Entry<V> {
+    value: V,
+    expires_at: Instant,
+    seq: u64,
+}
+
+impl<K, V> ExpiringMap<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub(super) fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries,
+            entries: HashMap::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Returns the value for `key` if it hasn't expired.
+    pub(super) fn get(&mut self, key: &K) -> Option<&V> {
+        let expired = self.entries.get(key)?.expires_at <= Instant::now();
+        if expired {
+            self.entries.remove(key);
+            return None;
+        }
+        self.entries.get(key).map(|entry| &entry.value)
+    }
+
+    /// Inserts `value` for `key`, replacing any previous value, and returns the sequence number
+    /// of the new entry.
+    pub(super) fn insert(&mut self, key: K, value: V) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        if self.max_entries == 0 {
+            return seq;
+        }
+
+        if self.entries.len() >= self.max_entries && !self.entries.contains_key(&key) {
+            let now = Instant::now();
+            self.entries.retain(|_, entry| entry.expires_at > now);
+        }
+
+        if self.entries.len() >= self.max_entries && !self.entries.contains_key(&key) {
+            let closest_to_expiring = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| (entry.expires_at, entry.seq))
+                .map(|(key, _)| key.clone());
+            if let Some(evicted) = closest_to_expiring {
+                self.entries.remove(&evicted);
+            }
+        }
+
+        let entry = Entry {
+            value,
+            expires_at: Instant::now() + self.ttl,
+            seq,
+        };
+        self.entries.insert(key, entry);
+        seq
+    }
+
+    /// Removes the entry for `key` if it's still the one inserted with the sequence number `seq`.
+    pub(super) fn remove_inserted(&mut self, key: &K, seq: u64) {
+        if self
+            .entries
+            .get(key)
+            .map_or(false, |entry| entry.seq == seq)
+        {
+            self.entries.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_when_full() {
+        let mut map = ExpiringMap::new(Duration::from_secs(60), 2);
+
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map.insert("c", 3);
+
+        assert_eq!(map.entries.len(), 2);
+        assert!(map.get(&"a").is_none());
+        assert_eq!(map.get(&"c"), Some(&3));
+    }
+
+    #[test]
+    fn remove_inserted() {
+        let mut map = ExpiringMap::new(Duration::from_secs(60), 2);
+
+        let first = map.insert("a", 1);
+        let second = map.insert("a", 2);
+
+        map.remove_inserted(&"a", first);
+        assert_eq!(map.get(&"a"), Some(&2));
+        map.remove_inserted(&"a", second);
+        assert!(map.get(&"a").is_none());
+    }
+}
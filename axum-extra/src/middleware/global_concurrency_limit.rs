@@ -0,0 +1,178 @@
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+use http::StatusCode;
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::Semaphore;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`GlobalConcurrencyLimit`] middleware.
+///
+/// All services created by the same layer, and clones of them, share one limit.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::GlobalConcurrencyLimitLayer;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     // respond with `503 Service Unavailable` if 100 requests are already in flight
+///     .layer(GlobalConcurrencyLimitLayer::new(100));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone)]
+pub struct GlobalConcurrencyLimitLayer {
+    semaphore: Arc<Semaphore>,
+}
+
+impl GlobalConcurrencyLimitLayer {
+    /// Create a new `GlobalConcurrencyLimitLayer` that allows at most `max` requests in flight.
+    pub fn new(max: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max)),
+        }
+    }
+}
+
+impl fmt::Debug for GlobalConcurrencyLimitLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalConcurrencyLimitLayer")
+            .field("available_permits", &self.semaphore.available_permits())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for GlobalConcurrencyLimitLayer {
+    type Service = GlobalConcurrencyLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GlobalConcurrencyLimit {
+            inner,
+            semaphore: Arc::clone(&self.semaphore),
+        }
+    }
+}
+
+/// Middleware that limits the number of requests in flight and sheds the rest.
+///
+/// Each request holds a permit of a shared semaphore until the response future of the inner
+/// service completes. If no permit is available the request is immediately rejected with
+/// `503 Service Unavailable` rather than waiting for one, which keeps latency bounded when the
+/// server is overloaded. This differs from [`tower::limit::ConcurrencyLimit`], which waits in
+/// `poll_ready` until a permit is released.
+///
+/// The permit is released when the response is returned, so streaming the response body doesn't
+/// count towards the limit.
+///
+/// Created with [`GlobalConcurrencyLimitLayer`].
+///
+/// [`tower::limit::ConcurrencyLimit`]: https://docs.rs/tower/latest/tower/limit/struct.ConcurrencyLimit.html
+pub struct GlobalConcurrencyLimit<S> {
+    inner: S,
+    semaphore: Arc<Semaphore>,
+}
+
+impl<S> GlobalConcurrencyLimit<S> {
+    /// Create a new `GlobalConcurrencyLimit` middleware that allows at most `max` requests in
+    /// flight.
+    ///
+    /// Use [`GlobalConcurrencyLimitLayer`] to share the limit between several services.
+    pub fn new(inner: S, max: usize) -> Self {
+        Self {
+            inner,
+            semaphore: Arc::new(Semaphore::new(max)),
+        }
+    }
+}
+
+impl<S> Clone for GlobalConcurrencyLimit<S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            semaphore: Arc::clone(&self.semaphore),
+        }
+    }
+}
+
+impl<S> fmt::Debug for GlobalConcurrencyLimit<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalConcurrencyLimit")
+            .field("inner", &self.inner)
+            .field("available_permits", &self.semaphore.available_permits())
+            .finish()
+    }
+}
+
+impl<S> Service<Request> for GlobalConcurrencyLimit<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        let Ok(permit) = Arc::clone(&self.semaphore).try_acquire_owned() else {
+            let res = (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Too many requests in flight",
+            );
+            return Box::pin(async move { Ok(res.into_response()) });
+        };
+
+        Box::pin(async move {
+            let res = inner.call(req).await;
+            drop(permit);
+            res
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use std::{convert::Infallible, time::Duration};
+    use tower::{service_fn, ServiceExt};
+
+    #[tokio::test]
+    async fn sheds_requests_over_the_limit() {
+        let svc = service_fn(|_: Request| async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok::<_, Infallible>(StatusCode::OK.into_response())
+        });
+        let svc = GlobalConcurrencyLimitLayer::new(1).layer(svc);
+
+        let req = || Request::new(Body::empty());
+        let (a, b) = tokio::join!(svc.clone().oneshot(req()), svc.clone().oneshot(req()));
+        assert_eq!(a.unwrap().status(), StatusCode::OK);
+        assert_eq!(b.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // the permit is released once the first request completes
+        let res = svc.oneshot(req()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
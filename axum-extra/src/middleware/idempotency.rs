@@ -0,0 +1,450 @@
+use super::{buffered_response::BufferedResponse, expiring_map::ExpiringMap};
+use axum::{
+    body::Body,
+    extract::{FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use http::{HeaderValue, Method, StatusCode};
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt,
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// Layer that applies the [`Idempotency`] middleware.
+///
+/// All services created by the same layer, and clones of them, share one store.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::post, Router};
+/// use axum_extra::middleware::IdempotencyLayer;
+/// use std::time::Duration;
+///
+/// let app = Router::new()
+///     .route("/payments", post(|| async { /* create a payment */ }))
+///     // remember up to 10000 responses for 24 hours each
+///     .layer(IdempotencyLayer::new(Duration::from_secs(24 * 60 * 60), 10_000));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone)]
+pub struct IdempotencyLayer {
+    store: Arc<Mutex<Store>>,
+}
+
+impl IdempotencyLayer {
+    /// Create a new `IdempotencyLayer` that stores up to `max_entries` responses for `ttl`.
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            store: Arc::new(Mutex::new(Store::new(ttl, max_entries))),
+        }
+    }
+}
+
+impl fmt::Debug for IdempotencyLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdempotencyLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for IdempotencyLayer {
+    type Service = Idempotency<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Idempotency {
+            inner,
+            store: Arc::clone(&self.store),
+        }
+    }
+}
+
+/// Middleware that makes `POST` and `PATCH` requests safe to retry using idempotency keys.
+///
+/// Clients opt in by sending an `Idempotency-Key` header with a unique value. The response to
+/// the first request with a key is stored, and subsequent requests with the same key within the
+/// time to live get the stored response without the inner service being called again.
+///
+/// A key is tied to the request it was first used with. Reusing a key with a different method,
+/// URI or body is rejected with `422 Unprocessable Entity`, and reusing it while the first request
+/// is still being processed is rejected with `409 Conflict`.
+///
+/// Server errors (`5xx`) and errors from the inner service aren't stored, so such requests can be
+/// retried with the same key. Requests with other methods or without an `Idempotency-Key` header
+/// are passed through unchanged.
+///
+/// To store a response its body is buffered into [`Bytes`], and so is the request body to detect
+/// reuse of keys. The [default body limit] applies while buffering the request body. Response
+/// extensions aren't stored. The number of stored responses is bounded. When the store is full,
+/// expired responses are removed first and then the response closest to expiring.
+///
+/// Created with [`IdempotencyLayer`].
+///
+/// [default body limit]: axum::extract::DefaultBodyLimit
+pub struct Idempotency<S> {
+    inner: S,
+    store: Arc<Mutex<Store>>,
+}
+
+impl<S> Idempotency<S> {
+    /// Create a new `Idempotency` middleware that stores up to `max_entries` responses for
+    /// `ttl`.
+    ///
+    /// Use [`IdempotencyLayer`] to share the store between several services.
+    pub fn new(inner: S, ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            inner,
+            store: Arc::new(Mutex::new(Store::new(ttl, max_entries))),
+        }
+    }
+}
+
+impl<S> Clone for Idempotency<S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            store: Arc::clone(&self.store),
+        }
+    }
+}
+
+impl<S> fmt::Debug for Idempotency<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Idempotency")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Service<Request> for Idempotency<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        let requires_key = matches!(*req.method(), Method::POST | Method::PATCH);
+        let key = match req.headers().get(IDEMPOTENCY_KEY) {
+            Some(key) if requires_key => key.clone(),
+            _ => return Box::pin(inner.call(req)),
+        };
+
+        let store = Arc::clone(&self.store);
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body =
+                match Bytes::from_request(Request::from_parts(parts.clone(), body), &()).await {
+                    Ok(bytes) => bytes,
+                    Err(rejection) => return Ok(rejection.into_response()),
+                };
+
+            let mut hasher = DefaultHasher::new();
+            parts.method.hash(&mut hasher);
+            parts.uri.hash(&mut hasher);
+            body.hash(&mut hasher);
+            let fingerprint = hasher.finish();
+
+            let lookup = store.lock().unwrap().lookup_or_reserve(&key, fingerprint);
+            // released when this future completes or is dropped without storing a response
+            let _reservation = match lookup {
+                Lookup::Reserved { seq } => Reservation {
+                    store: Arc::clone(&store),
+                    key: key.clone(),
+                    seq,
+                },
+                Lookup::Stored(stored) => return Ok(stored.to_response()),
+                Lookup::InProgress => {
+                    return Ok((
+                        StatusCode::CONFLICT,
+                        "A request with this idempotency key is still being processed",
+                    )
+                        .into_response());
+                }
+                Lookup::Mismatch => {
+                    return Ok((
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        "The idempotency key was already used for a different request",
+                    )
+                        .into_response());
+                }
+            };
+
+            let res = inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await?;
+
+            if res.status().is_server_error() {
+                return Ok(res);
+            }
+
+            let buffered = match BufferedResponse::from_response(res).await {
+                Ok(buffered) => Arc::new(buffered),
+                Err(err) => {
+                    return Ok((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        format!("Failed to buffer the response body: {err}"),
+                    )
+                        .into_response());
+                }
+            };
+
+            store
+                .lock()
+                .unwrap()
+                .insert(key, fingerprint, Arc::clone(&buffered));
+
+            Ok(buffered.to_response())
+        })
+    }
+}
+
+enum Lookup {
+    /// The key wasn't known and has been reserved for the current request.
+    Reserved {
+        seq: u64,
+    },
+    Stored(Arc<BufferedResponse>),
+    InProgress,
+    Mismatch,
+}
+
+struct Store {
+    entries: ExpiringMap<HeaderValue, Entry>,
+}
+
+struct Entry {
+    fingerprint: u64,
+    // `None` while the first request with the key is being processed
+    response: Option<Arc<BufferedResponse>>,
+}
+
+impl Store {
+    fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            entries: ExpiringMap::new(ttl, max_entries),
+        }
+    }
+
+    fn lookup_or_reserve(&mut self, key: &HeaderValue, fingerprint: u64) -> Lookup {
+        if let Some(entry) = self.entries.get(key) {
+            return if entry.fingerprint != fingerprint {
+                Lookup::Mismatch
+            } else if let Some(response) = &entry.response {
+                Lookup::Stored(Arc::clone(response))
+            } else {
+                Lookup::InProgress
+            };
+        }
+
+        let entry = Entry {
+            fingerprint,
+            response: None,
+        };
+        let seq = self.entries.insert(key.clone(), entry);
+        Lookup::Reserved { seq }
+    }
+
+    fn insert(&mut self, key: HeaderValue, fingerprint: u64, response: Arc<BufferedResponse>) {
+        let entry = Entry {
+            fingerprint,
+            response: Some(response),
+        };
+        self.entries.insert(key, entry);
+    }
+
+    /// Removes the entry for `key` if it's still the reservation with the sequence number `seq`.
+    ///
+    /// Storing a response for the key replaces the reservation with an entry with a new sequence
+    /// number, so it isn't removed.
+    fn release(&mut self, key: &HeaderValue, seq: u64) {
+        self.entries.remove_inserted(key, seq);
+    }
+}
+
+/// The reservation of a key for the request being processed.
+///
+/// Dropping it releases the key unless a response has been stored for it in the meantime, so
+/// requests that fail or are cancelled can be retried with the same key.
+struct Reservation {
+    store: Arc<Mutex<Store>>,
+    key: HeaderValue,
+    seq: u64,
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if let Ok(mut store) = self.store.lock() {
+            store.release(&self.key, self.seq);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn replays_stored_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route(
+                "/",
+                post({
+                    let calls = Arc::clone(&calls);
+                    move |body: String| async move {
+                        let n = calls.fetch_add(1, Ordering::SeqCst);
+                        (StatusCode::CREATED, format!("{body} {n}"))
+                    }
+                }),
+            )
+            .layer(IdempotencyLayer::new(Duration::from_secs(60), 10));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header(IDEMPOTENCY_KEY, "a")
+            .body("foo")
+            .await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(res.text().await, "foo 0");
+
+        // the inner service isn't called again
+        let res = client
+            .post("/")
+            .header(IDEMPOTENCY_KEY, "a")
+            .body("foo")
+            .await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(res.text().await, "foo 0");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // requests without a key aren't affected
+        let res = client.post("/").body("foo").await;
+        assert_eq!(res.text().await, "foo 1");
+    }
+
+    #[tokio::test]
+    async fn different_body_is_rejected() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route(
+                "/",
+                post({
+                    let calls = Arc::clone(&calls);
+                    move || async move {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        StatusCode::CREATED
+                    }
+                }),
+            )
+            .layer(IdempotencyLayer::new(Duration::from_secs(60), 10));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header(IDEMPOTENCY_KEY, "a")
+            .body("foo")
+            .await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        let res = client
+            .post("/")
+            .header(IDEMPOTENCY_KEY, "a")
+            .body("bar")
+            .await;
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn cancelled_request_releases_key() {
+        let hang = Arc::new(AtomicBool::new(true));
+        let app = Router::new()
+            .route(
+                "/",
+                post({
+                    let hang = Arc::clone(&hang);
+                    move || async move {
+                        if hang.load(Ordering::SeqCst) {
+                            std::future::pending::<()>().await;
+                        }
+                        StatusCode::CREATED
+                    }
+                }),
+            )
+            .layer(IdempotencyLayer::new(Duration::from_secs(60), 10));
+
+        let req = || {
+            Request::post("/")
+                .header(IDEMPOTENCY_KEY, "a")
+                .body(Body::from("foo"))
+                .unwrap()
+        };
+
+        let first =
+            tokio::time::timeout(Duration::from_millis(10), app.clone().oneshot(req())).await;
+        assert!(first.is_err());
+
+        hang.store(false, Ordering::SeqCst);
+        let res = app.oneshot(req()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn body_limit() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route(
+                "/",
+                post({
+                    let calls = Arc::clone(&calls);
+                    move || async move {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                    }
+                }),
+            )
+            .layer(IdempotencyLayer::new(Duration::from_secs(60), 10))
+            .layer(axum::extract::DefaultBodyLimit::max(4));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header(IDEMPOTENCY_KEY, "a")
+            .body("too long")
+            .await;
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+}
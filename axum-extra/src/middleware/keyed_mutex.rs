@@ -0,0 +1,302 @@
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+use http::StatusCode;
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+type Locks<K> = Arc<Mutex<HashMap<K, Arc<tokio::sync::Mutex<()>>>>>;
+
+/// Layer that applies the [`KeyedMutex`] middleware.
+///
+/// All services created by the same layer share their locks, so requests for the same key are
+/// serialized even if they're handled by different routes.
+///
+/// # Example
+///
+/// ```
+/// use axum::{extract::Request, routing::put, Router};
+/// use axum_extra::middleware::KeyedMutexLayer;
+///
+/// let app = Router::new()
+///     .route("/documents/:id", put(|| async { /* edit the document */ }))
+///     // only one edit per document at a time
+///     .layer(KeyedMutexLayer::new(|req: &Request| Some(req.uri().path().to_owned())));
+/// # let _: Router = app;
+/// ```
+pub struct KeyedMutexLayer<F, K> {
+    key: F,
+    locks: Locks<K>,
+    max_keys: usize,
+}
+
+impl<F, K> KeyedMutexLayer<F, K> {
+    /// Create a new `KeyedMutexLayer` that computes the key of each request with `key`.
+    ///
+    /// Requests are only serialized if `key` returns `Some`.
+    pub fn new(key: F) -> Self {
+        Self {
+            key,
+            locks: Default::default(),
+            max_keys: 10_000,
+        }
+    }
+
+    /// Set the maximum number of keys that can be locked at the same time.
+    ///
+    /// Requests for a new key are rejected with `503 Service Unavailable` while this many other
+    /// keys have requests in flight. Defaults to 10,000.
+    pub fn max_keys(mut self, max_keys: usize) -> Self {
+        self.max_keys = max_keys;
+        self
+    }
+}
+
+impl<F, K> Clone for KeyedMutexLayer<F, K>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            locks: Arc::clone(&self.locks),
+            max_keys: self.max_keys,
+        }
+    }
+}
+
+impl<F, K> fmt::Debug for KeyedMutexLayer<F, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedMutexLayer")
+            .field("max_keys", &self.max_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F, K> Layer<S> for KeyedMutexLayer<F, K>
+where
+    F: Clone,
+{
+    type Service = KeyedMutex<S, F, K>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        KeyedMutex {
+            inner,
+            key: self.key.clone(),
+            locks: Arc::clone(&self.locks),
+            max_keys: self.max_keys,
+        }
+    }
+}
+
+/// Middleware that serializes concurrent requests for the same key.
+///
+/// The key of each request is computed by a user supplied function, for example from the path
+/// of the resource the request modifies. While a request with some key is being handled, other
+/// requests with the same key wait for it to finish before they're passed to the inner service.
+/// Requests with different keys are handled in parallel. This prevents concurrent edits of the
+/// same resource from overwriting each other.
+///
+/// The lock is held until the inner service produces a response, not until the response body
+/// has been sent. Requests for which the key function returns `None` are passed through as is.
+///
+/// Locks are removed once no request holds or waits for them, so only keys with requests in
+/// flight take up memory. The number of such keys is limited by [`KeyedMutexLayer::max_keys`].
+///
+/// Created with [`KeyedMutexLayer`].
+pub struct KeyedMutex<S, F, K> {
+    inner: S,
+    key: F,
+    locks: Locks<K>,
+    max_keys: usize,
+}
+
+impl<S, F, K> Clone for KeyedMutex<S, F, K>
+where
+    S: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            key: self.key.clone(),
+            locks: Arc::clone(&self.locks),
+            max_keys: self.max_keys,
+        }
+    }
+}
+
+impl<S, F, K> fmt::Debug for KeyedMutex<S, F, K>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedMutex")
+            .field("inner", &self.inner)
+            .field("max_keys", &self.max_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F, K> Service<Request> for KeyedMutex<S, F, K>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    F: Fn(&Request) -> Option<K>,
+    K: Hash + Eq + Clone + Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        let Some(key) = (self.key)(&req) else {
+            return Box::pin(inner.call(req));
+        };
+
+        let lock = {
+            let mut locks = self.locks.lock().unwrap();
+            if let Some(lock) = locks.get(&key) {
+                Arc::clone(lock)
+            } else if locks.len() >= self.max_keys {
+                return Box::pin(async { Ok(StatusCode::SERVICE_UNAVAILABLE.into_response()) });
+            } else {
+                let lock = Arc::new(tokio::sync::Mutex::new(()));
+                locks.insert(key.clone(), Arc::clone(&lock));
+                lock
+            }
+        };
+        let entry = LockEntry {
+            locks: Arc::clone(&self.locks),
+            key,
+            lock: Some(lock),
+        };
+
+        Box::pin(async move {
+            let guard = entry.lock().lock_owned().await;
+            let result = inner.call(req).await;
+            drop(guard);
+            drop(entry);
+            result
+        })
+    }
+}
+
+/// A reference to the lock of a key that removes the lock from the map when the last reference
+/// is dropped, including when the request is cancelled.
+struct LockEntry<K>
+where
+    K: Hash + Eq,
+{
+    locks: Locks<K>,
+    key: K,
+    lock: Option<Arc<tokio::sync::Mutex<()>>>,
+}
+
+impl<K> LockEntry<K>
+where
+    K: Hash + Eq,
+{
+    fn lock(&self) -> Arc<tokio::sync::Mutex<()>> {
+        Arc::clone(self.lock.as_ref().unwrap())
+    }
+}
+
+impl<K> Drop for LockEntry<K>
+where
+    K: Hash + Eq,
+{
+    fn drop(&mut self) {
+        // entries are only created and dropped while holding the map lock, so no other request
+        // can start waiting for the lock while we decide to remove it
+        let mut locks = self.locks.lock().unwrap();
+        if let Some(lock) = self.lock.take() {
+            // one reference is held by the map and one by us
+            if Arc::strong_count(&lock) == 2 {
+                locks.remove(&self.key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::State, routing::put, Router};
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    use tower::ServiceExt;
+
+    #[derive(Default)]
+    struct Concurrency {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    async fn track(State(concurrency): State<Arc<Concurrency>>) {
+        let current = concurrency.current.fetch_add(1, Ordering::SeqCst) + 1;
+        concurrency.max.fetch_max(current, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        concurrency.current.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    fn put_request(uri: &str) -> Request {
+        Request::put(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn same_key_is_serialized() {
+        let concurrency = Arc::new(Concurrency::default());
+        let app = Router::new()
+            .route("/:id", put(track))
+            .layer(KeyedMutexLayer::new(|req: &Request| {
+                Some(req.uri().path().to_owned())
+            }))
+            .with_state(Arc::clone(&concurrency));
+
+        let (a, b) = tokio::join!(
+            app.clone().oneshot(put_request("/1")),
+            app.clone().oneshot(put_request("/1")),
+        );
+        assert_eq!(a.unwrap().status(), StatusCode::OK);
+        assert_eq!(b.unwrap().status(), StatusCode::OK);
+        assert_eq!(concurrency.max.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn different_keys_run_in_parallel() {
+        let concurrency = Arc::new(Concurrency::default());
+        let app = Router::new()
+            .route("/:id", put(track))
+            .layer(KeyedMutexLayer::new(|req: &Request| {
+                Some(req.uri().path().to_owned())
+            }))
+            .with_state(Arc::clone(&concurrency));
+
+        let (a, b) = tokio::join!(
+            app.clone().oneshot(put_request("/1")),
+            app.clone().oneshot(put_request("/2")),
+        );
+        assert_eq!(a.unwrap().status(), StatusCode::OK);
+        assert_eq!(b.unwrap().status(), StatusCode::OK);
+        assert_eq!(concurrency.max.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,133 @@
+use axum::response::{IntoResponse, Response};
+use futures_util::future::{ready, Either, Ready};
+use http::{Request, StatusCode};
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`MaxHeaderBytes`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::MaxHeaderBytesLayer;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     // reject requests with more than 8 KiB of headers
+///     .layer(MaxHeaderBytesLayer::new(8 * 1024));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MaxHeaderBytesLayer {
+    max: usize,
+}
+
+impl MaxHeaderBytesLayer {
+    /// Create a new `MaxHeaderBytesLayer` that allows at most `max` bytes of headers.
+    pub fn new(max: usize) -> Self {
+        Self { max }
+    }
+}
+
+impl<S> Layer<S> for MaxHeaderBytesLayer {
+    type Service = MaxHeaderBytes<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MaxHeaderBytes::new(inner, self.max)
+    }
+}
+
+/// Middleware that rejects requests with too many bytes of headers.
+///
+/// The size of the headers is the sum of the lengths of all header names and values. Requests
+/// whose headers are larger than the maximum are rejected with
+/// `431 Request Header Fields Too Large` without calling the inner service.
+///
+/// This complements the limits of the HTTP server, such as hyper's maximum buffer size, with a
+/// limit that can differ between routes.
+///
+/// Created with [`MaxHeaderBytesLayer`].
+#[derive(Debug, Clone, Copy)]
+pub struct MaxHeaderBytes<S> {
+    inner: S,
+    max: usize,
+}
+
+impl<S> MaxHeaderBytes<S> {
+    /// Create a new `MaxHeaderBytes` middleware that allows at most `max` bytes of headers.
+    pub fn new(inner: S, max: usize) -> Self {
+        Self { inner, max }
+    }
+}
+
+impl<S, B> Service<Request<B>> for MaxHeaderBytes<S>
+where
+    S: Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let size = req
+            .headers()
+            .iter()
+            .map(|(name, value)| name.as_str().len() + value.len())
+            .sum::<usize>();
+
+        if size <= self.max {
+            return Either::Right(self.inner.call(req));
+        }
+
+        let res = (
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            format!(
+                "Request headers are {size} bytes which is more than the limit of {} bytes",
+                self.max
+            ),
+        );
+        Either::Left(ready(Ok(res.into_response())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn request(value: &str) -> Request<Body> {
+        Request::builder()
+            .uri("/")
+            .header("x-data", value)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn under_the_limit() {
+        // 6 bytes of name and 58 bytes of value is exactly the limit
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(MaxHeaderBytesLayer::new(64));
+
+        let res = app.oneshot(request(&"a".repeat(58))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn over_the_limit() {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(MaxHeaderBytesLayer::new(64));
+
+        let res = app.oneshot(request(&"a".repeat(59))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE);
+    }
+}
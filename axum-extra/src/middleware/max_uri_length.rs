@@ -0,0 +1,146 @@
+use axum::response::{IntoResponse, Response};
+use futures_util::future::{ready, Either, Ready};
+use http::{Request, StatusCode, Uri};
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`MaxUriLength`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::MaxUriLengthLayer;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     // reject requests whose URI is longer than 2 KiB
+///     .layer(MaxUriLengthLayer::new(2048));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MaxUriLengthLayer {
+    max: usize,
+}
+
+impl MaxUriLengthLayer {
+    /// Create a new `MaxUriLengthLayer` that allows URIs of at most `max` bytes.
+    pub fn new(max: usize) -> Self {
+        Self { max }
+    }
+}
+
+impl<S> Layer<S> for MaxUriLengthLayer {
+    type Service = MaxUriLength<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MaxUriLength::new(inner, self.max)
+    }
+}
+
+/// Middleware that rejects requests with overly long URIs.
+///
+/// The length of the URI is the number of bytes of the request target, including the query
+/// string, as well as the scheme and authority if the request has them. Requests whose URI is
+/// longer than the maximum are rejected with `414 URI Too Long` without calling the inner
+/// service.
+///
+/// Created with [`MaxUriLengthLayer`].
+#[derive(Debug, Clone, Copy)]
+pub struct MaxUriLength<S> {
+    inner: S,
+    max: usize,
+}
+
+impl<S> MaxUriLength<S> {
+    /// Create a new `MaxUriLength` middleware that allows URIs of at most `max` bytes.
+    pub fn new(inner: S, max: usize) -> Self {
+        Self { inner, max }
+    }
+}
+
+impl<S, B> Service<Request<B>> for MaxUriLength<S>
+where
+    S: Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let len = uri_len(req.uri());
+
+        if len <= self.max {
+            return Either::Right(self.inner.call(req));
+        }
+
+        let res = (
+            StatusCode::URI_TOO_LONG,
+            format!(
+                "URI is {len} bytes which is more than the limit of {} bytes",
+                self.max
+            ),
+        );
+        Either::Left(ready(Ok(res.into_response())))
+    }
+}
+
+/// The length of `uri` as it would be formatted, without allocating.
+fn uri_len(uri: &Uri) -> usize {
+    let scheme = uri
+        .scheme_str()
+        .map_or(0, |scheme| scheme.len() + "://".len());
+    let authority = uri
+        .authority()
+        .map_or(0, |authority| authority.as_str().len());
+    let path_and_query = uri
+        .path_and_query()
+        .map_or(0, |path_and_query| path_and_query.as_str().len());
+    scheme + authority + path_and_query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn request(query: &str) -> Request<Body> {
+        Request::builder()
+            .uri(format!("/?q={query}"))
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn under_the_limit() {
+        // `/?q=` is 4 bytes, so 60 bytes of query is exactly the limit
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(MaxUriLengthLayer::new(64));
+
+        let res = app.oneshot(request(&"a".repeat(60))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn over_the_limit() {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(MaxUriLengthLayer::new(64));
+
+        let res = app.oneshot(request(&"a".repeat(61))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::URI_TOO_LONG);
+    }
+
+    #[test]
+    fn absolute_uri() {
+        let uri = "https://example.com/path?query".parse::<Uri>().unwrap();
+        assert_eq!(uri_len(&uri), uri.to_string().len());
+    }
+}
@@ -0,0 +1,155 @@
+use http::{HeaderName, Method, Request};
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+const X_HTTP_METHOD_OVERRIDE: HeaderName = HeaderName::from_static("x-http-method-override");
+
+/// Layer that applies the [`MethodOverride`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{extract::Request, routing::put, Router, ServiceExt};
+/// use axum_extra::middleware::MethodOverrideLayer;
+/// use tower::Layer;
+///
+/// let router = Router::new().route("/", put(|| async { /* ... */ }));
+///
+/// // the layer must wrap the whole router so the method is changed before routing
+/// let app = MethodOverrideLayer::new().layer(router);
+/// # async {
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+/// axum::serve(listener, ServiceExt::<Request>::into_make_service(app)).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MethodOverrideLayer {
+    _priv: (),
+}
+
+impl MethodOverrideLayer {
+    /// Create a new `MethodOverrideLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for MethodOverrideLayer {
+    type Service = MethodOverride<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MethodOverride::new(inner)
+    }
+}
+
+/// Middleware that overrides the method of `POST` requests with the `X-HTTP-Method-Override`
+/// header.
+///
+/// This supports clients, such as HTML forms, that can only send `GET` and `POST` requests. A
+/// `POST` request with an `X-HTTP-Method-Override: PUT` header is treated as a `PUT` request by
+/// the inner service, so routing and the [`Method`] extractor see the overridden method.
+///
+/// Only `POST` requests are overridden and only to `PUT`, `DELETE`, or `PATCH`. Other values of
+/// the header are ignored, so a request can't be turned into a safe method like `GET` and bypass
+/// protections such as CSRF checks that only apply to unsafe methods.
+///
+/// Since the method is used for routing, the middleware must wrap the whole [`Router`] rather
+/// than being added with [`Router::layer`].
+///
+/// [`Router`]: axum::Router
+/// [`Router::layer`]: axum::Router::layer
+#[derive(Debug, Clone, Copy)]
+pub struct MethodOverride<S> {
+    inner: S,
+}
+
+impl<S> MethodOverride<S> {
+    /// Create a new `MethodOverride` middleware.
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S, B> Service<Request<B>> for MethodOverride<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        if req.method() == Method::POST {
+            if let Some(method) = override_method(&req) {
+                *req.method_mut() = method;
+            }
+        }
+
+        self.inner.call(req)
+    }
+}
+
+fn override_method<B>(req: &Request<B>) -> Option<Method> {
+    let value = req.headers().get(X_HTTP_METHOD_OVERRIDE)?.as_bytes();
+
+    [Method::PUT, Method::DELETE, Method::PATCH]
+        .into_iter()
+        .find(|method| method.as_str().as_bytes().eq_ignore_ascii_case(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::any, Router};
+    use http::StatusCode;
+
+    #[tokio::test]
+    async fn overrides_post() {
+        let app = Router::new().route("/", any(|method: Method| async move { method.to_string() }));
+        let app = MethodOverrideLayer::new().layer(app);
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("x-http-method-override", "PUT")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "PUT");
+
+        let res = client
+            .post("/")
+            .header("x-http-method-override", "delete")
+            .await;
+        assert_eq!(res.text().await, "DELETE");
+
+        let res = client.post("/").await;
+        assert_eq!(res.text().await, "POST");
+    }
+
+    #[tokio::test]
+    async fn ignores_other_methods_and_overrides() {
+        let app = Router::new().route("/", any(|method: Method| async move { method.to_string() }));
+        let app = MethodOverrideLayer::new().layer(app);
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("x-http-method-override", "GET")
+            .await;
+        assert_eq!(res.text().await, "POST");
+
+        let res = client
+            .get("/")
+            .header("x-http-method-override", "DELETE")
+            .await;
+        assert_eq!(res.text().await, "GET");
+    }
+}
@@ -0,0 +1,167 @@
+use axum::{
+    body::Body,
+    extract::{FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use std::task::{Context, Poll};
+use tower::ServiceExt;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`Mirror`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{extract::Request, routing::get, Router};
+/// use axum_extra::middleware::MirrorLayer;
+/// use std::convert::Infallible;
+/// use tower::service_fn;
+///
+/// // a service that sends requests to the new version of some backend
+/// let shadow = service_fn(|_req: Request| async move {
+///     // ...
+///     Ok::<_, Infallible>(())
+/// });
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     .layer(MirrorLayer::new(shadow));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+pub struct MirrorLayer<Sh> {
+    shadow: Sh,
+}
+
+impl<Sh> MirrorLayer<Sh> {
+    /// Create a new `MirrorLayer` that mirrors requests to `shadow`.
+    pub fn new(shadow: Sh) -> Self {
+        Self { shadow }
+    }
+}
+
+impl<P, Sh> Layer<P> for MirrorLayer<Sh>
+where
+    Sh: Clone,
+{
+    type Service = Mirror<P, Sh>;
+
+    fn layer(&self, primary: P) -> Self::Service {
+        Mirror::new(primary, self.shadow.clone())
+    }
+}
+
+/// Middleware that sends a copy of each request to a shadow service.
+///
+/// The request is handled by the primary service as usual, and its response is returned to the
+/// client. A copy of the request is sent to the shadow service on a separate task, so it doesn't
+/// delay the primary response. The shadow service's response and errors are ignored.
+///
+/// This is useful for testing a new version of a service with real traffic before rolling it out.
+///
+/// The request body is buffered into [`Bytes`] so it can be sent to both services. The [default
+/// body limit] applies while buffering.
+///
+/// [default body limit]: axum::extract::DefaultBodyLimit
+#[derive(Debug, Clone)]
+pub struct Mirror<P, Sh> {
+    primary: P,
+    shadow: Sh,
+}
+
+impl<P, Sh> Mirror<P, Sh> {
+    /// Create a new `Mirror` middleware.
+    pub fn new(primary: P, shadow: Sh) -> Self {
+        Self { primary, shadow }
+    }
+}
+
+impl<P, Sh> Service<Request> for Mirror<P, Sh>
+where
+    P: Service<Request, Response = Response> + Clone + Send + 'static,
+    P::Future: Send + 'static,
+    P::Error: Send + 'static,
+    Sh: Service<Request> + Clone + Send + 'static,
+    Sh::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = P::Error;
+    type Future = BoxFuture<'static, Result<Response, P::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.primary.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_primary = self.primary.clone();
+        let mut primary = std::mem::replace(&mut self.primary, not_ready_primary);
+        let shadow = self.shadow.clone();
+
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let bytes =
+                match Bytes::from_request(Request::from_parts(parts.clone(), body), &()).await {
+                    Ok(bytes) => bytes,
+                    Err(rejection) => return Ok(rejection.into_response()),
+                };
+
+            let shadow_req = Request::from_parts(parts.clone(), Body::from(bytes.clone()));
+            tokio::spawn(async move {
+                let _ = shadow.oneshot(shadow_req).await;
+            });
+
+            primary
+                .call(Request::from_parts(parts, Body::from(bytes)))
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::{Method, StatusCode};
+    use std::convert::Infallible;
+    use tokio::sync::mpsc;
+    use tower::service_fn;
+
+    #[tokio::test]
+    async fn mirrors_request_to_shadow() {
+        let primary = service_fn(|req: Request| async move {
+            let body = String::from_request(req, &()).await.unwrap();
+            Ok::<_, Infallible>(format!("primary: {body}").into_response())
+        });
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let shadow = service_fn(move |req: Request| {
+            let tx = tx.clone();
+            async move {
+                let (parts, body) = req.into_parts();
+                let body = String::from_request(Request::new(body), &()).await.unwrap();
+                tx.send((parts, body)).unwrap();
+                Ok::<_, Infallible>(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        });
+
+        let req = Request::post("/foo?bar=baz")
+            .header("x-foo", "foo")
+            .body(Body::from("hello"))
+            .unwrap();
+        let res = Mirror::new(primary, shadow).oneshot(req).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = String::from_request(Request::new(res.into_body()), &())
+            .await
+            .unwrap();
+        assert_eq!(body, "primary: hello");
+
+        let (parts, body) = rx.recv().await.unwrap();
+        assert_eq!(parts.method, Method::POST);
+        assert_eq!(parts.uri, "/foo?bar=baz");
+        assert_eq!(parts.headers["x-foo"], "foo");
+        assert_eq!(body, "hello");
+    }
+}
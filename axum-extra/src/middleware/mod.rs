@@ -0,0 +1,208 @@
+//! Additional middleware utilities.
+
+use crate::either::Either;
+use tower_layer::Identity;
+
+mod auto_head;
+
+mod buffered_response;
+
+#[cfg(feature = "byte-accounting")]
+mod byte_accounting;
+
+mod circuit_breaker;
+
+#[cfg(feature = "coalesce")]
+mod coalesce;
+
+#[cfg(any(
+    feature = "compression-br",
+    feature = "compression-deflate",
+    feature = "compression-gzip"
+))]
+mod compress_response;
+
+mod content_type_guard;
+
+#[cfg(feature = "deadline")]
+mod deadline;
+
+mod dedup_headers;
+
+mod drain;
+
+mod expiring_map;
+
+#[cfg(feature = "global-concurrency-limit")]
+mod global_concurrency_limit;
+
+mod idempotency;
+
+#[cfg(feature = "keyed-mutex")]
+mod keyed_mutex;
+
+#[cfg(feature = "mirror")]
+mod mirror;
+
+mod max_header_bytes;
+
+mod max_uri_length;
+
+mod method_override;
+
+#[cfg(feature = "rate-limit")]
+mod rate_limit;
+
+mod record;
+
+mod require_headers;
+
+mod require_secure;
+
+#[cfg(feature = "response-body-timeout")]
+mod response_body_timeout;
+
+mod response_cache;
+
+#[cfg(feature = "response-pacer")]
+mod response_pacer;
+
+#[cfg(feature = "retry")]
+mod retry;
+
+mod schema_version;
+
+mod security_headers;
+
+mod server_timing;
+
+#[cfg(feature = "slow-request-log")]
+mod slow_request_log;
+
+mod title_case_headers;
+
+mod trailing_slash_redirect;
+
+#[cfg(feature = "verify-digest")]
+mod verify_digest;
+
+pub use self::auto_head::{AutoHead, AutoHeadLayer};
+
+#[cfg(feature = "byte-accounting")]
+pub use self::byte_accounting::{ByteAccounting, ByteAccountingLayer, ByteCounts};
+
+pub use self::circuit_breaker::{CircuitBreaker, CircuitBreakerLayer};
+
+#[cfg(feature = "coalesce")]
+pub use self::coalesce::{Coalesce, CoalesceLayer};
+
+#[cfg(any(
+    feature = "compression-br",
+    feature = "compression-deflate",
+    feature = "compression-gzip"
+))]
+pub use self::compress_response::{CompressResponse, CompressResponseLayer};
+
+pub use self::content_type_guard::{ContentTypeGuard, ContentTypeGuardLayer};
+
+#[cfg(feature = "deadline")]
+pub use self::deadline::{Deadline, DeadlineLayer, DeadlineService, MissingDeadline};
+
+pub use self::dedup_headers::{DedupHeaders, DedupHeadersLayer};
+
+pub use self::drain::{Drain, DrainLayer};
+
+#[cfg(feature = "global-concurrency-limit")]
+pub use self::global_concurrency_limit::{GlobalConcurrencyLimit, GlobalConcurrencyLimitLayer};
+
+pub use self::idempotency::{Idempotency, IdempotencyLayer};
+
+#[cfg(feature = "keyed-mutex")]
+pub use self::keyed_mutex::{KeyedMutex, KeyedMutexLayer};
+
+pub use self::max_header_bytes::{MaxHeaderBytes, MaxHeaderBytesLayer};
+
+pub use self::max_uri_length::{MaxUriLength, MaxUriLengthLayer};
+
+pub use self::method_override::{MethodOverride, MethodOverrideLayer};
+
+#[cfg(feature = "mirror")]
+pub use self::mirror::{Mirror, MirrorLayer};
+
+#[cfg(feature = "rate-limit")]
+pub use self::rate_limit::{RateLimit, RateLimitLayer};
+
+pub use self::record::{replay, Record, RecordLayer, RecordedRequest};
+
+pub use self::require_headers::{RequireHeaders, RequireHeadersLayer};
+
+pub use self::require_secure::{RequireSecure, RequireSecureLayer, TlsInfo, TlsVersion};
+
+#[cfg(feature = "response-body-timeout")]
+pub use self::response_body_timeout::{ResponseBodyTimeout, ResponseBodyTimeoutLayer};
+
+pub use self::response_cache::{ResponseCache, ResponseCacheLayer};
+
+#[cfg(feature = "response-pacer")]
+pub use self::response_pacer::{ResponsePacer, ResponsePacerLayer};
+
+#[cfg(feature = "retry")]
+pub use self::retry::{Retry, RetryLayer, RetryPolicy};
+
+pub use self::schema_version::{SchemaVersion, SchemaVersionLayer};
+
+pub use self::security_headers::{SecurityHeaders, SecurityHeadersLayer};
+
+pub use self::server_timing::{
+    MissingServerTimings, ServerTiming, ServerTimingLayer, ServerTimings,
+};
+
+#[cfg(feature = "slow-request-log")]
+pub use self::slow_request_log::{SlowRequestLog, SlowRequestLogLayer};
+
+pub use self::title_case_headers::{TitleCaseHeaders, TitleCaseHeadersLayer, TitleCasedHeaders};
+
+pub use self::trailing_slash_redirect::{TrailingSlashRedirect, TrailingSlashRedirectLayer};
+
+#[cfg(feature = "verify-digest")]
+pub use self::verify_digest::{VerifyDigest, VerifyDigestLayer};
+
+/// Convert an `Option<Layer>` into a [`Layer`].
+///
+/// If the layer is a `Some` it'll be applied, otherwise not.
+///
+/// # Example
+///
+/// ```
+/// use axum_extra::middleware::option_layer;
+/// use axum::{Router, routing::get};
+/// use std::time::Duration;
+/// use tower_http::timeout::TimeoutLayer;
+///
+/// # let option_timeout = Some(Duration::new(10, 0));
+/// let timeout_layer = option_timeout.map(TimeoutLayer::new);
+///
+/// let app = Router::new()
+///     .route("/", get(|| async {}))
+///     .layer(option_layer(timeout_layer));
+/// # let _: Router = app;
+/// ```
+///
+/// # Difference between this and [`tower::util::option_layer`]
+///
+/// [`tower::util::option_layer`] always changes the error type to [`BoxError`] which requires
+/// using [`HandleErrorLayer`] when used with axum, even if the layer you're applying uses
+/// [`Infallible`].
+///
+/// `axum_extra::middleware::option_layer` on the other hand doesn't change the error type so can
+/// be applied directly.
+///
+/// [`Layer`]: tower_layer::Layer
+/// [`BoxError`]: tower::BoxError
+/// [`HandleErrorLayer`]: axum::error_handling::HandleErrorLayer
+/// [`Infallible`]: std::convert::Infallible
+pub fn option_layer<L>(layer: Option<L>) -> Either<L, Identity> {
+    layer
+        .map(Either::E1)
+        .unwrap_or_else(|| Either::E2(Identity::new()))
+}
@@ -0,0 +1,363 @@
+use crate::extract::TrustedProxies;
+use axum::{
+    extract::connect_info::ConnectInfo,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::{ready, Either, Ready};
+use http::{header, Request, StatusCode};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`RateLimit`] middleware.
+///
+/// All services created by the same layer, and clones of them, share one set of buckets.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::RateLimitLayer;
+/// use std::{net::SocketAddr, time::Duration};
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     // allow 10 requests per second per client, with bursts of up to 20 requests
+///     .layer(RateLimitLayer::new(10, Duration::from_secs(1)).burst(20));
+///
+/// // the client IP is read from `ConnectInfo`
+/// let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+/// # let _ = make_service;
+/// ```
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    config: Config,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+#[derive(Debug, Clone)]
+struct Config {
+    // tokens added per second
+    rate: f64,
+    burst: f64,
+    trusted_proxies: TrustedProxies,
+}
+
+impl RateLimitLayer {
+    /// Create a new `RateLimitLayer` that allows each client `num` requests `per` duration.
+    ///
+    /// By default the burst size is `num` and at most 10000 clients are tracked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num` is zero or `per` is zero.
+    pub fn new(num: u32, per: Duration) -> Self {
+        assert!(num > 0, "`num` must not be zero");
+        assert!(!per.is_zero(), "`per` must not be zero");
+        Self {
+            config: Config {
+                rate: f64::from(num) / per.as_secs_f64(),
+                burst: f64::from(num),
+                trusted_proxies: TrustedProxies::new(),
+            },
+            buckets: Arc::new(Mutex::new(Buckets::new(10_000))),
+        }
+    }
+
+    /// Set the maximum number of requests a client can make at once after being idle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    pub fn burst(mut self, burst: u32) -> Self {
+        assert!(burst > 0, "`burst` must not be zero");
+        self.config.burst = f64::from(burst);
+        self
+    }
+
+    /// Set the maximum number of clients that are tracked.
+    ///
+    /// When a new client arrives and the limit is reached, the client that was seen least
+    /// recently is forgotten, so its next request starts with a full bucket.
+    pub fn max_clients(self, max_clients: usize) -> Self {
+        Self {
+            config: self.config,
+            buckets: Arc::new(Mutex::new(Buckets::new(max_clients))),
+        }
+    }
+
+    /// Set the proxies whose forwarding headers are trusted when resolving the client IP.
+    ///
+    /// The client IP is resolved the same way as by the [`ClientIp`] extractor, so only
+    /// addresses added by trusted proxies are considered and clients can't evade the limit by
+    /// sending the headers themselves.
+    ///
+    /// Defaults to [`TrustedProxies::new`], which doesn't trust any proxy.
+    ///
+    /// [`ClientIp`]: crate::extract::ClientIp
+    pub fn trusted_proxies(mut self, trusted_proxies: TrustedProxies) -> Self {
+        self.config.trusted_proxies = trusted_proxies;
+        self
+    }
+}
+
+impl fmt::Debug for RateLimitLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimitLayer")
+            .field("rate", &self.config.rate)
+            .field("burst", &self.config.burst)
+            .field("trusted_proxies", &self.config.trusted_proxies)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            config: self.config.clone(),
+            buckets: Arc::clone(&self.buckets),
+        }
+    }
+}
+
+/// Middleware that limits the rate of requests per client IP address.
+///
+/// Each client gets its own [token bucket], holding up to the burst size of tokens and refilled
+/// at the configured rate. Every request takes a token. Requests from clients whose bucket is
+/// empty are rejected with `429 Too Many Requests` and a `Retry-After` header saying how many
+/// seconds until the next token is available, without calling the inner service.
+///
+/// The address of the peer is read from the [`ConnectInfo<SocketAddr>`](ConnectInfo) request
+/// extension, so the app must be served with [`Router::into_make_service_with_connect_info`].
+/// If the peer is one of the proxies set with [`RateLimitLayer::trusted_proxies`], the client IP
+/// is resolved from the proxy headers. Requests without `ConnectInfo` aren't limited.
+///
+/// The number of tracked clients is bounded, see [`RateLimitLayer::max_clients`].
+///
+/// Created with [`RateLimitLayer`].
+///
+/// [token bucket]: https://en.wikipedia.org/wiki/Token_bucket
+/// [`Router::into_make_service_with_connect_info`]: axum::Router::into_make_service_with_connect_info
+#[derive(Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    config: Config,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+impl<S> fmt::Debug for RateLimit<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimit")
+            .field("inner", &self.inner)
+            .field("rate", &self.config.rate)
+            .field("burst", &self.config.burst)
+            .field("trusted_proxies", &self.config.trusted_proxies)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, B> Service<Request<B>> for RateLimit<S>
+where
+    S: Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let Some(ip) = client_ip(&req, &self.config.trusted_proxies) else {
+            return Either::Right(self.inner.call(req));
+        };
+
+        let acquired = self.buckets.lock().unwrap().acquire(ip, &self.config);
+        let retry_after = match acquired {
+            Ok(()) => return Either::Right(self.inner.call(req)),
+            Err(retry_after) => retry_after,
+        };
+
+        // round up so clients retrying after the given number of seconds get a token
+        let retry_after = (retry_after.as_secs_f64().ceil() as u64).max(1);
+        let res = (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            "Too many requests",
+        );
+        Either::Left(ready(Ok(res.into_response())))
+    }
+}
+
+fn client_ip<B>(req: &Request<B>, trusted_proxies: &TrustedProxies) -> Option<IpAddr> {
+    let ConnectInfo(peer) = req.extensions().get::<ConnectInfo<SocketAddr>>()?;
+    Some(trusted_proxies.resolve(peer.ip(), req.headers()))
+}
+
+struct Buckets {
+    max_clients: usize,
+    buckets: HashMap<IpAddr, Bucket>,
+    // clients by the sequence number they were last seen at, least recently seen first
+    order: BTreeMap<u64, IpAddr>,
+    next_seq: u64,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+    last_seen: u64,
+}
+
+impl Buckets {
+    fn new(max_clients: usize) -> Self {
+        Self {
+            max_clients,
+            buckets: HashMap::new(),
+            order: BTreeMap::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Take a token from the bucket of `ip`, or return how long until one is available.
+    fn acquire(&mut self, ip: IpAddr, config: &Config) -> Result<(), Duration> {
+        let now = Instant::now();
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        if self.buckets.len() >= self.max_clients && !self.buckets.contains_key(&ip) {
+            if let Some((_, evicted)) = self.order.pop_first() {
+                self.buckets.remove(&evicted);
+            }
+        }
+
+        let bucket = self.buckets.entry(ip).or_insert(Bucket {
+            tokens: config.burst,
+            last_refill: now,
+            last_seen: seq,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * config.rate).min(config.burst);
+        bucket.last_refill = now;
+        self.order.remove(&bucket.last_seen);
+        self.order.insert(seq, ip);
+        bucket.last_seen = seq;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / config.rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn request(ip: [u8; 4]) -> Request<Body> {
+        let mut req = Request::new(Body::empty());
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((ip, 1234))));
+        req
+    }
+
+    #[tokio::test]
+    async fn limits_per_ip() {
+        let app = Router::new()
+            .route("/", get(|| async {}))
+            .layer(RateLimitLayer::new(1, Duration::from_secs(60)).burst(2));
+
+        for _ in 0..2 {
+            let res = app.clone().oneshot(request([10, 0, 0, 1])).await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+        }
+
+        let res = app.clone().oneshot(request([10, 0, 0, 1])).await.unwrap();
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = res.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((59..=60).contains(&retry_after));
+
+        // other clients are unaffected
+        let res = app.clone().oneshot(request([10, 0, 0, 2])).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn trusted_proxies() {
+        let app = Router::new().route("/", get(|| async {})).layer(
+            RateLimitLayer::new(1, Duration::from_secs(60))
+                .trusted_proxies(TrustedProxies::new().trust("10.0.0.0/8")),
+        );
+
+        let forwarded = |chain: &'static str| {
+            let mut req = request([10, 0, 0, 1]);
+            req.headers_mut()
+                .insert("x-forwarded-for", chain.parse().unwrap());
+            req
+        };
+
+        let res = app.clone().oneshot(forwarded("1.1.1.1")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // the client can't evade the limit by prepending addresses
+        let res = app
+            .clone()
+            .oneshot(forwarded("9.9.9.9, 1.1.1.1, 10.0.0.2"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // same proxy, different client
+        let res = app.clone().oneshot(forwarded("2.2.2.2")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // the headers of untrusted peers are ignored
+        let mut req = request([1, 1, 1, 1]);
+        req.headers_mut()
+            .insert("x-forwarded-for", "3.3.3.3".parse().unwrap());
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[test]
+    fn evicts_least_recently_seen() {
+        let config = Config {
+            rate: 1.0,
+            burst: 1.0,
+            trusted_proxies: TrustedProxies::new(),
+        };
+        let mut buckets = Buckets::new(2);
+        let ip = |n| IpAddr::from([10, 0, 0, n]);
+
+        buckets.acquire(ip(1), &config).unwrap();
+        buckets.acquire(ip(2), &config).unwrap();
+        buckets.acquire(ip(1), &config).unwrap_err();
+        buckets.acquire(ip(3), &config).unwrap();
+
+        assert_eq!(buckets.buckets.len(), 2);
+        assert_eq!(buckets.order.len(), 2);
+        assert!(!buckets.buckets.contains_key(&ip(2)));
+    }
+}
@@ -0,0 +1,335 @@
+use axum::{
+    body::Body,
+    extract::{FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use http::{HeaderMap, Method, Uri, Version};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tower::ServiceExt;
+use tower_layer::Layer;
+use tower_service::Service;
+
+type Sink = Arc<dyn Fn(RecordedRequest) + Send + Sync>;
+
+/// A request captured by [`Record`].
+///
+/// Use [`replay`] to send it through a service again.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RecordedRequest {
+    /// The method of the request.
+    pub method: Method,
+    /// The URI of the request.
+    pub uri: Uri,
+    /// The HTTP version of the request.
+    pub version: Version,
+    /// The headers of the request.
+    pub headers: HeaderMap,
+    /// The buffered body of the request.
+    pub body: Bytes,
+}
+
+impl RecordedRequest {
+    /// Create a new `RecordedRequest`, for example from a request that was stored elsewhere.
+    pub fn new(method: Method, uri: Uri, headers: HeaderMap, body: Bytes) -> Self {
+        Self {
+            method,
+            uri,
+            version: Version::default(),
+            headers,
+            body,
+        }
+    }
+
+    /// Convert the recorded request into a [`Request`].
+    ///
+    /// Extensions aren't recorded, so the request doesn't have any.
+    pub fn to_request(&self) -> Request {
+        let mut req = Request::new(Body::from(self.body.clone()));
+        *req.method_mut() = self.method.clone();
+        *req.uri_mut() = self.uri.clone();
+        *req.version_mut() = self.version;
+        *req.headers_mut() = self.headers.clone();
+        req
+    }
+}
+
+/// Send a recorded request through `service`.
+///
+/// This is meant for reproducing issues in tests.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::post, Router};
+/// use axum_extra::middleware::{replay, RecordedRequest};
+///
+/// # async fn load_recorded_request() -> RecordedRequest { unimplemented!() }
+/// # async {
+/// let app = Router::new().route("/", post(|body: String| async move { body }));
+///
+/// let recorded = load_recorded_request().await;
+/// let res = replay(app, &recorded).await.unwrap();
+/// # };
+/// ```
+pub async fn replay<S>(service: S, recorded: &RecordedRequest) -> Result<S::Response, S::Error>
+where
+    S: Service<Request>,
+{
+    service.oneshot(recorded.to_request()).await
+}
+
+/// Layer that applies the [`Record`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::{RecordLayer, RecordedRequest};
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     // record one in every 100 requests
+///     .layer(
+///         RecordLayer::new(|req: RecordedRequest| {
+///             eprintln!("{} {} {:?}", req.method, req.uri, req.body);
+///         })
+///         .sample_one_in(100),
+///     );
+/// # let _: Router = app;
+/// ```
+#[derive(Clone)]
+pub struct RecordLayer {
+    sink: Sink,
+    sample_one_in: u64,
+    counter: Arc<AtomicU64>,
+}
+
+impl RecordLayer {
+    /// Create a new `RecordLayer` that passes each recorded request to `sink`.
+    ///
+    /// By default every request is recorded.
+    pub fn new<F>(sink: F) -> Self
+    where
+        F: Fn(RecordedRequest) + Send + Sync + 'static,
+    {
+        Self {
+            sink: Arc::new(sink),
+            sample_one_in: 1,
+            counter: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Only record one in every `n` requests.
+    ///
+    /// Requests that aren't sampled are passed through without buffering their body.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn sample_one_in(mut self, n: u64) -> Self {
+        assert!(n > 0, "`n` must not be zero");
+        self.sample_one_in = n;
+        self
+    }
+}
+
+impl fmt::Debug for RecordLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordLayer")
+            .field("sample_one_in", &self.sample_one_in)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for RecordLayer {
+    type Service = Record<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Record {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Middleware that records requests for debugging.
+///
+/// Sampled requests have their body buffered into [`Bytes`] and are then passed, together with
+/// their method, URI, version and headers, to a sink provided by the user, for example to write
+/// them to a file. The request is then passed on to the inner service unchanged. Recorded
+/// requests can be sent through a service again with [`replay`] to reproduce an issue. The
+/// [default body limit] applies while buffering.
+///
+/// Requests are sampled deterministically: with [`RecordLayer::sample_one_in`] set to `n`, the
+/// first request and every `n`th request after it are recorded. Services created by the same
+/// layer share one counter.
+///
+/// Note that recorded requests include all headers, which may contain credentials such as
+/// cookies or `Authorization` headers, so the sink should handle them accordingly.
+///
+/// Created with [`RecordLayer`].
+///
+/// [default body limit]: axum::extract::DefaultBodyLimit
+#[derive(Clone)]
+pub struct Record<S> {
+    inner: S,
+    layer: RecordLayer,
+}
+
+impl<S> fmt::Debug for Record<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Record")
+            .field("inner", &self.inner)
+            .field("sample_one_in", &self.layer.sample_one_in)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Service<Request> for Record<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        let n = self.layer.counter.fetch_add(1, Ordering::Relaxed);
+        if n % self.layer.sample_one_in != 0 {
+            return Box::pin(inner.call(req));
+        }
+
+        let sink = Arc::clone(&self.layer.sink);
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body =
+                match Bytes::from_request(Request::from_parts(parts.clone(), body), &()).await {
+                    Ok(bytes) => bytes,
+                    Err(rejection) => return Ok(rejection.into_response()),
+                };
+
+            sink(RecordedRequest {
+                method: parts.method.clone(),
+                uri: parts.uri.clone(),
+                version: parts.version,
+                headers: parts.headers.clone(),
+                body: body.clone(),
+            });
+
+            inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Router};
+    use http::StatusCode;
+    use http_body_util::BodyExt;
+    use std::sync::Mutex;
+
+    async fn text(res: Response) -> String {
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let app = Router::new().route(
+            "/users/:id",
+            post(|headers: HeaderMap, body: String| async move {
+                let name = headers["x-name"].to_str().unwrap().to_owned();
+                format!("{name}: {body}")
+            }),
+        );
+
+        let recording_app = app.clone().layer(RecordLayer::new({
+            let recorded = Arc::clone(&recorded);
+            move |req| recorded.lock().unwrap().push(req)
+        }));
+
+        let req = Request::post("/users/1")
+            .header("x-name", "alice")
+            .body(Body::from("hello"))
+            .unwrap();
+        let res = recording_app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let original = text(res).await;
+        assert_eq!(original, "alice: hello");
+
+        let recorded = recorded.lock().unwrap().pop().unwrap();
+        assert_eq!(recorded.method, Method::POST);
+        assert_eq!(recorded.uri, "/users/1");
+        assert_eq!(recorded.body, "hello");
+
+        let res = replay(app, &recorded).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(text(res).await, original);
+    }
+
+    #[tokio::test]
+    async fn sampling() {
+        let count = Arc::new(AtomicU64::new(0));
+        let app = Router::new().route("/", post(|| async {})).layer(
+            RecordLayer::new({
+                let count = Arc::clone(&count);
+                move |_| {
+                    count.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+            .sample_one_in(3),
+        );
+
+        for _ in 0..7 {
+            let req = Request::post("/").body(Body::empty()).unwrap();
+            app.clone().oneshot(req).await.unwrap();
+        }
+
+        // requests 1, 4 and 7
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn body_limit() {
+        let count = Arc::new(AtomicU64::new(0));
+        let app = Router::new()
+            .route("/", post(|| async {}))
+            .layer(RecordLayer::new({
+                let count = Arc::clone(&count);
+                move |_| {
+                    count.fetch_add(1, Ordering::SeqCst);
+                }
+            }))
+            .layer(axum::extract::DefaultBodyLimit::max(4));
+
+        let req = Request::post("/").body(Body::from("too long")).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+}
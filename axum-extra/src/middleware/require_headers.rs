@@ -0,0 +1,210 @@
+use axum::response::{IntoResponse, Response};
+use futures_util::future::{ready, Either, Ready};
+use http::{HeaderMap, HeaderName, HeaderValue, Request, StatusCode};
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+type Predicate = Arc<dyn Fn(&HeaderValue) -> bool + Send + Sync>;
+
+/// Layer that applies the [`RequireHeaders`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::RequireHeadersLayer;
+/// use http::HeaderName;
+///
+/// let layer = RequireHeadersLayer::new([HeaderName::from_static("x-tenant-id")])
+///     .header_matching(HeaderName::from_static("x-api-version"), |value| {
+///         value == "1" || value == "2"
+///     });
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     .layer(layer);
+/// # let _: Router = app;
+/// ```
+#[derive(Clone)]
+pub struct RequireHeadersLayer {
+    required: Arc<Vec<(HeaderName, Option<Predicate>)>>,
+}
+
+impl RequireHeadersLayer {
+    /// Create a new `RequireHeadersLayer` that requires the given headers to be present.
+    pub fn new<I>(names: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        let required = names.into_iter().map(|name| (name, None)).collect();
+        Self {
+            required: Arc::new(required),
+        }
+    }
+
+    /// Also require the header `name` to be present.
+    pub fn header(self, name: HeaderName) -> Self {
+        self.push(name, None)
+    }
+
+    /// Also require the header `name` to be present and its value to match `predicate`.
+    ///
+    /// If the header appears multiple times, every value must match.
+    pub fn header_matching<F>(self, name: HeaderName, predicate: F) -> Self
+    where
+        F: Fn(&HeaderValue) -> bool + Send + Sync + 'static,
+    {
+        self.push(name, Some(Arc::new(predicate)))
+    }
+
+    fn push(mut self, name: HeaderName, predicate: Option<Predicate>) -> Self {
+        Arc::make_mut(&mut self.required).push((name, predicate));
+        self
+    }
+}
+
+impl fmt::Debug for RequireHeadersLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequireHeadersLayer")
+            .field("required", &names(&self.required))
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for RequireHeadersLayer {
+    type Service = RequireHeaders<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireHeaders {
+            inner,
+            required: Arc::clone(&self.required),
+        }
+    }
+}
+
+/// Middleware that rejects requests missing required headers.
+///
+/// If a required header is missing, or its value doesn't match the predicate given with
+/// [`RequireHeadersLayer::header_matching`], the request is rejected with `400 Bad Request`
+/// without calling the inner service.
+///
+/// Created with [`RequireHeadersLayer`].
+#[derive(Clone)]
+pub struct RequireHeaders<S> {
+    inner: S,
+    required: Arc<Vec<(HeaderName, Option<Predicate>)>>,
+}
+
+impl<S> fmt::Debug for RequireHeaders<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequireHeaders")
+            .field("inner", &self.inner)
+            .field("required", &names(&self.required))
+            .finish()
+    }
+}
+
+impl<S, B> Service<Request<B>> for RequireHeaders<S>
+where
+    S: Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        for (name, predicate) in self.required.iter() {
+            if let Err(body) = check_header(req.headers(), name, predicate.as_ref()) {
+                return Either::Left(ready(Ok((StatusCode::BAD_REQUEST, body).into_response())));
+            }
+        }
+
+        Either::Right(self.inner.call(req))
+    }
+}
+
+fn check_header(
+    headers: &HeaderMap,
+    name: &HeaderName,
+    predicate: Option<&Predicate>,
+) -> Result<(), String> {
+    let mut values = headers.get_all(name).iter().peekable();
+    if values.peek().is_none() {
+        return Err(format!("Missing required header `{name}`"));
+    }
+
+    if let Some(predicate) = predicate {
+        if !values.all(|value| predicate(value)) {
+            return Err(format!("Invalid value for header `{name}`"));
+        }
+    }
+
+    Ok(())
+}
+
+fn names(required: &[(HeaderName, Option<Predicate>)]) -> Vec<&HeaderName> {
+    required.iter().map(|(name, _)| name).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    #[tokio::test]
+    async fn present() {
+        let app = Router::new().route("/", get(|| async { "ok" })).layer(
+            RequireHeadersLayer::new([HeaderName::from_static("x-tenant-id")])
+                .header_matching(HeaderName::from_static("x-api-version"), |value| {
+                    value == "2"
+                }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .get("/")
+            .header("x-tenant-id", "acme")
+            .header("x-api-version", "2")
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "ok");
+    }
+
+    #[tokio::test]
+    async fn absent() {
+        let app = Router::new().route("/", get(|| async { "ok" })).layer(
+            RequireHeadersLayer::new([HeaderName::from_static("x-tenant-id")])
+                .header_matching(HeaderName::from_static("x-api-version"), |value| {
+                    value == "2"
+                }),
+        );
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/").header("x-api-version", "2").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "Missing required header `x-tenant-id`");
+
+        let res = client
+            .get("/")
+            .header("x-tenant-id", "acme")
+            .header("x-api-version", "1")
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "Invalid value for header `x-api-version`");
+    }
+}
@@ -0,0 +1,226 @@
+use axum::response::{IntoResponse, Response};
+use futures_util::future::{ready, Either, Ready};
+use http::{header, HeaderValue, Request, StatusCode};
+use std::{
+    fmt,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A version of the TLS protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum TlsVersion {
+    /// TLS 1.0.
+    Tls1_0,
+    /// TLS 1.1.
+    Tls1_1,
+    /// TLS 1.2.
+    Tls1_2,
+    /// TLS 1.3.
+    Tls1_3,
+}
+
+impl TlsVersion {
+    fn as_upgrade_token(self) -> &'static str {
+        match self {
+            Self::Tls1_0 => "TLS/1.0",
+            Self::Tls1_1 => "TLS/1.1",
+            Self::Tls1_2 => "TLS/1.2",
+            Self::Tls1_3 => "TLS/1.3",
+        }
+    }
+}
+
+impl fmt::Display for TlsVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tls1_0 => f.write_str("TLS 1.0"),
+            Self::Tls1_1 => f.write_str("TLS 1.1"),
+            Self::Tls1_2 => f.write_str("TLS 1.2"),
+            Self::Tls1_3 => f.write_str("TLS 1.3"),
+        }
+    }
+}
+
+/// Request extension describing the TLS session a request was received on.
+///
+/// This is meant to be inserted by the TLS layer, for example from
+/// [`rustls::ServerConnection::protocol_version`] and
+/// [`rustls::ServerConnection::negotiated_cipher_suite`], similarly to how [`ConnectInfo`] is
+/// provided by the server.
+///
+/// [`rustls::ServerConnection::protocol_version`]: https://docs.rs/rustls/latest/rustls/server/struct.ServerConnection.html#method.protocol_version
+/// [`rustls::ServerConnection::negotiated_cipher_suite`]: https://docs.rs/rustls/latest/rustls/server/struct.ServerConnection.html#method.negotiated_cipher_suite
+/// [`ConnectInfo`]: axum::extract::ConnectInfo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsInfo {
+    /// The negotiated protocol version.
+    pub version: TlsVersion,
+    /// The name of the negotiated cipher suite, if known.
+    pub cipher_suite: Option<String>,
+}
+
+/// Layer that applies the [`RequireSecure`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::{RequireSecureLayer, TlsVersion};
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     .layer(RequireSecureLayer::new(TlsVersion::Tls1_2));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RequireSecureLayer {
+    min_version: TlsVersion,
+    allow_missing: bool,
+}
+
+impl RequireSecureLayer {
+    /// Create a new `RequireSecureLayer` that requires at least `min_version`.
+    pub fn new(min_version: TlsVersion) -> Self {
+        Self {
+            min_version,
+            allow_missing: false,
+        }
+    }
+
+    /// Set whether requests without a [`TlsInfo`] extension are let through.
+    ///
+    /// Defaults to `false`, meaning such requests are rejected.
+    pub fn allow_missing(mut self, allow_missing: bool) -> Self {
+        self.allow_missing = allow_missing;
+        self
+    }
+}
+
+impl<S> Layer<S> for RequireSecureLayer {
+    type Service = RequireSecure<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireSecure {
+            inner,
+            layer: *self,
+        }
+    }
+}
+
+/// Middleware that rejects requests made over an outdated version of TLS.
+///
+/// The TLS version is read from the [`TlsInfo`] request extension, which must be inserted by the
+/// TLS layer. Requests made with a version below the minimum are rejected with
+/// `426 Upgrade Required` without calling the inner service, and the `Upgrade` header is set to
+/// the minimum version.
+///
+/// Requests without a [`TlsInfo`] extension, for example because they weren't made over TLS,
+/// are also rejected unless [`RequireSecureLayer::allow_missing`] is set.
+///
+/// Created with [`RequireSecureLayer`].
+#[derive(Debug, Clone, Copy)]
+pub struct RequireSecure<S> {
+    inner: S,
+    layer: RequireSecureLayer,
+}
+
+impl<S, B> Service<Request<B>> for RequireSecure<S>
+where
+    S: Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let RequireSecureLayer {
+            min_version,
+            allow_missing,
+        } = self.layer;
+
+        let allowed = match req.extensions().get::<TlsInfo>() {
+            Some(info) => info.version >= min_version,
+            None => allow_missing,
+        };
+        if allowed {
+            return Either::Right(self.inner.call(req));
+        }
+
+        let res = (
+            StatusCode::UPGRADE_REQUIRED,
+            [
+                (
+                    header::UPGRADE,
+                    HeaderValue::from_static(min_version.as_upgrade_token()),
+                ),
+                (header::CONNECTION, HeaderValue::from_static("Upgrade")),
+            ],
+            format!("{min_version} or later is required"),
+        );
+        Either::Left(ready(Ok(res.into_response())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn request(version: Option<TlsVersion>) -> Request<Body> {
+        let mut req = Request::new(Body::empty());
+        if let Some(version) = version {
+            req.extensions_mut().insert(TlsInfo {
+                version,
+                cipher_suite: None,
+            });
+        }
+        req
+    }
+
+    #[tokio::test]
+    async fn rejects_old_versions() {
+        let app = Router::new()
+            .route("/", get(|| async {}))
+            .layer(RequireSecureLayer::new(TlsVersion::Tls1_2));
+
+        let req = request(Some(TlsVersion::Tls1_1));
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::UPGRADE_REQUIRED);
+        assert_eq!(res.headers()[header::UPGRADE], "TLS/1.2");
+    }
+
+    #[tokio::test]
+    async fn accepts_new_versions() {
+        let app = Router::new()
+            .route("/", get(|| async {}))
+            .layer(RequireSecureLayer::new(TlsVersion::Tls1_2));
+
+        for version in [TlsVersion::Tls1_2, TlsVersion::Tls1_3] {
+            let res = app.clone().oneshot(request(Some(version))).await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_info() {
+        let app = Router::new()
+            .route("/", get(|| async {}))
+            .layer(RequireSecureLayer::new(TlsVersion::Tls1_2));
+        let res = app.oneshot(request(None)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::UPGRADE_REQUIRED);
+
+        let app = Router::new()
+            .route("/", get(|| async {}))
+            .layer(RequireSecureLayer::new(TlsVersion::Tls1_2).allow_missing(true));
+        let res = app.oneshot(request(None)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
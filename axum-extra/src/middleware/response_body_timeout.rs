@@ -0,0 +1,198 @@
+use axum::{body::Body, extract::Request, response::Response, BoxError};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use http_body::{Body as HttpBody, Frame, SizeHint};
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::Sleep;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`ResponseBodyTimeout`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::ResponseBodyTimeoutLayer;
+/// use std::time::Duration;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* a streaming response */ }))
+///     // abort response bodies that don't produce any data for 30 seconds
+///     .layer(ResponseBodyTimeoutLayer::new(Duration::from_secs(30)));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseBodyTimeoutLayer {
+    timeout: Duration,
+}
+
+impl ResponseBodyTimeoutLayer {
+    /// Create a new `ResponseBodyTimeoutLayer` that aborts response bodies that don't make
+    /// progress for `timeout`.
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<S> Layer<S> for ResponseBodyTimeoutLayer {
+    type Service = ResponseBodyTimeout<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseBodyTimeout {
+            inner,
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// Middleware that aborts response bodies that stop making progress.
+///
+/// Timeouts such as [`tower_http::timeout::TimeoutLayer`] only cover the time until the handler
+/// returns a response. A streaming response body can still get stuck afterwards, for example if
+/// the stream is waiting for a source that stopped responding, tying up the connection forever.
+///
+/// This middleware wraps the response body and fails it with an error of kind
+/// [`io::ErrorKind::TimedOut`] if no frame is produced within the timeout. The timeout restarts
+/// after every frame, so long bodies that keep making progress are never aborted. Since the
+/// status and headers have already been sent at that point, the client sees the connection being
+/// closed before the body is complete.
+///
+/// Only time spent waiting for the body counts. If the client reads the body slowly that doesn't
+/// trigger the timeout.
+///
+/// Created with [`ResponseBodyTimeoutLayer`].
+///
+/// [`tower_http::timeout::TimeoutLayer`]: https://docs.rs/tower-http/latest/tower_http/timeout/struct.TimeoutLayer.html
+#[derive(Debug, Clone)]
+pub struct ResponseBodyTimeout<S> {
+    inner: S,
+    timeout: Duration,
+}
+
+impl<S> Service<Request> for ResponseBodyTimeout<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let future = self.inner.call(req);
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            let res = future.await?;
+            Ok(res.map(|body| {
+                Body::new(TimeoutBody {
+                    inner: body,
+                    timeout,
+                    sleep: None,
+                })
+            }))
+        })
+    }
+}
+
+struct TimeoutBody {
+    inner: Body,
+    timeout: Duration,
+    // started when the inner body first returns `Pending` after a frame
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl HttpBody for TimeoutBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+
+        if let Poll::Ready(frame) = Pin::new(&mut this.inner).poll_frame(cx) {
+            this.sleep = None;
+            return Poll::Ready(frame.map(|frame| frame.map_err(Into::into)));
+        }
+
+        let timeout = this.timeout;
+        let sleep = this
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep(timeout)));
+        if sleep.as_mut().poll(cx).is_ready() {
+            let err = io::Error::new(io::ErrorKind::TimedOut, "response body timed out");
+            return Poll::Ready(Some(Err(err.into())));
+        }
+
+        Poll::Pending
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use futures_util::stream::{self, StreamExt};
+    use http_body_util::BodyExt;
+    use std::convert::Infallible;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn stalled_body_fails() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    let stream =
+                        stream::iter([Ok::<_, Infallible>("hello")]).chain(stream::pending());
+                    Body::from_stream(stream)
+                }),
+            )
+            .layer(ResponseBodyTimeoutLayer::new(Duration::from_millis(50)));
+
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        let mut body = res.into_body();
+
+        let frame = body.frame().await.unwrap().unwrap();
+        assert_eq!(frame.into_data().unwrap(), "hello");
+
+        let err = body.frame().await.unwrap().unwrap_err();
+        let err = err.into_inner().downcast::<io::Error>().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[tokio::test]
+    async fn complete_body_passes() {
+        let app = Router::new()
+            .route("/", get(|| async { "hello" }))
+            .layer(ResponseBodyTimeoutLayer::new(Duration::from_millis(50)));
+
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "hello");
+    }
+}
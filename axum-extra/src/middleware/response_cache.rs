@@ -0,0 +1,262 @@
+use super::{buffered_response::BufferedResponse, expiring_map::ExpiringMap};
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+use http::{header, HeaderMap, Method, StatusCode, Uri};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`ResponseCache`] middleware.
+///
+/// All services created by the same layer, and clones of them, share one cache.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::ResponseCacheLayer;
+/// use std::time::Duration;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* something expensive */ }))
+///     // cache up to 1000 responses for 30 seconds each
+///     .layer(ResponseCacheLayer::new(Duration::from_secs(30), 1000));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone)]
+pub struct ResponseCacheLayer {
+    cache: Arc<Mutex<Cache>>,
+}
+
+impl ResponseCacheLayer {
+    /// Create a new `ResponseCacheLayer` that caches up to `max_entries` responses for `ttl`.
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            cache: Arc::new(Mutex::new(ExpiringMap::new(ttl, max_entries))),
+        }
+    }
+}
+
+impl fmt::Debug for ResponseCacheLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCacheLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for ResponseCacheLayer {
+    type Service = ResponseCache<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseCache {
+            inner,
+            cache: Arc::clone(&self.cache),
+        }
+    }
+}
+
+/// Middleware that caches successful responses to `GET` requests.
+///
+/// Responses are cached by the method and URI of the request for a fixed time to live. While a
+/// response is cached, requests with the same URI are served from the cache without calling the
+/// inner service. Only `2xx` responses are cached. Responses with a `Set-Cookie` header or a
+/// `Cache-Control` header containing `no-store` or `private` are never cached, since they're
+/// meant for a single client.
+///
+/// To cache a response its body is buffered into memory. Response extensions aren't cached.
+/// The number of cached responses is bounded. When the cache is full, expired responses are
+/// removed first and then the response closest to expiring.
+///
+/// Note that the cache key doesn't include any request headers, so responses that depend on
+/// headers such as `Authorization` or `Accept` must not be cached with this middleware.
+///
+/// Created with [`ResponseCacheLayer`].
+pub struct ResponseCache<S> {
+    inner: S,
+    cache: Arc<Mutex<Cache>>,
+}
+
+impl<S> ResponseCache<S> {
+    /// Create a new `ResponseCache` middleware that caches up to `max_entries` responses for
+    /// `ttl`.
+    ///
+    /// Use [`ResponseCacheLayer`] to share the cache between several services.
+    pub fn new(inner: S, ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(Mutex::new(ExpiringMap::new(ttl, max_entries))),
+        }
+    }
+}
+
+impl<S> Clone for ResponseCache<S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            cache: Arc::clone(&self.cache),
+        }
+    }
+}
+
+impl<S> fmt::Debug for ResponseCache<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Service<Request> for ResponseCache<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        if req.method() != Method::GET {
+            return Box::pin(inner.call(req));
+        }
+
+        let key = (req.method().clone(), req.uri().clone());
+        if let Some(cached) = self.cache.lock().unwrap().get(&key).cloned() {
+            return Box::pin(async move { Ok(cached.to_response()) });
+        }
+
+        let cache = Arc::clone(&self.cache);
+        Box::pin(async move {
+            let res = inner.call(req).await?;
+
+            if !res.status().is_success() || !is_cacheable(res.headers()) {
+                return Ok(res);
+            }
+
+            let buffered = match BufferedResponse::from_response(res).await {
+                Ok(buffered) => Arc::new(buffered),
+                Err(err) => {
+                    return Ok((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        format!("Failed to buffer the response body: {err}"),
+                    )
+                        .into_response());
+                }
+            };
+
+            cache.lock().unwrap().insert(key, Arc::clone(&buffered));
+
+            Ok(buffered.to_response())
+        })
+    }
+}
+
+fn is_cacheable(headers: &HeaderMap) -> bool {
+    if headers.contains_key(header::SET_COOKIE) {
+        return false;
+    }
+
+    !headers
+        .get_all(header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|directive| directive.split('=').next().unwrap_or_default().trim())
+        .any(|name| name.eq_ignore_ascii_case("no-store") || name.eq_ignore_ascii_case("private"))
+}
+
+type CacheKey = (Method, Uri);
+
+type Cache = ExpiringMap<CacheKey, Arc<BufferedResponse>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn hit_and_miss() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route(
+                "/",
+                get({
+                    let calls = Arc::clone(&calls);
+                    move || async move { calls.fetch_add(1, Ordering::SeqCst).to_string() }
+                }),
+            )
+            .layer(ResponseCacheLayer::new(Duration::from_secs(60), 10));
+
+        let client = TestClient::new(app);
+
+        // miss
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "0");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // hit, the inner service isn't called
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "0");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // a different URI is a miss
+        let res = client.get("/?page=2").await;
+        assert_eq!(res.text().await, "1");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn uncacheable_responses() {
+        for (name, value) in [
+            (header::CACHE_CONTROL, "private, no-store"),
+            (header::CACHE_CONTROL, "private=\"x-user\""),
+            (header::SET_COOKIE, "session=abc"),
+        ] {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let app = Router::new()
+                .route(
+                    "/",
+                    get({
+                        let calls = Arc::clone(&calls);
+                        move || async move {
+                            let n = calls.fetch_add(1, Ordering::SeqCst);
+                            ([(name, value)], n.to_string())
+                        }
+                    }),
+                )
+                .layer(ResponseCacheLayer::new(Duration::from_secs(60), 10));
+
+            let client = TestClient::new(app);
+
+            let res = client.get("/").await;
+            assert_eq!(res.text().await, "0", "{value}");
+
+            let res = client.get("/").await;
+            assert_eq!(res.text().await, "1", "{value}");
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+        }
+    }
+}
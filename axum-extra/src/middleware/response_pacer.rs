@@ -0,0 +1,257 @@
+use axum::{body::Body, extract::Request, response::Response};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use http_body::{Body as HttpBody, Frame, SizeHint};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`ResponsePacer`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::ResponsePacerLayer;
+///
+/// let app = Router::new()
+///     .route("/download", get(|| async { /* a large response */ }))
+///     // send at most 1 MiB per second, in bursts of up to 64 KiB
+///     .layer(ResponsePacerLayer::new(1024 * 1024).burst(64 * 1024));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ResponsePacerLayer {
+    bytes_per_second: u64,
+    burst: u64,
+}
+
+impl ResponsePacerLayer {
+    /// Create a new `ResponsePacerLayer` that limits response bodies to `bytes_per_second`.
+    ///
+    /// By default the burst size is one second worth of bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes_per_second` is zero.
+    pub fn new(bytes_per_second: u64) -> Self {
+        assert!(bytes_per_second > 0, "`bytes_per_second` must not be zero");
+        Self {
+            bytes_per_second,
+            burst: bytes_per_second,
+        }
+    }
+
+    /// Set the maximum number of bytes that can be sent at once after the body has been idle.
+    ///
+    /// This is also the largest chunk of data the body yields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    pub fn burst(mut self, burst: u64) -> Self {
+        assert!(burst > 0, "`burst` must not be zero");
+        self.burst = burst;
+        self
+    }
+}
+
+impl<S> Layer<S> for ResponsePacerLayer {
+    type Service = ResponsePacer<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponsePacer {
+            inner,
+            layer: *self,
+        }
+    }
+}
+
+/// Middleware that limits the rate at which response bodies are sent.
+///
+/// Each response body gets its own [token bucket] that's filled at the configured number of bytes
+/// per second, up to the burst size. Data from the inner body is only yielded once enough tokens
+/// are available, so a handler producing data faster than the rate is paced rather than filling
+/// up buffers. This evens out bursts and keeps a fast handler from tying up resources on behalf of
+/// clients that can't keep up anyway.
+///
+/// Trailers are passed through unchanged.
+///
+/// Created with [`ResponsePacerLayer`].
+///
+/// [token bucket]: https://en.wikipedia.org/wiki/Token_bucket
+#[derive(Clone)]
+pub struct ResponsePacer<S> {
+    inner: S,
+    layer: ResponsePacerLayer,
+}
+
+impl<S> fmt::Debug for ResponsePacer<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponsePacer")
+            .field("inner", &self.inner)
+            .field("bytes_per_second", &self.layer.bytes_per_second)
+            .field("burst", &self.layer.burst)
+            .finish()
+    }
+}
+
+impl<S> Service<Request> for ResponsePacer<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let future = self.inner.call(req);
+        let layer = self.layer;
+
+        Box::pin(async move {
+            let res = future.await?;
+            Ok(res.map(|body| Body::new(PacedBody::new(body, layer))))
+        })
+    }
+}
+
+struct PacedBody {
+    inner: Body,
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+    // data received from the inner body that hasn't been sent yet
+    pending: Option<Bytes>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl PacedBody {
+    fn new(inner: Body, layer: ResponsePacerLayer) -> Self {
+        let capacity = layer.burst as f64;
+        Self {
+            inner,
+            rate: layer.bytes_per_second as f64,
+            capacity,
+            tokens: capacity,
+            last_refill: Instant::now(),
+            pending: None,
+            sleep: None,
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+    }
+}
+
+impl HttpBody for PacedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(sleep) = &mut this.sleep {
+                ready!(sleep.as_mut().poll(cx));
+                this.sleep = None;
+            }
+
+            let Some(mut data) = this.pending.take() else {
+                match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+                    Some(Ok(frame)) => match frame.into_data() {
+                        Ok(data) if data.is_empty() => continue,
+                        Ok(data) => {
+                            this.pending = Some(data);
+                            continue;
+                        }
+                        Err(frame) => return Poll::Ready(Some(Ok(frame))),
+                    },
+                    other => return Poll::Ready(other),
+                }
+            };
+
+            this.refill();
+            let chunk_len = data.len().min(this.capacity as usize);
+            let missing = chunk_len as f64 - this.tokens;
+            if missing > 0.0 {
+                let wait = Duration::from_secs_f64(missing / this.rate);
+                this.sleep = Some(Box::pin(tokio::time::sleep(wait)));
+                this.pending = Some(data);
+                continue;
+            }
+
+            this.tokens -= chunk_len as f64;
+            let chunk = data.split_to(chunk_len);
+            if !data.is_empty() {
+                this.pending = Some(data);
+            }
+            return Poll::Ready(Some(Ok(Frame::data(chunk))));
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.pending.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let mut hint = self.inner.size_hint();
+        if let Some(pending) = &self.pending {
+            let pending = pending.len() as u64;
+            // raise the upper bound first since `set_lower` panics if it's above the upper bound
+            if let Some(upper) = hint.upper() {
+                hint.set_upper(upper + pending);
+            }
+            hint.set_lower(hint.lower() + pending);
+        }
+        hint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+    use http_body_util::BodyExt;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+
+    #[tokio::test]
+    async fn limits_the_rate() {
+        let svc = service_fn(|_: Request| async {
+            let body = vec![b'a'; 60];
+            Ok::<_, Infallible>(body.into_response())
+        });
+        let svc = ResponsePacerLayer::new(100).burst(10).layer(svc);
+
+        let start = std::time::Instant::now();
+        let res = svc.oneshot(Request::new(Body::empty())).await.unwrap();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+
+        assert_eq!(body.len(), 60);
+        // the first 10 bytes are sent immediately and the remaining 50 at 100 bytes per second
+        assert!(start.elapsed() >= Duration::from_millis(450));
+    }
+}
@@ -0,0 +1,257 @@
+use axum::{
+    body::Body,
+    extract::{FromRequest, IdempotentMethod, Request},
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use http::StatusCode;
+use std::{
+    fmt,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::ServiceExt;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Policy used by [`Retry`] to decide whether, and how often, to retry a request.
+///
+/// By default requests are retried up to three times if the response has the status
+/// `502 Bad Gateway`, `503 Service Unavailable`, or `504 Gateway Timeout`. The delay between
+/// attempts starts at 100 milliseconds and doubles after each attempt.
+#[derive(Clone, Copy)]
+pub struct RetryPolicy {
+    max_retries: usize,
+    backoff: Duration,
+    retry_on: fn(StatusCode) -> bool,
+}
+
+impl RetryPolicy {
+    /// Create a new `RetryPolicy` that retries at most `max_retries` times.
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// Set the delay before the first retry. The delay doubles after each retry.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the function used to decide if a response with the given status should be retried.
+    pub fn retry_on(mut self, retry_on: fn(StatusCode) -> bool) -> Self {
+        self.retry_on = retry_on;
+        self
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: Duration::from_millis(100),
+            retry_on: |status| {
+                matches!(
+                    status,
+                    StatusCode::BAD_GATEWAY
+                        | StatusCode::SERVICE_UNAVAILABLE
+                        | StatusCode::GATEWAY_TIMEOUT
+                )
+            },
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("backoff", &self.backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Layer that applies the [`Retry`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::{RetryLayer, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* call some flaky upstream */ }))
+///     .layer(RetryLayer::new(
+///         RetryPolicy::new(2).backoff(Duration::from_millis(50)),
+///     ));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RetryLayer {
+    policy: RetryPolicy,
+}
+
+impl RetryLayer {
+    /// Create a new `RetryLayer` with the given policy.
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S> Layer<S> for RetryLayer {
+    type Service = Retry<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Retry::new(inner, self.policy)
+    }
+}
+
+/// Middleware that retries idempotent requests based on the response status.
+///
+/// Only requests with an idempotent method (`GET`, `HEAD`, `OPTIONS`, `TRACE`, `PUT`, and
+/// `DELETE`) are retried. Their body is buffered into [`Bytes`] before calling the inner service,
+/// so it can be replayed. The [default body limit] applies while buffering.
+///
+/// See [`RetryPolicy`] for details about which responses are retried.
+///
+/// [default body limit]: axum::extract::DefaultBodyLimit
+#[derive(Debug, Clone)]
+pub struct Retry<S> {
+    inner: S,
+    policy: RetryPolicy,
+}
+
+impl<S> Retry<S> {
+    /// Create a new `Retry` middleware.
+    pub fn new(inner: S, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+}
+
+impl<S> Service<Request> for Retry<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        if !IdempotentMethod::is_idempotent(req.method()) {
+            let future = self.inner.call(req);
+            return Box::pin(future);
+        }
+
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+        let policy = self.policy;
+
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let bytes =
+                match Bytes::from_request(Request::from_parts(parts.clone(), body), &()).await {
+                    Ok(bytes) => bytes,
+                    Err(rejection) => return Ok(rejection.into_response()),
+                };
+
+            let mut retries = 0;
+            let mut backoff = policy.backoff;
+            loop {
+                let req = Request::from_parts(parts.clone(), Body::from(bytes.clone()));
+                let res = inner.call(req).await?;
+
+                if retries >= policy.max_retries || !(policy.retry_on)(res.status()) {
+                    return Ok(res);
+                }
+
+                retries += 1;
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+                inner.ready().await?;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        convert::Infallible,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+    use tower::{service_fn, util::BoxCloneService};
+
+    fn flaky(
+        failures: usize,
+        calls: Arc<AtomicUsize>,
+    ) -> Retry<BoxCloneService<Request, Response, Infallible>> {
+        let svc = service_fn(move |req: Request| {
+            let calls = Arc::clone(&calls);
+            async move {
+                let body = String::from_request(req, &()).await.unwrap();
+                assert_eq!(body, "foo");
+
+                let status = if calls.fetch_add(1, Ordering::SeqCst) < failures {
+                    StatusCode::SERVICE_UNAVAILABLE
+                } else {
+                    StatusCode::OK
+                };
+                Ok::<_, Infallible>(status.into_response())
+            }
+        });
+
+        Retry::new(
+            BoxCloneService::new(svc),
+            RetryPolicy::new(3).backoff(Duration::from_millis(1)),
+        )
+    }
+
+    #[tokio::test]
+    async fn retries_until_success() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = flaky(2, Arc::clone(&calls));
+
+        let res = svc.oneshot(Request::new(Body::from("foo"))).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = flaky(10, Arc::clone(&calls));
+
+        let res = svc.oneshot(Request::new(Body::from("foo"))).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_non_idempotent_requests() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = flaky(2, Arc::clone(&calls));
+
+        let req = Request::post("/").body(Body::from("foo")).unwrap();
+        let res = svc.oneshot(req).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,178 @@
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+use http::{HeaderValue, StatusCode};
+use std::{
+    ops::RangeInclusive,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+const SCHEMA_VERSION: &str = "x-schema-version";
+
+/// Layer that applies the [`SchemaVersion`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::SchemaVersionLayer;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     // clients must speak schema version 2, 3 or 4
+///     .layer(SchemaVersionLayer::new(2..=4));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SchemaVersionLayer {
+    supported: RangeInclusive<u32>,
+}
+
+impl SchemaVersionLayer {
+    /// Create a new `SchemaVersionLayer` that accepts the schema versions in `supported`.
+    pub fn new(supported: RangeInclusive<u32>) -> Self {
+        Self { supported }
+    }
+}
+
+impl<S> Layer<S> for SchemaVersionLayer {
+    type Service = SchemaVersion<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SchemaVersion::new(inner, self.supported.clone())
+    }
+}
+
+/// Middleware that requires clients to send a supported version of the request schema.
+///
+/// The version is read from the `X-Schema-Version` header, which must contain a number in the
+/// supported range. Requests are rejected without calling the inner service with
+///
+/// - `400 Bad Request` if the header is missing, isn't a number, or the version is older than the
+///   oldest supported one, since the server can no longer understand the request.
+/// - `426 Upgrade Required` if the version is newer than the newest supported one, signaling that
+///   the server has to be upgraded before it can handle the request.
+///
+/// Responses to accepted requests get an `X-Schema-Version` header with the version the request
+/// was handled with, unless the inner service already set one.
+///
+/// Created with [`SchemaVersionLayer`].
+#[derive(Debug, Clone)]
+pub struct SchemaVersion<S> {
+    inner: S,
+    supported: RangeInclusive<u32>,
+}
+
+impl<S> SchemaVersion<S> {
+    /// Create a new `SchemaVersion` middleware that accepts the schema versions in `supported`.
+    pub fn new(inner: S, supported: RangeInclusive<u32>) -> Self {
+        Self { inner, supported }
+    }
+}
+
+impl<S> Service<Request> for SchemaVersion<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let version = req
+            .headers()
+            .get(SCHEMA_VERSION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u32>().ok());
+
+        let (start, end) = (*self.supported.start(), *self.supported.end());
+        let checked = match version {
+            Some(version) if (start..=end).contains(&version) => Ok(version),
+            None => Err((
+                StatusCode::BAD_REQUEST,
+                "Missing or invalid `X-Schema-Version` header".to_owned(),
+            )),
+            Some(version) if version < start => Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Schema version {version} is no longer supported, the oldest supported version \
+                     is {start}"
+                ),
+            )),
+            Some(version) => Err((
+                StatusCode::UPGRADE_REQUIRED,
+                format!(
+                    "Schema version {version} is not supported yet, the newest supported version \
+                     is {end}"
+                ),
+            )),
+        };
+        let version = match checked {
+            Ok(version) => version,
+            Err(rejection) => return Box::pin(async move { Ok(rejection.into_response()) }),
+        };
+
+        let version = HeaderValue::from(version);
+        let future = self.inner.call(req);
+        Box::pin(async move {
+            let mut res = future.await?;
+            res.headers_mut().entry(SCHEMA_VERSION).or_insert(version);
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use tower::ServiceExt;
+
+    async fn send(version: Option<&str>) -> Response {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(SchemaVersionLayer::new(2..=4));
+
+        let mut req = Request::get("/");
+        if let Some(version) = version {
+            req = req.header("x-schema-version", version);
+        }
+        app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn in_range() {
+        for version in ["2", "3", "4"] {
+            let res = send(Some(version)).await;
+            assert_eq!(res.status(), StatusCode::OK);
+            assert_eq!(res.headers()["x-schema-version"], version);
+        }
+    }
+
+    #[tokio::test]
+    async fn too_old() {
+        let res = send(Some("1")).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert!(res.headers().get("x-schema-version").is_none());
+
+        for version in [None, Some("two")] {
+            let res = send(version).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn too_new() {
+        let res = send(Some("5")).await;
+        assert_eq!(res.status(), StatusCode::UPGRADE_REQUIRED);
+    }
+}
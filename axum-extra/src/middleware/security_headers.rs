@@ -0,0 +1,187 @@
+use axum::{extract::Request, response::Response};
+use futures_util::future::BoxFuture;
+use http::{header, HeaderMap, HeaderName, HeaderValue};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`SecurityHeaders`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{
+///     http::{header, HeaderValue},
+///     routing::get,
+///     Router,
+/// };
+/// use axum_extra::middleware::SecurityHeadersLayer;
+///
+/// let security_headers = SecurityHeadersLayer::new()
+///     // allow the app to be framed by pages from the same origin
+///     .header(header::X_FRAME_OPTIONS, HeaderValue::from_static("SAMEORIGIN"))
+///     // the app is also served over plain HTTP
+///     .without(header::STRICT_TRANSPORT_SECURITY);
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     .layer(security_headers);
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SecurityHeadersLayer {
+    headers: HeaderMap,
+}
+
+impl SecurityHeadersLayer {
+    /// Create a new `SecurityHeadersLayer` with the default set of headers.
+    ///
+    /// The defaults are:
+    ///
+    /// - `X-Content-Type-Options: nosniff`
+    /// - `X-Frame-Options: DENY`
+    /// - `Strict-Transport-Security: max-age=63072000; includeSubDomains`
+    /// - `Content-Security-Policy: default-src 'self'`
+    pub fn new() -> Self {
+        Self::empty()
+            .header(
+                header::X_CONTENT_TYPE_OPTIONS,
+                HeaderValue::from_static("nosniff"),
+            )
+            .header(header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY"))
+            .header(
+                header::STRICT_TRANSPORT_SECURITY,
+                HeaderValue::from_static("max-age=63072000; includeSubDomains"),
+            )
+            .header(
+                header::CONTENT_SECURITY_POLICY,
+                HeaderValue::from_static("default-src 'self'"),
+            )
+    }
+
+    /// Create a new `SecurityHeadersLayer` without any headers.
+    pub fn empty() -> Self {
+        Self {
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Add a header to the set, replacing the value if the header is already in the set.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Remove a header from the set.
+    pub fn without(mut self, name: HeaderName) -> Self {
+        self.headers.remove(name);
+        self
+    }
+}
+
+impl Default for SecurityHeadersLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for SecurityHeadersLayer {
+    type Service = SecurityHeaders<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SecurityHeaders {
+            inner,
+            headers: Arc::new(self.headers.clone()),
+        }
+    }
+}
+
+/// Middleware that adds security related headers to responses.
+///
+/// Each header in the set is added to the response unless the response already has a header
+/// with that name, so handlers can override the value for individual responses, for example to
+/// use a different `Content-Security-Policy`.
+///
+/// Created with [`SecurityHeadersLayer`].
+#[derive(Debug, Clone)]
+pub struct SecurityHeaders<S> {
+    inner: S,
+    headers: Arc<HeaderMap>,
+}
+
+impl<S> Service<Request> for SecurityHeaders<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let future = self.inner.call(req);
+        let headers = Arc::clone(&self.headers);
+
+        Box::pin(async move {
+            let mut res = future.await?;
+            for (name, value) in headers.iter() {
+                if !res.headers().contains_key(name) {
+                    res.headers_mut().insert(name, value.clone());
+                }
+            }
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::get, Router};
+
+    #[tokio::test]
+    async fn adds_default_headers() {
+        let app = Router::new()
+            .route("/", get(|| async {}))
+            .layer(SecurityHeadersLayer::new());
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.headers()[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(res.headers()[header::X_FRAME_OPTIONS], "DENY");
+        assert_eq!(
+            res.headers()[header::STRICT_TRANSPORT_SECURITY],
+            "max-age=63072000; includeSubDomains"
+        );
+        assert_eq!(
+            res.headers()[header::CONTENT_SECURITY_POLICY],
+            "default-src 'self'"
+        );
+    }
+
+    #[tokio::test]
+    async fn keeps_existing_headers() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async { [(header::X_FRAME_OPTIONS, "SAMEORIGIN")] }),
+            )
+            .layer(SecurityHeadersLayer::new().without(header::CONTENT_SECURITY_POLICY));
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        let headers = res.headers();
+        assert_eq!(headers[header::X_FRAME_OPTIONS], "SAMEORIGIN");
+        assert_eq!(headers.get_all(header::X_FRAME_OPTIONS).iter().count(), 1);
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert!(!headers.contains_key(header::CONTENT_SECURITY_POLICY));
+    }
+}
@@ -0,0 +1,249 @@
+use crate::extract::is_token;
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request},
+    response::Response,
+};
+use axum_core::__define_rejection as define_rejection;
+use futures_util::future::BoxFuture;
+use http::{header::HeaderName, request::Parts, HeaderValue};
+use std::{
+    fmt::Write,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+/// Named phases of handling a request, reported in the `Server-Timing` response header.
+///
+/// This is inserted as a request extension by [`ServerTimingLayer`] and can be extracted in
+/// handlers, or read from the extensions by other extractors and middleware, to record how long
+/// parts of the request took. Clones share the same phases.
+///
+/// Extracting `ServerTimings` without [`ServerTimingLayer`] being added rejects the request with
+/// `500 Internal Server Error`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::{ServerTimingLayer, ServerTimings};
+/// use std::time::Instant;
+///
+/// async fn handler(timings: ServerTimings) {
+///     let start = Instant::now();
+///     // query the database...
+///     timings.record("db", start.elapsed());
+/// }
+///
+/// // responses get a header such as `Server-Timing: db;dur=12.345, app;dur=15.678`
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .layer(ServerTimingLayer::new());
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ServerTimings {
+    phases: Arc<Mutex<Vec<(String, Duration)>>>,
+}
+
+impl ServerTimings {
+    /// Record that the phase `name` took `duration`.
+    ///
+    /// `name` must be a valid HTTP token, such as `db` or `cache-lookup`. Phases with other names
+    /// are left out of the header.
+    pub fn record(&self, name: impl Into<String>, duration: Duration) {
+        self.phases.lock().unwrap().push((name.into(), duration));
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ServerTimings
+where
+    S: Send + Sync,
+{
+    type Rejection = MissingServerTimings;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Self>()
+            .cloned()
+            .ok_or(MissingServerTimings)
+    }
+}
+
+define_rejection! {
+    #[status = INTERNAL_SERVER_ERROR]
+    #[body = "Missing server timings. Is `ServerTimingLayer` added?"]
+    /// Rejection type for [`ServerTimings`] used if [`ServerTimingLayer`] hasn't been added.
+    pub struct MissingServerTimings;
+}
+
+/// Layer that applies the [`ServerTiming`] middleware.
+///
+/// See [`ServerTimings`] for an example.
+#[derive(Debug, Clone, Copy)]
+pub struct ServerTimingLayer {
+    _priv: (),
+}
+
+impl ServerTimingLayer {
+    /// Create a new `ServerTimingLayer`.
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl Default for ServerTimingLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for ServerTimingLayer {
+    type Service = ServerTiming<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ServerTiming::new(inner)
+    }
+}
+
+/// Middleware that reports how long requests took in the `Server-Timing` response header.
+///
+/// The time from calling the inner service until it returns a response is reported as the `app`
+/// phase, in milliseconds. Phases recorded with the [`ServerTimings`] request extension are
+/// reported before it. Browsers show these durations in their developer tools, so this is handy
+/// for debugging performance.
+///
+/// Time spent streaming the response body isn't included. Since the header exposes details about
+/// the server, consider only adding this middleware in development or for trusted clients.
+///
+/// Created with [`ServerTimingLayer`].
+///
+/// See [`ServerTimings`] for an example.
+#[derive(Debug, Clone)]
+pub struct ServerTiming<S> {
+    inner: S,
+}
+
+impl<S> ServerTiming<S> {
+    /// Create a new `ServerTiming` middleware.
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S> Service<Request> for ServerTiming<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request) -> Self::Future {
+        let timings = ServerTimings::default();
+        req.extensions_mut().insert(timings.clone());
+
+        let start = Instant::now();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let mut res = future.await?;
+            timings.record("app", start.elapsed());
+
+            let mut value = String::new();
+            for (name, duration) in timings.phases.lock().unwrap().iter() {
+                if !is_token(name) {
+                    continue;
+                }
+                if !value.is_empty() {
+                    value.push_str(", ");
+                }
+                let millis = duration.as_secs_f64() * 1000.0;
+                let _ = write!(value, "{name};dur={millis:.3}");
+            }
+
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                res.headers_mut().append(SERVER_TIMING, value);
+            }
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn durations(res: &Response) -> Vec<(String, f64)> {
+        res.headers()["server-timing"]
+            .to_str()
+            .unwrap()
+            .split(", ")
+            .map(|metric| {
+                let (name, duration) = metric.split_once(";dur=").unwrap();
+                (name.to_owned(), duration.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn app_duration() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }),
+            )
+            .layer(ServerTimingLayer::new());
+
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+
+        let durations = durations(&res);
+        assert_eq!(durations.len(), 1);
+        let (name, duration) = &durations[0];
+        assert_eq!(name, "app");
+        assert!(*duration >= 20.0 && *duration < 10_000.0, "{duration}");
+    }
+
+    #[tokio::test]
+    async fn recorded_phases() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|timings: ServerTimings| async move {
+                    timings.record("db", Duration::from_millis(5));
+                    timings.record("not a token", Duration::from_millis(1));
+                    timings.record("cache", Duration::from_micros(1500));
+                }),
+            )
+            .layer(ServerTimingLayer::new());
+
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+
+        let durations = durations(&res);
+        let names = durations
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["db", "cache", "app"]);
+        assert_eq!(durations[0].1, 5.0);
+        assert_eq!(durations[1].1, 1.5);
+    }
+}
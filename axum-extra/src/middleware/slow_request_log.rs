@@ -0,0 +1,158 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    response::Response,
+};
+use futures_util::future::BoxFuture;
+use std::{
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`SlowRequestLog`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::SlowRequestLogLayer;
+/// use std::time::Duration;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     // log requests that take longer than 500 milliseconds
+///     .layer(SlowRequestLogLayer::new(Duration::from_millis(500)));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SlowRequestLogLayer {
+    threshold: Duration,
+}
+
+impl SlowRequestLogLayer {
+    /// Create a new `SlowRequestLogLayer` that logs requests taking longer than `threshold`.
+    pub fn new(threshold: Duration) -> Self {
+        Self { threshold }
+    }
+}
+
+impl<S> Layer<S> for SlowRequestLogLayer {
+    type Service = SlowRequestLog<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SlowRequestLog {
+            inner,
+            threshold: self.threshold,
+        }
+    }
+}
+
+/// Middleware that logs requests that take longer than a threshold.
+///
+/// The time from calling the inner service until it produces a response is measured. If that
+/// exceeds the threshold a `WARN` event is emitted with the method, the [`MatchedPath`], the
+/// response status and the duration in milliseconds. Requests that finish in time aren't logged,
+/// so this can stay enabled in production without flooding the logs.
+///
+/// Only the time until the response head is produced is measured, not the time it takes to send
+/// the response body. To see the matched route the middleware must be added with
+/// [`Router::layer`] or [`Router::route_layer`].
+///
+/// Created with [`SlowRequestLogLayer`].
+///
+/// [`Router::layer`]: axum::Router::layer
+/// [`Router::route_layer`]: axum::Router::route_layer
+#[derive(Debug, Clone)]
+pub struct SlowRequestLog<S> {
+    inner: S,
+    threshold: Duration,
+}
+
+impl<S> Service<Request> for SlowRequestLog<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let method = req.method().clone();
+        let matched_path = req.extensions().get::<MatchedPath>().cloned();
+        let threshold = self.threshold;
+
+        let start = Instant::now();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let res = future.await?;
+            let elapsed = start.elapsed();
+            if elapsed > threshold {
+                tracing::warn!(
+                    method = %method,
+                    matched_path = matched_path.as_ref().map(MatchedPath::as_str),
+                    status = res.status().as_u16(),
+                    duration_ms = elapsed.as_millis() as u64,
+                    "slow request",
+                );
+            }
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use tower::ServiceExt;
+    use tracing::{Event, Level, Subscriber};
+    use tracing_subscriber::{layer::Context as LayerContext, prelude::*};
+
+    #[derive(Default, Clone)]
+    struct CountWarnings(Arc<AtomicUsize>);
+
+    impl<S> tracing_subscriber::Layer<S> for CountWarnings
+    where
+        S: Subscriber,
+    {
+        fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+            if *event.metadata().level() == Level::WARN {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn logs_only_slow_requests() {
+        let warnings = CountWarnings::default();
+        let subscriber = tracing_subscriber::registry().with(warnings.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = Router::new()
+            .route("/fast", get(|| async {}))
+            .route(
+                "/slow",
+                get(|| async { tokio::time::sleep(Duration::from_millis(100)).await }),
+            )
+            .layer(SlowRequestLogLayer::new(Duration::from_millis(50)));
+
+        let req = Request::get("/fast").body(Body::empty()).unwrap();
+        app.clone().oneshot(req).await.unwrap();
+        assert_eq!(warnings.0.load(Ordering::SeqCst), 0);
+
+        let req = Request::get("/slow").body(Body::empty()).unwrap();
+        app.oneshot(req).await.unwrap();
+        assert_eq!(warnings.0.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,183 @@
+use axum::{extract::Request, response::Response};
+use futures_util::future::BoxFuture;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`TitleCaseHeaders`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_extra::middleware::TitleCaseHeadersLayer;
+///
+/// let app = Router::new()
+///     .route("/", get(|| async { /* ... */ }))
+///     .layer(TitleCaseHeadersLayer::new());
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TitleCaseHeadersLayer {
+    _priv: (),
+}
+
+impl TitleCaseHeadersLayer {
+    /// Create a new `TitleCaseHeadersLayer`.
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<S> Layer<S> for TitleCaseHeadersLayer {
+    type Service = TitleCaseHeaders<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TitleCaseHeaders { inner }
+    }
+}
+
+/// Middleware that records the response headers with canonical Title-Case names.
+///
+/// [`HeaderMap`] always stores header names in lowercase, which is what HTTP/2 requires and what
+/// HTTP/1.1 clients should accept, but some legacy clients only recognize headers such as
+/// `Content-Type` in their canonical casing. Since the casing can't be expressed in a
+/// [`HeaderMap`], this middleware inserts a [`TitleCasedHeaders`] extension into the response
+/// which contains every header, in order, with its name converted to Title-Case.
+///
+/// # Integration
+///
+/// The extension only has an effect if the code that writes the response to the connection
+/// uses it, for example a custom HTTP/1.1 server or a proxy that forwards responses to legacy
+/// clients. Such code should write the headers from [`TitleCasedHeaders::iter`] instead of
+/// [`Response::headers`].
+///
+/// If the app is served with hyper, the simpler option is to enable
+/// [`title_case_headers`] on the HTTP/1 connection builder, which title-cases all headers when
+/// they're written and doesn't need this middleware.
+///
+/// Created with [`TitleCaseHeadersLayer`].
+///
+/// [`title_case_headers`]: https://docs.rs/hyper/1/hyper/server/conn/http1/struct.Builder.html#method.title_case_headers
+#[derive(Debug, Clone, Copy)]
+pub struct TitleCaseHeaders<S> {
+    inner: S,
+}
+
+impl<S> Service<Request> for TitleCaseHeaders<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let mut res = future.await?;
+            let headers = TitleCasedHeaders::from_headers(res.headers());
+            res.extensions_mut().insert(headers);
+            Ok(res)
+        })
+    }
+}
+
+/// Headers with their names in canonical Title-Case, such as `Content-Type`.
+///
+/// Inserted into responses by [`TitleCaseHeaders`], but can also be created from any
+/// [`HeaderMap`], for example for requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TitleCasedHeaders(Vec<(String, HeaderValue)>);
+
+impl TitleCasedHeaders {
+    /// Create `TitleCasedHeaders` from all headers in `headers`, in iteration order.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self(
+            headers
+                .iter()
+                .map(|(name, value)| (title_case(name), value.clone()))
+                .collect(),
+        )
+    }
+
+    /// Iterate over the Title-Case names and values of the headers.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &HeaderValue)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Returns the number of headers.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no headers.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Convert a header name to Title-Case, capitalizing the first letter and every letter after a
+/// `-`.
+fn title_case(name: &HeaderName) -> String {
+    let mut title_cased = String::with_capacity(name.as_str().len());
+    let mut capitalize = true;
+    for c in name.as_str().chars() {
+        if capitalize {
+            title_cased.push(c.to_ascii_uppercase());
+        } else {
+            title_cased.push(c);
+        }
+        capitalize = c == '-';
+    }
+    title_cased
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use http::header;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn title_cases_response_headers() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async { ([("x-request-id", "1"), ("www-authenticate", "Basic")], "ok") }),
+            )
+            .layer(TitleCaseHeadersLayer::new());
+
+        let res = app.oneshot(Request::new(Body::empty())).await.unwrap();
+        let headers = res.extensions().get::<TitleCasedHeaders>().unwrap();
+        let names = headers.iter().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(names.len(), res.headers().len());
+        assert!(names.contains(&"Content-Type"));
+        assert!(names.contains(&"X-Request-Id"));
+        assert!(names.contains(&"Www-Authenticate"));
+
+        let (_, value) = headers
+            .iter()
+            .find(|(name, _)| *name == "X-Request-Id")
+            .unwrap();
+        assert_eq!(value, "1");
+    }
+
+    #[test]
+    fn title_case_names() {
+        assert_eq!(title_case(&header::CONTENT_TYPE), "Content-Type");
+        assert_eq!(title_case(&header::ETAG), "Etag");
+        assert_eq!(
+            title_case(&HeaderName::from_static("x-forwarded-for")),
+            "X-Forwarded-For"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use axum::response::{IntoResponse, Redirect, Response};
+use futures_util::future::{ready, Either, Ready};
+use http::{Request, Uri};
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`TrailingSlashRedirect`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{extract::Request, routing::get, Router, ServiceExt};
+/// use axum_extra::middleware::TrailingSlashRedirectLayer;
+/// use tower::Layer;
+///
+/// let router = Router::new().route("/users", get(|| async { /* ... */ }));
+///
+/// // redirect `/users/` to `/users`
+/// let app = TrailingSlashRedirectLayer::trim().layer(router);
+/// # async {
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+/// axum::serve(listener, ServiceExt::<Request>::into_make_service(app)).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TrailingSlashRedirectLayer {
+    append: bool,
+}
+
+impl TrailingSlashRedirectLayer {
+    /// Create a new `TrailingSlashRedirectLayer` that redirects paths with a trailing slash to
+    /// the path without it.
+    pub fn trim() -> Self {
+        Self { append: false }
+    }
+
+    /// Create a new `TrailingSlashRedirectLayer` that redirects paths without a trailing slash to
+    /// the path with it.
+    pub fn append() -> Self {
+        Self { append: true }
+    }
+}
+
+impl<S> Layer<S> for TrailingSlashRedirectLayer {
+    type Service = TrailingSlashRedirect<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TrailingSlashRedirect {
+            inner,
+            append: self.append,
+        }
+    }
+}
+
+/// Middleware that redirects requests to the canonical form of their path.
+///
+/// Depending on the policy, requests whose path has a trailing slash, or doesn't have one, are
+/// answered with a `308 Permanent Redirect` to the canonical path without calling the inner
+/// service. The query string is kept, and since `308` requires clients to repeat the request with
+/// the same method and body, this also works for requests other than `GET`. The root path `/` is
+/// always passed through. Repeated leading slashes are collapsed into one in the redirect location.
+///
+/// Unlike [`RouterExt::route_with_tsr`], which adds a redirect for a single route, this applies to
+/// every request. Since the redirect has to happen before routing, the middleware must wrap the
+/// whole [`Router`] rather than being added with [`Router::layer`].
+///
+/// Created with [`TrailingSlashRedirectLayer`].
+///
+/// [`RouterExt::route_with_tsr`]: crate::routing::RouterExt::route_with_tsr
+/// [`Router`]: axum::Router
+/// [`Router::layer`]: axum::Router::layer
+#[derive(Debug, Clone, Copy)]
+pub struct TrailingSlashRedirect<S> {
+    inner: S,
+    append: bool,
+}
+
+impl<S, B> Service<Request<B>> for TrailingSlashRedirect<S>
+where
+    S: Service<Request<B>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        match canonical_location(req.uri(), self.append) {
+            Some(location) => {
+                let res = Redirect::permanent(&location).into_response();
+                Either::Left(ready(Ok(res)))
+            }
+            None => Either::Right(self.inner.call(req)),
+        }
+    }
+}
+
+/// Returns the location to redirect to, or `None` if the path is already canonical.
+fn canonical_location(uri: &Uri, append: bool) -> Option<String> {
+    let path = uri.path();
+    if path == "/" {
+        return None;
+    }
+
+    let canonical = if append {
+        if path.ends_with('/') {
+            return None;
+        }
+        format!("{path}/")
+    } else {
+        if !path.ends_with('/') {
+            return None;
+        }
+        match path.trim_end_matches('/') {
+            "" => "/".to_owned(),
+            trimmed => trimmed.to_owned(),
+        }
+    };
+    // a location starting with `//` is a scheme relative URL pointing to another host, so
+    // `//evil.com/` must not be redirected to `//evil.com`
+    let canonical = format!("/{}", canonical.trim_start_matches('/'));
+
+    Some(match uri.query() {
+        Some(query) => format!("{canonical}?{query}"),
+        None => canonical,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{
+        routing::{get, post},
+        Router,
+    };
+    use http::StatusCode;
+
+    #[tokio::test]
+    async fn trim_redirects() {
+        let app = Router::new()
+            .route("/foo", get(|| async {}))
+            .route("/bar", post(|| async {}));
+        let app = TrailingSlashRedirectLayer::trim().layer(app);
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/foo/?a=1&b=2").await;
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(res.headers()["location"], "/foo?a=1&b=2");
+
+        let res = client.post("/bar//").await;
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(res.headers()["location"], "/bar");
+    }
+
+    #[tokio::test]
+    async fn trim_passes_canonical_paths_through() {
+        let app = Router::new()
+            .route("/", get(|| async { "root" }))
+            .route("/foo", get(|| async { "foo" }));
+        let app = TrailingSlashRedirectLayer::trim().layer(app);
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/foo?a=1").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "foo");
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "root");
+    }
+
+    #[tokio::test]
+    async fn append() {
+        let app = Router::new().route("/foo/", get(|| async { "foo/" }));
+        let app = TrailingSlashRedirectLayer::append().layer(app);
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/foo?a=1").await;
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(res.headers()["location"], "/foo/?a=1");
+
+        let res = client.get("/foo/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "foo/");
+    }
+
+    #[tokio::test]
+    async fn no_redirect_to_other_hosts() {
+        let app = TrailingSlashRedirectLayer::trim().layer(Router::new());
+
+        let client = TestClient::new(app);
+        let res = client.get("//evil.com/").await;
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(res.headers()["location"], "/evil.com");
+
+        let app = TrailingSlashRedirectLayer::append().layer(Router::new());
+
+        let client = TestClient::new(app);
+        let res = client.get("//evil.com").await;
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(res.headers()["location"], "/evil.com/");
+    }
+}
@@ -0,0 +1,314 @@
+use axum::{
+    body::Body,
+    extract::{FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use http::{HeaderMap, StatusCode};
+use sha2::{Digest, Sha256, Sha512};
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`VerifyDigest`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{routing::post, Router};
+/// use axum_extra::middleware::VerifyDigestLayer;
+///
+/// let app = Router::new()
+///     .route("/upload", post(|body: String| async { /* ... */ }))
+///     .layer(VerifyDigestLayer::new());
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct VerifyDigestLayer {
+    _priv: (),
+}
+
+impl VerifyDigestLayer {
+    /// Create a new `VerifyDigestLayer`.
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl Default for VerifyDigestLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for VerifyDigestLayer {
+    type Service = VerifyDigest<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        VerifyDigest::new(inner)
+    }
+}
+
+/// Middleware that verifies the request body against the digest declared by the client.
+///
+/// The digest is read from the `Content-Digest` header ([RFC 9530]), such as
+/// `sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:`, or if that's missing from the legacy
+/// `Digest` header ([RFC 3230]), such as `SHA-256=X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=`.
+/// The `sha-256` and `sha-512` algorithms are supported. If the header lists several supported
+/// algorithms, all of them must match.
+///
+/// To compute the digest the body is buffered into memory, and the inner service gets the
+/// buffered body so it can be extracted as usual. The [default body limit] applies while
+/// buffering, and if the body can't be buffered the request is rejected like by the [`Bytes`]
+/// extractor. Requests are rejected with `400 Bad Request` without calling the inner service if
+///
+/// - the digest doesn't match the body,
+/// - or the header is malformed or doesn't list any supported algorithm.
+///
+/// Requests without either header are passed through unchanged and aren't buffered.
+///
+/// Created with [`VerifyDigestLayer`].
+///
+/// [default body limit]: axum::extract::DefaultBodyLimit
+/// [RFC 9530]: https://www.rfc-editor.org/rfc/rfc9530
+/// [RFC 3230]: https://www.rfc-editor.org/rfc/rfc3230
+#[derive(Debug, Clone)]
+pub struct VerifyDigest<S> {
+    inner: S,
+}
+
+impl<S> VerifyDigest<S> {
+    /// Create a new `VerifyDigest` middleware.
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S> Service<Request> for VerifyDigest<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+
+        let expected = match declared_digests(req.headers()) {
+            Some(Ok(expected)) => expected,
+            Some(Err(())) => {
+                return Box::pin(async {
+                    Ok((
+                        StatusCode::BAD_REQUEST,
+                        "Malformed digest header or no supported digest algorithm",
+                    )
+                        .into_response())
+                });
+            }
+            None => return Box::pin(inner.call(req)),
+        };
+
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body =
+                match Bytes::from_request(Request::from_parts(parts.clone(), body), &()).await {
+                    Ok(bytes) => bytes,
+                    Err(rejection) => return Ok(rejection.into_response()),
+                };
+
+            for (algorithm, digest) in expected {
+                if algorithm.digest(&body) != digest {
+                    return Ok((
+                        StatusCode::BAD_REQUEST,
+                        format!(
+                            "The `{}` digest doesn't match the request body",
+                            algorithm.as_str()
+                        ),
+                    )
+                        .into_response());
+                }
+            }
+
+            inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Algorithm {
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    fn parse(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("sha-256") {
+            Some(Self::Sha256)
+        } else if name.eq_ignore_ascii_case("sha-512") {
+            Some(Self::Sha512)
+        } else {
+            None
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha-256",
+            Self::Sha512 => "sha-512",
+        }
+    }
+
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => Sha256::digest(data).to_vec(),
+            Self::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+type Digests = Vec<(Algorithm, Vec<u8>)>;
+
+/// Returns the digests of supported algorithms declared in `headers`.
+///
+/// `None` means there is no digest header and `Err` that it's malformed or only lists
+/// unsupported algorithms.
+fn declared_digests(headers: &HeaderMap) -> Option<Result<Digests, ()>> {
+    let (name, is_structured) = if headers.contains_key("content-digest") {
+        ("content-digest", true)
+    } else if headers.contains_key("digest") {
+        ("digest", false)
+    } else {
+        return None;
+    };
+
+    let mut digests = Vec::new();
+    for value in headers.get_all(name) {
+        let Ok(value) = value.to_str() else {
+            return Some(Err(()));
+        };
+
+        for member in value.split(',') {
+            let Some((algorithm, digest)) = member.trim().split_once('=') else {
+                return Some(Err(()));
+            };
+            let Some(algorithm) = Algorithm::parse(algorithm.trim()) else {
+                continue;
+            };
+
+            // structured fields wrap byte sequences in colons
+            let digest = if is_structured {
+                digest.strip_prefix(':').and_then(|d| d.strip_suffix(':'))
+            } else {
+                Some(digest)
+            };
+            let Some(digest) = digest.and_then(|digest| STANDARD.decode(digest).ok()) else {
+                return Some(Err(()));
+            };
+            digests.push((algorithm, digest));
+        }
+    }
+
+    if digests.is_empty() {
+        Some(Err(()))
+    } else {
+        Some(Ok(digests))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::DefaultBodyLimit, routing::post, Router};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    // base64 of the sha-256 digest of `hello world`
+    const HELLO_WORLD_SHA256: &str = "uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=";
+
+    async fn send(
+        app: Router,
+        headers: &[(&str, &str)],
+        body: &'static str,
+    ) -> (StatusCode, String) {
+        let mut req = Request::post("/");
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        let req = req.body(Body::from(body)).unwrap();
+
+        let res = app.oneshot(req).await.unwrap();
+        let status = res.status();
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn matching_digest() {
+        let app = Router::new()
+            .route("/", post(|body: String| async move { body }))
+            .layer(VerifyDigestLayer::new());
+
+        let content_digest = format!("sha-256=:{HELLO_WORLD_SHA256}:");
+        let headers = [("content-digest", content_digest.as_str())];
+        let (status, body) = send(app.clone(), &headers, "hello world").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "hello world");
+
+        let digest = format!("SHA-256={HELLO_WORLD_SHA256}");
+        let (status, body) = send(app, &[("digest", &digest)], "hello world").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "hello world");
+    }
+
+    #[tokio::test]
+    async fn mismatched_digest() {
+        let app = Router::new()
+            .route("/", post(|| async {}))
+            .layer(VerifyDigestLayer::new());
+
+        let content_digest = format!("sha-256=:{HELLO_WORLD_SHA256}:");
+        let headers = [("content-digest", content_digest.as_str())];
+        let (status, _) = send(app.clone(), &headers, "goodbye world").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // unsupported algorithms only
+        let headers = [("content-digest", "md5=:abc=:")];
+        let (status, _) = send(app, &headers, "hello world").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn no_digest() {
+        let app = Router::new()
+            .route("/", post(|body: String| async move { body }))
+            .layer(VerifyDigestLayer::new());
+
+        let (status, body) = send(app, &[], "hello world").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "hello world");
+    }
+
+    #[tokio::test]
+    async fn body_limit() {
+        let app = Router::new()
+            .route("/", post(|| async {}))
+            .layer(VerifyDigestLayer::new())
+            .layer(DefaultBodyLimit::max(16));
+
+        let content_digest = format!("sha-256=:{HELLO_WORLD_SHA256}:");
+        let headers = [("content-digest", content_digest.as_str())];
+        let (status, _) = send(app, &headers, "this body is longer than the limit").await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
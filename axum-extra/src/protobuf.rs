@@ -5,8 +5,9 @@ use axum::{
     extract::{rejection::BytesRejection, FromRequest, Request},
     response::{IntoResponse, Response},
 };
+use axum_core::__define_rejection as define_rejection;
 use bytes::{Bytes, BytesMut};
-use http::StatusCode;
+use http::{header, HeaderMap, StatusCode};
 use prost::Message;
 
 /// A Protocol Buffer message extractor and response.
@@ -19,12 +20,16 @@ use prost::Message;
 /// implements [`prost::Message`]. The request will be rejected (and a [`ProtobufRejection`] will
 /// be returned) if:
 ///
+/// - The request doesn't have a `Content-Type: application/x-protobuf` (or
+///   `application/protobuf`) header.
 /// - The body couldn't be decoded into the target Protocol Buffer message type.
 /// - Buffering the request body fails.
 ///
 /// See [`ProtobufRejection`] for more details.
 ///
-/// The extractor does not expect a `Content-Type` header to be present in the request.
+/// The request body is buffered in memory and limited by [`DefaultBodyLimit`].
+///
+/// [`DefaultBodyLimit`]: axum::extract::DefaultBodyLimit
 ///
 /// # Extractor example
 ///
@@ -99,6 +104,10 @@ where
     type Rejection = ProtobufRejection;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !protobuf_content_type(req.headers()) {
+            return Err(MissingProtobufContentType.into());
+        }
+
         let mut bytes = Bytes::from_request(req, state).await?;
 
         match T::decode(&mut bytes) {
@@ -110,6 +119,21 @@ where
 
 axum_core::__impl_deref!(Protobuf);
 
+fn protobuf_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers.get(header::CONTENT_TYPE) else {
+        return false;
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return false;
+    };
+    let Ok(mime) = content_type.parse::<mime::Mime>() else {
+        return false;
+    };
+
+    mime.type_() == "application"
+        && (mime.subtype() == "x-protobuf" || mime.subtype() == "protobuf")
+}
+
 impl<T> From<T> for Protobuf<T> {
     fn from(inner: T) -> Self {
         Self(inner)
@@ -162,6 +186,15 @@ impl IntoResponse for ProtobufDecodeError {
     }
 }
 
+define_rejection! {
+    #[status = UNSUPPORTED_MEDIA_TYPE]
+    #[body = "Expected request with `Content-Type: application/x-protobuf`"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+    /// Rejection type for [`Protobuf`] used if the `Content-Type`
+    /// header is missing or isn't a Protocol Buffer content type.
+    pub struct MissingProtobufContentType;
+}
+
 /// Rejection used for [`Protobuf`].
 ///
 /// Contains one variant for each way the [`Protobuf`] extractor
@@ -173,6 +206,8 @@ pub enum ProtobufRejection {
     ProtobufDecodeError(ProtobufDecodeError),
     #[allow(missing_docs)]
     BytesRejection(BytesRejection),
+    #[allow(missing_docs)]
+    MissingProtobufContentType(MissingProtobufContentType),
 }
 
 impl From<ProtobufDecodeError> for ProtobufRejection {
@@ -187,11 +222,18 @@ impl From<BytesRejection> for ProtobufRejection {
     }
 }
 
+impl From<MissingProtobufContentType> for ProtobufRejection {
+    fn from(inner: MissingProtobufContentType) -> Self {
+        Self::MissingProtobufContentType(inner)
+    }
+}
+
 impl IntoResponse for ProtobufRejection {
     fn into_response(self) -> Response {
         match self {
             Self::ProtobufDecodeError(inner) => inner.into_response(),
             Self::BytesRejection(inner) => inner.into_response(),
+            Self::MissingProtobufContentType(inner) => inner.into_response(),
         }
     }
 }
@@ -220,7 +262,11 @@ mod tests {
         };
 
         let client = TestClient::new(app);
-        let res = client.post("/").body(input.encode_to_vec()).await;
+        let res = client
+            .post("/")
+            .header("content-type", "application/x-protobuf")
+            .body(input.encode_to_vec())
+            .await;
 
         let body = res.text().await;
 
@@ -248,7 +294,11 @@ mod tests {
         };
 
         let client = TestClient::new(app);
-        let res = client.post("/").body(input.encode_to_vec()).await;
+        let res = client
+            .post("/")
+            .header("content-type", "application/x-protobuf")
+            .body(input.encode_to_vec())
+            .await;
 
         assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
@@ -283,7 +333,11 @@ mod tests {
         };
 
         let client = TestClient::new(app);
-        let res = client.post("/").body(input.encode_to_vec()).await;
+        let res = client
+            .post("/")
+            .header("content-type", "application/x-protobuf")
+            .body(input.encode_to_vec())
+            .await;
 
         assert_eq!(
             res.headers()["content-type"],
@@ -296,4 +350,42 @@ mod tests {
 
         assert_eq!(output.result, "bar");
     }
+
+    #[tokio::test]
+    async fn requires_protobuf_content_type() {
+        #[derive(prost::Message)]
+        struct Input {
+            #[prost(string, tag = "1")]
+            foo: String,
+        }
+
+        let app = Router::new().route(
+            "/",
+            post(|input: Protobuf<Input>| async move { input.foo.to_owned() }),
+        );
+
+        let input = Input {
+            foo: "bar".to_owned(),
+        };
+
+        let client = TestClient::new(app);
+
+        let res = client.post("/").body(input.encode_to_vec()).await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/json")
+            .body(input.encode_to_vec())
+            .await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/protobuf")
+            .body(input.encode_to_vec())
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "bar");
+    }
 }
@@ -0,0 +1,219 @@
+//! XML extractor.
+
+use axum::{
+    async_trait,
+    extract::{rejection::BytesRejection, FromRequest, Request},
+};
+use axum_core::__composite_rejection as composite_rejection;
+use axum_core::__define_rejection as define_rejection;
+use bytes::Bytes;
+use http::{header, HeaderMap};
+use serde::de::DeserializeOwned;
+
+/// An XML extractor.
+///
+/// Deserializes request bodies into some type that implements [`serde::de::DeserializeOwned`],
+/// using [`quick_xml`]. The request will be rejected (and an [`XmlRejection`] will be returned)
+/// if:
+///
+/// - The request doesn't have a `Content-Type: application/xml` or `Content-Type: text/xml`
+///   header. Content types with a `+xml` suffix, such as `application/soap+xml`, are accepted as
+///   well.
+/// - The body couldn't be deserialized into the target type, either because it isn't valid XML
+///   or because it doesn't match the shape of the target type.
+/// - Buffering the request body fails.
+///
+/// See [`XmlRejection`] for more details.
+///
+/// The request body is buffered in memory and limited by [`DefaultBodyLimit`].
+///
+/// [`DefaultBodyLimit`]: axum::extract::DefaultBodyLimit
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use axum_extra::xml::Xml;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Order {
+///     id: u64,
+///     #[serde(rename = "item")]
+///     items: Vec<String>,
+/// }
+///
+/// async fn create_order(Xml(order): Xml<Order>) {
+///     // order is `Order`
+/// }
+///
+/// let app = Router::new().route("/orders", post(create_order));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+#[must_use]
+pub struct Xml<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Xml<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = XmlRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !xml_content_type(req.headers()) {
+            return Err(MissingXmlContentType.into());
+        }
+
+        let bytes = Bytes::from_request(req, state).await?;
+
+        match quick_xml::de::from_reader(&*bytes) {
+            Ok(value) => Ok(Self(value)),
+            Err(err) => Err(XmlError::from_err(err).into()),
+        }
+    }
+}
+
+axum_core::__impl_deref!(Xml);
+
+fn xml_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers.get(header::CONTENT_TYPE) else {
+        return false;
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return false;
+    };
+    let Ok(mime) = content_type.parse::<mime::Mime>() else {
+        return false;
+    };
+
+    (mime.type_() == "application" || mime.type_() == "text")
+        && (mime.subtype() == "xml" || mime.suffix().map_or(false, |name| name == "xml"))
+}
+
+impl<T> From<T> for Xml<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to deserialize the XML body into the target type"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+    /// Rejection type for [`Xml`].
+    ///
+    /// This rejection is used if the request body isn't valid XML or couldn't be deserialized
+    /// into the target type.
+    pub struct XmlError(Error);
+}
+
+define_rejection! {
+    #[status = UNSUPPORTED_MEDIA_TYPE]
+    #[body = "Expected request with `Content-Type: application/xml`"]
+    #[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+    /// Rejection type for [`Xml`] used if the `Content-Type`
+    /// header is missing or isn't an XML content type.
+    pub struct MissingXmlContentType;
+}
+
+composite_rejection! {
+    /// Rejection used for [`Xml`].
+    ///
+    /// Contains one variant for each way the [`Xml`] extractor
+    /// can fail.
+    #[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+    pub enum XmlRejection {
+        XmlError,
+        MissingXmlContentType,
+        BytesRejection,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Router};
+    use http::StatusCode;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Order {
+        id: u64,
+        #[serde(rename = "item")]
+        items: Vec<String>,
+    }
+
+    const ORDER: &str = "<order><id>7</id><item>foo</item><item>bar</item></order>";
+
+    #[tokio::test]
+    async fn decode_body() {
+        let app = Router::new().route(
+            "/",
+            post(|Xml(order): Xml<Order>| async move {
+                format!("{}: {}", order.id, order.items.join(","))
+            }),
+        );
+
+        let client = TestClient::new(app);
+        let res = client
+            .post("/")
+            .header("content-type", "application/xml")
+            .body(ORDER)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "7: foo,bar");
+    }
+
+    #[tokio::test]
+    async fn malformed_body() {
+        let app = Router::new().route("/", post(|_: Xml<Order>| async {}));
+
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/xml")
+            .body("<order><id>7</id></item></order>")
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/xml")
+            .body("<order><id>not a number</id></order>")
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn requires_xml_content_type() {
+        let app = Router::new().route("/", post(|_: Xml<Order>| async {}));
+
+        let client = TestClient::new(app);
+
+        let res = client.post("/").body(ORDER).await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/json")
+            .body(ORDER)
+            .await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        for content_type in ["text/xml; charset=utf-8", "application/soap+xml"] {
+            let res = client
+                .post("/")
+                .header("content-type", content_type)
+                .body(ORDER)
+                .await;
+            assert_eq!(res.status(), StatusCode::OK, "{content_type}");
+        }
+    }
+}
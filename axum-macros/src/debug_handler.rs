@@ -4,7 +4,7 @@ use crate::{
     attr_parsing::{parse_assignment_attribute, second},
     with_position::{Position, WithPosition},
 };
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
 use syn::{parse::Parse, spanned::Spanned, FnArg, ItemFn, ReturnType, Token, Type};
 
@@ -34,14 +34,7 @@ pub(crate) fn expand(attr: Attrs, item_fn: ItemFn) -> TokenStream {
             if state_types_from_args.len() == 1 {
                 state_ty = state_types_from_args.into_iter().next();
             } else if state_types_from_args.len() > 1 {
-                err = Some(
-                    syn::Error::new(
-                        Span::call_site(),
-                        "can't infer state type, please add set it explicitly, as in \
-                         `#[debug_handler(state = MyStateType)]`",
-                    )
-                    .into_compile_error(),
-                );
+                err = Some(check_conflicting_state_types(&item_fn));
             }
         }
 
@@ -246,13 +239,23 @@ fn check_inputs_impls_from_request(item_fn: &ItemFn, state_ty: Type) -> TokenStr
                 }
             };
 
-            let check_fn_generics = if must_impl_from_request_parts || consumes_request {
-                quote! {}
-            } else {
-                quote! { <M> }
-            };
+            let inner_state_ty = state_type_from_arg(&ty);
+
+            let check_fn_generics =
+                if must_impl_from_request_parts || consumes_request || inner_state_ty.is_some() {
+                    quote! {}
+                } else {
+                    quote! { <M> }
+                };
 
-            let from_request_bound = if must_impl_from_request_parts {
+            let from_request_bound = if let Some(inner_state_ty) = inner_state_ty {
+                // `State<T>` implements `FromRequestParts<S>` if `T: FromRef<S>`. Checking that
+                // directly points the error at the substate that can't be extracted.
+                let span = inner_state_ty.span();
+                quote_spanned! {span=>
+                    #inner_state_ty: ::axum::extract::FromRef<#state_ty>
+                }
+            } else if must_impl_from_request_parts {
                 quote_spanned! {span=>
                     #ty: ::axum::extract::FromRequestParts<#state_ty> + Send
                 }
@@ -763,6 +766,49 @@ fn state_types_from_args(item_fn: &ItemFn) -> HashSet<Type> {
     crate::infer_state_types(types).collect()
 }
 
+/// Emit an error for each `State` argument whose type conflicts with the state type of an earlier
+/// argument.
+///
+/// Used when the state type isn't set explicitly and the `State` arguments don't agree on it.
+fn check_conflicting_state_types(item_fn: &ItemFn) -> TokenStream {
+    let mut first_state_ty = None;
+
+    item_fn
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(pat_type) => Some(&*pat_type.ty),
+        })
+        .filter_map(|ty| Some((ty, state_type_from_arg(ty)?)))
+        .filter_map(|(ty, state_ty)| {
+            let first_state_ty = first_state_ty.get_or_insert(state_ty.clone());
+            if *first_state_ty == state_ty {
+                return None;
+            }
+
+            let error = syn::Error::new_spanned(
+                ty,
+                format!(
+                    "can't infer state type, this argument requires `{}` but an earlier argument \
+                     requires `{}`. Please set it explicitly, as in \
+                     `#[debug_handler(state = MyStateType)]`, and implement \
+                     `FromRef<MyStateType>` for each substate",
+                    quote!(#state_ty),
+                    quote!(#first_state_ty),
+                ),
+            );
+            Some(error.into_compile_error())
+        })
+        .collect()
+}
+
+/// Get `T` if `ty` is a `State<T>`.
+fn state_type_from_arg(ty: &Type) -> Option<Type> {
+    crate::infer_state_types(std::iter::once(ty)).next()
+}
+
 #[test]
 fn ui() {
     crate::run_ui_tests("debug_handler");
@@ -1,12 +1,15 @@
+use heck::ToUpperCamelCase;
 use proc_macro2::{Ident, TokenStream};
-use quote::quote_spanned;
+use quote::{format_ident, quote_spanned};
 use syn::{
     parse::{Parse, ParseStream},
     spanned::Spanned,
-    Field, ItemStruct, Token, Type,
+    Field, ItemStruct, LitStr, Token, Type, Visibility,
 };
 
-use crate::attr_parsing::{combine_unary_attribute, parse_attrs, Combine};
+use crate::attr_parsing::{
+    combine_attribute, combine_unary_attribute, parse_assignment_attribute, parse_attrs, Combine,
+};
 
 pub(crate) fn expand(item: ItemStruct) -> syn::Result<TokenStream> {
     if !item.generics.params.is_empty() {
@@ -20,14 +23,14 @@ pub(crate) fn expand(item: ItemStruct) -> syn::Result<TokenStream> {
         .fields
         .iter()
         .enumerate()
-        .map(|(idx, field)| expand_field(&item.ident, idx, field))
+        .map(|(idx, field)| expand_field(&item.ident, &item.vis, idx, field))
         .collect();
 
     Ok(tokens)
 }
 
-fn expand_field(state: &Ident, idx: usize, field: &Field) -> TokenStream {
-    let FieldAttrs { skip } = match parse_attrs("from_ref", &field.attrs) {
+fn expand_field(state: &Ident, vis: &Visibility, idx: usize, field: &Field) -> TokenStream {
+    let FieldAttrs { skip, name } = match parse_attrs("from_ref", &field.attrs) {
         Ok(attrs) => attrs,
         Err(err) => return err.into_compile_error(),
     };
@@ -53,6 +56,31 @@ fn expand_field(state: &Ident, idx: usize, field: &Field) -> TokenStream {
         quote_spanned! {span=> state.#idx.clone() }
     };
 
+    if let Some((_, name)) = name {
+        let wrapper = format_ident!(
+            "{}State",
+            name.value().to_upper_camel_case(),
+            span = name.span()
+        );
+        let doc = format!(
+            "Newtype wrapper around the `{}` field of `{state}`, generated by `#[derive(FromRef)]`.",
+            name.value(),
+        );
+
+        return quote_spanned! {span=>
+            #[doc = #doc]
+            #[derive(Clone)]
+            #vis struct #wrapper(pub #field_ty);
+
+            #[allow(clippy::clone_on_copy)]
+            impl ::axum::extract::FromRef<#state> for #wrapper {
+                fn from_ref(state: &#state) -> Self {
+                    Self(#body)
+                }
+            }
+        };
+    }
+
     quote_spanned! {span=>
         #[allow(clippy::clone_on_copy)]
         impl ::axum::extract::FromRef<#state> for #field_ty {
@@ -65,21 +93,26 @@ fn expand_field(state: &Ident, idx: usize, field: &Field) -> TokenStream {
 
 mod kw {
     syn::custom_keyword!(skip);
+    syn::custom_keyword!(name);
 }
 
 #[derive(Default)]
 pub(super) struct FieldAttrs {
     pub(super) skip: Option<kw::skip>,
+    pub(super) name: Option<(kw::name, LitStr)>,
 }
 
 impl Parse for FieldAttrs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut skip = None;
+        let mut name = None;
 
         while !input.is_empty() {
             let lh = input.lookahead1();
             if lh.peek(kw::skip) {
                 skip = Some(input.parse()?);
+            } else if lh.peek(kw::name) {
+                parse_assignment_attribute(input, &mut name)?;
             } else {
                 return Err(lh.error());
             }
@@ -87,14 +120,15 @@ impl Parse for FieldAttrs {
             let _ = input.parse::<Token![,]>();
         }
 
-        Ok(Self { skip })
+        Ok(Self { skip, name })
     }
 }
 
 impl Combine for FieldAttrs {
     fn combine(mut self, other: Self) -> syn::Result<Self> {
-        let Self { skip } = other;
+        let Self { skip, name } = other;
         combine_unary_attribute(&mut self.skip, skip)?;
+        combine_attribute(&mut self.name, name)?;
         Ok(self)
     }
 }
@@ -691,6 +691,32 @@ pub fn derive_typed_path(input: TokenStream) -> TokenStream {
 /// # let _: axum::Router = app;
 /// ```
 ///
+/// # Fields with the same type
+///
+/// Implementing `FromRef` for two fields of the same type would conflict. Use
+/// `#[from_ref(name = "...")]` to instead generate a newtype wrapper for such fields. The wrapper
+/// is named after `name` converted to `UpperCamelCase` with a `State` suffix, so
+/// `#[from_ref(name = "db")]` generates `struct DbState(pub T)` with the same visibility as the
+/// state struct:
+///
+/// ```
+/// use axum::extract::{State, FromRef};
+///
+/// # type Pool = ();
+/// #[derive(FromRef, Clone)]
+/// struct AppState {
+///     #[from_ref(name = "db")]
+///     db: Pool,
+///     #[from_ref(name = "analytics_db")]
+///     analytics_db: Pool,
+/// }
+///
+/// async fn handler(
+///     State(DbState(db)): State<DbState>,
+///     State(AnalyticsDbState(analytics_db)): State<AnalyticsDbState>,
+/// ) {}
+/// ```
+///
 /// [`FromRef`]: https://docs.rs/axum/0.7/axum/extract/trait.FromRef.html
 #[proc_macro_derive(FromRef, attributes(from_ref))]
 pub fn derive_from_ref(item: TokenStream) -> TokenStream {
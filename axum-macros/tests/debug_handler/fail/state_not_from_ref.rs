@@ -0,0 +1,13 @@
+use axum::extract::State;
+use axum_macros::debug_handler;
+
+#[debug_handler(state = AppState)]
+async fn handler(_: State<AppState>, _: State<OtherState>) {}
+
+#[derive(Clone)]
+struct AppState;
+
+#[derive(Clone)]
+struct OtherState;
+
+fn main() {}
@@ -0,0 +1,30 @@
+use axum::{
+    extract::{FromRef, State},
+    routing::get,
+    Router,
+};
+use axum_macros::FromRef;
+
+#[derive(Clone)]
+struct Pool;
+
+#[derive(Clone, FromRef)]
+struct AppState {
+    #[from_ref(name = "db")]
+    db: Pool,
+    #[from_ref(name = "replica")]
+    replica: Pool,
+}
+
+async fn handler(State(DbState(_)): State<DbState>, State(ReplicaState(_)): State<ReplicaState>) {}
+
+fn main() {
+    let state = AppState {
+        db: Pool,
+        replica: Pool,
+    };
+
+    let _: DbState = FromRef::from_ref(&state);
+
+    let _: Router = Router::new().route("/", get(handler)).with_state(state);
+}
@@ -52,3 +52,43 @@ pub async fn to_bytes(body: Body, limit: usize) -> Result<Bytes, axum_core::Erro
         .map(|col| col.to_bytes())
         .map_err(axum_core::Error::new)
 }
+
+/// Checks whether a [`Body`] is empty, consuming it.
+///
+/// The body is read until the first non-empty chunk of data or until it ends, so at most one
+/// chunk is buffered. Trailers are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::body::{is_empty, Body};
+///
+/// # async fn foo() -> Result<(), axum_core::Error> {
+/// assert!(is_empty(Body::empty()).await?);
+/// assert!(!is_empty(Body::from("foo")).await?);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn is_empty(mut body: Body) -> Result<bool, axum_core::Error> {
+    while let Some(frame) = body.frame().await {
+        if let Ok(data) = frame?.into_data() {
+            if !data.is_empty() {
+                return Ok(false);
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[crate::test]
+    async fn body_is_empty() {
+        assert!(is_empty(Body::empty()).await.unwrap());
+        assert!(is_empty(Body::from("")).await.unwrap());
+        assert!(!is_empty(Body::from("foo")).await.unwrap());
+    }
+}
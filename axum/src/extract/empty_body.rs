@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use axum_core::extract::{FromRequest, Request};
+
+use super::rejection::{BodyNotEmpty, EmptyBodyRejection, FailedToReadBody};
+
+/// Extractor that requires the request body to be empty.
+///
+/// The body is read until its first non-empty chunk of data, using [`body::is_empty`], and the
+/// request is rejected with `400 Bad Request` if it contains any bytes. Non-empty bodies are
+/// never buffered in full, so the [default body limit](super::DefaultBodyLimit) doesn't apply.
+///
+/// [`body::is_empty`]: crate::body::is_empty
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{
+///     extract::EmptyBody,
+///     routing::delete,
+///     Router,
+/// };
+///
+/// async fn delete_user(_: EmptyBody) {}
+///
+/// let app = Router::new().route("/users/:id", delete(delete_user));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmptyBody;
+
+#[async_trait]
+impl<S> FromRequest<S> for EmptyBody
+where
+    S: Send + Sync,
+{
+    type Rejection = EmptyBodyRejection;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let is_empty = crate::body::is_empty(req.into_body())
+            .await
+            .map_err(FailedToReadBody::from_err)?;
+
+        if is_empty {
+            Ok(Self)
+        } else {
+            Err(BodyNotEmpty.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{body::Body, routing::post, test_helpers::*, Router};
+    use bytes::Bytes;
+    use http::StatusCode;
+    use std::convert::Infallible;
+    use tower::ServiceExt;
+
+    #[crate::test]
+    async fn empty_body() {
+        let app = Router::new().route("/", post(|_: EmptyBody| async {}));
+
+        let client = TestClient::new(app);
+        let res = client.post("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[crate::test]
+    async fn non_empty_body() {
+        let app = Router::new().route("/", post(|_: EmptyBody| async {}));
+
+        let client = TestClient::new(app);
+        let res = client.post("/").body("foo").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "Expected request with an empty body");
+    }
+
+    #[crate::test]
+    async fn non_empty_body_isnt_buffered() {
+        let app = Router::new().route("/", post(|_: EmptyBody| async {}));
+
+        // a body that never ends is rejected as soon as the first chunk arrives
+        let body = futures_util::stream::repeat(Ok::<_, Infallible>(Bytes::from_static(b"foo")));
+        let req = http::Request::post("/")
+            .body(Body::from_stream(body))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -10,6 +10,7 @@ pub mod rejection;
 #[cfg(feature = "ws")]
 pub mod ws;
 
+mod empty_body;
 mod host;
 pub(crate) mod nested_path;
 mod raw_form;
@@ -18,7 +19,10 @@ mod request_parts;
 mod state;
 
 #[doc(inline)]
-pub use axum_core::extract::{DefaultBodyLimit, FromRef, FromRequest, FromRequestParts, Request};
+pub use axum_core::extract::{
+    BytesAndTrailers, DefaultBodyLimit, FromRef, FromRequest, FromRequestParts, IdempotentMethod,
+    LimitedChars, Request, SafeMethod,
+};
 
 #[cfg(feature = "macros")]
 pub use axum_macros::{FromRef, FromRequest, FromRequestParts};
@@ -26,6 +30,7 @@ pub use axum_macros::{FromRef, FromRequest, FromRequestParts};
 #[doc(inline)]
 #[allow(deprecated)]
 pub use self::{
+    empty_body::EmptyBody,
     host::Host,
     nested_path::NestedPath,
     path::{Path, RawPathParams},
@@ -42,6 +47,10 @@ pub use self::connect_info::ConnectInfo;
 #[cfg(feature = "json")]
 pub use crate::Json;
 
+#[doc(inline)]
+#[cfg(feature = "json")]
+pub use crate::json::JsonValue;
+
 #[doc(no_inline)]
 pub use crate::Extension;
 
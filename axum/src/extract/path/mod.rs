@@ -476,6 +476,28 @@ impl RawPathParams {
     pub fn iter(&self) -> RawPathParamsIter<'_> {
         self.into_iter()
     }
+
+    /// Get the value of the path parameter called `name`, if it was captured.
+    ///
+    /// ```rust,no_run
+    /// use axum::{
+    ///     extract::RawPathParams,
+    ///     routing::get,
+    ///     Router,
+    /// };
+    ///
+    /// async fn users_show(params: RawPathParams) -> String {
+    ///     params.get("user_id").unwrap_or_default().to_owned()
+    /// }
+    ///
+    /// let app = Router::new().route("/users/:user_id", get(users_show));
+    /// # let _: Router = app;
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
 }
 
 impl<'a> IntoIterator for &'a RawPathParams {
@@ -887,4 +909,35 @@ mod tests {
         let body = res.text().await;
         assert_eq!(body, "a=foo b=bar c=baz");
     }
+
+    #[crate::test]
+    async fn raw_path_params_get() {
+        let app = Router::new()
+            .route(
+                "/users/:user_id/team/:team_id",
+                get(|params: RawPathParams| async move {
+                    format!(
+                        "{} {} {:?}",
+                        params.get("user_id").unwrap(),
+                        params.get("team_id").unwrap(),
+                        params.get("missing"),
+                    )
+                }),
+            )
+            .route(
+                "/",
+                get(|params: RawPathParams| async move {
+                    format!("{} {:?}", params.iter().count(), params.get("user_id"))
+                }),
+            );
+
+        let client = TestClient::new(app);
+
+        let res = client.get("/users/1/team/2").await;
+        assert_eq!(res.text().await, "1 2 None");
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "0 None");
+    }
 }
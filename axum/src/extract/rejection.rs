@@ -65,6 +65,22 @@ define_rejection! {
     pub struct InvalidFormContentType;
 }
 
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Expected request with an empty body"]
+    /// Rejection type used if the [`EmptyBody`](super::EmptyBody) extractor
+    /// encounters a request body that isn't empty.
+    pub struct BodyNotEmpty;
+}
+
+define_rejection! {
+    #[status = BAD_REQUEST]
+    #[body = "Failed to read the request body"]
+    /// Rejection type used if the [`EmptyBody`](super::EmptyBody) extractor
+    /// fails to read the request body.
+    pub struct FailedToReadBody(Error);
+}
+
 define_rejection! {
     #[status = BAD_REQUEST]
     #[body = "No host found in request"]
@@ -131,6 +147,17 @@ composite_rejection! {
     }
 }
 
+composite_rejection! {
+    /// Rejection used for [`EmptyBody`](super::EmptyBody).
+    ///
+    /// Contains one variant for each way the [`EmptyBody`](super::EmptyBody) extractor
+    /// can fail.
+    pub enum EmptyBodyRejection {
+        BodyNotEmpty,
+        FailedToReadBody,
+    }
+}
+
 #[cfg(feature = "json")]
 composite_rejection! {
     /// Rejection used for [`Json`](super::Json).
@@ -146,6 +173,20 @@ composite_rejection! {
     }
 }
 
+#[cfg(feature = "json")]
+composite_rejection! {
+    /// Rejection used for [`JsonValue`](super::JsonValue).
+    ///
+    /// Contains one variant for each way the [`JsonValue`](super::JsonValue) extractor
+    /// can fail.
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub enum JsonValueRejection {
+        JsonSyntaxError,
+        MissingJsonContentType,
+        BytesRejection,
+    }
+}
+
 composite_rejection! {
     /// Rejection used for [`Extension`](super::Extension).
     ///
@@ -1,6 +1,6 @@
 use super::{Extension, FromRequestParts};
 use async_trait::async_trait;
-use http::{request::Parts, Uri};
+use http::{request::Parts, Request, Uri};
 use std::convert::Infallible;
 
 /// Extractor that gets the original request URI regardless of nesting.
@@ -86,6 +86,48 @@ where
     }
 }
 
+#[cfg(feature = "original-uri")]
+impl OriginalUri {
+    /// Borrow the original URI from the request extensions, without cloning it.
+    ///
+    /// This is useful in middleware that only needs to read the URI, for example to record it
+    /// in a span. Returns `None` if the extension is missing, whereas the extractor falls back to
+    /// the current request URI.
+    ///
+    /// This can't be used as a handler argument since extractors must own their data. Use
+    /// `OriginalUri` itself for that.
+    pub fn get(parts: &Parts) -> Option<&Uri> {
+        parts.extensions.get::<Self>().map(|uri| &uri.0)
+    }
+
+    /// Split a request into its parts and body, making sure the parts contain the `OriginalUri`
+    /// extension.
+    ///
+    /// If the extension is missing, for example because a middleware removed it, it's inserted
+    /// using the current request URI. This way extracting `OriginalUri` from the parts later
+    /// still works.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axum::extract::{OriginalUri, Request};
+    ///
+    /// async fn handler(req: Request) {
+    ///     let (parts, body) = OriginalUri::into_parts_preserving(req);
+    ///     assert!(OriginalUri::get(&parts).is_some());
+    ///     // ...
+    /// }
+    /// ```
+    pub fn into_parts_preserving<B>(req: Request<B>) -> (Parts, B) {
+        let (mut parts, body) = req.into_parts();
+        if parts.extensions.get::<Self>().is_none() {
+            let uri = parts.uri.clone();
+            parts.extensions.insert(Self(uri));
+        }
+        (parts, body)
+    }
+}
+
 #[cfg(feature = "original-uri")]
 axum_core::__impl_deref!(OriginalUri: Uri);
 
@@ -112,4 +154,45 @@ mod tests {
         let res = client.get("/").header("x-foo", "123").await;
         assert_eq!(res.status(), StatusCode::OK);
     }
+
+    #[cfg(feature = "original-uri")]
+    #[crate::test]
+    async fn original_uri_get_borrows_extension() {
+        use super::OriginalUri;
+
+        async fn handler(OriginalUri(owned): OriginalUri, parts: http::request::Parts) {
+            assert_eq!(owned, "/api/users");
+            assert_eq!(OriginalUri::get(&parts), Some(&owned));
+        }
+
+        let app = Router::new().nest("/api", Router::new().route("/users", get(handler)));
+        let client = TestClient::new(app);
+
+        let res = client.get("/api/users").await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[cfg(feature = "original-uri")]
+    #[crate::test]
+    async fn original_uri_into_parts_preserving() {
+        use super::OriginalUri;
+        use crate::extract::FromRequestParts;
+        use http::{Request, Uri};
+
+        // the extension is inserted if it's missing
+        let req = Request::get("/users").body(()).unwrap();
+        let (mut parts, ()) = OriginalUri::into_parts_preserving(req);
+        let OriginalUri(uri) = OriginalUri::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+        assert_eq!(uri, "/users");
+
+        // an existing extension is kept as is
+        let mut req = Request::get("/users").body(()).unwrap();
+        req.extensions_mut()
+            .insert(OriginalUri(Uri::from_static("/api/users")));
+        let (parts, ()) = OriginalUri::into_parts_preserving(req);
+        assert_eq!(OriginalUri::get(&parts).unwrap(), "/api/users");
+        assert_eq!(parts.uri, "/users");
+    }
 }
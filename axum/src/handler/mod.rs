@@ -188,6 +188,48 @@ pub trait Handler<T, S>: Clone + Send + Sized + 'static {
     fn with_state(self, state: S) -> HandlerService<Self, T, S> {
         HandlerService::new(self, state)
     }
+
+    /// Adapt the handler to a different state type.
+    ///
+    /// The returned handler accepts state of type `S2` and calls `f` to produce the `S` this
+    /// handler requires. This is useful for mounting a handler that was written for some inner
+    /// state type into a router with a larger state type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{
+    ///     extract::{FromRef, State},
+    ///     handler::Handler,
+    ///     routing::get,
+    ///     Router,
+    /// };
+    ///
+    /// #[derive(Clone)]
+    /// struct AppState {
+    ///     db: DbState,
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// struct DbState {}
+    ///
+    /// async fn handler(State(db): State<DbState>) { /* ... */ }
+    ///
+    /// let app = Router::new()
+    ///     .route("/", get(handler.map_state(|state: &AppState| state.db.clone())))
+    ///     .with_state(AppState { db: DbState {} });
+    /// # let _: Router = app;
+    /// ```
+    fn map_state<F, S2>(self, f: F) -> MapState<Self, F, T, S>
+    where
+        F: FnOnce(&S2) -> S + Clone + Send + 'static,
+    {
+        MapState {
+            handler: self,
+            f,
+            _marker: PhantomData,
+        }
+    }
 }
 
 impl<F, Fut, Res, S> Handler<((),), S> for F
@@ -335,6 +377,52 @@ where
     }
 }
 
+/// A [`Handler`] that maps the state before calling another handler.
+///
+/// Created with [`Handler::map_state`]. See that method for more details.
+pub struct MapState<H, F, T, S> {
+    handler: H,
+    f: F,
+    _marker: PhantomData<fn() -> (T, S)>,
+}
+
+impl<H, F, T, S> fmt::Debug for MapState<H, F, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapState")
+            .field("f", &format_args!("{}", std::any::type_name::<F>()))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H, F, T, S> Clone for MapState<H, F, T, S>
+where
+    H: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            f: self.f.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<H, F, T, S, S2> Handler<T, S2> for MapState<H, F, T, S>
+where
+    H: Handler<T, S>,
+    F: FnOnce(&S2) -> S + Clone + Send + 'static,
+    T: 'static,
+    S: 'static,
+{
+    type Future = H::Future;
+
+    fn call(self, req: Request, state: S2) -> Self::Future {
+        let state = (self.f)(&state);
+        self.handler.call(req, state)
+    }
+}
+
 /// Extension trait for [`Handler`]s that don't have state.
 ///
 /// This provides convenience methods to convert the [`Handler`] into a [`Service`] or [`MakeService`].
@@ -427,4 +515,40 @@ mod tests {
         let res = client.get("/").await;
         assert_eq!(res.text().await, "foo");
     }
+
+    #[crate::test]
+    async fn map_state_with_from_ref() {
+        use crate::{extract::FromRef, routing::get, Router};
+
+        #[derive(Clone)]
+        struct Outer {
+            inner: Inner,
+        }
+
+        #[derive(Clone)]
+        struct Inner(&'static str);
+
+        impl FromRef<Outer> for Inner {
+            fn from_ref(outer: &Outer) -> Self {
+                outer.inner.clone()
+            }
+        }
+
+        async fn handle(State(inner): State<Inner>) -> &'static str {
+            inner.0
+        }
+
+        let app = Router::new()
+            .route(
+                "/",
+                get(handle.map_state(|outer: &Outer| Inner::from_ref(outer))),
+            )
+            .with_state(Outer {
+                inner: Inner("inner"),
+            });
+
+        let client = TestClient::new(app);
+        let res = client.get("/").await;
+        assert_eq!(res.text().await, "inner");
+    }
 }
@@ -179,6 +179,58 @@ where
     }
 }
 
+/// Extractor that deserializes a JSON body into a [`serde_json::Value`].
+///
+/// This is useful for endpoints that don't know the shape of the JSON up front, such as proxies
+/// or endpoints that transform the JSON, without having to define a type for it. It works like
+/// [`Json<serde_json::Value>`](Json) except that the rejection only has the variants that can
+/// actually happen, since every syntactically valid JSON document is a valid `Value`.
+///
+/// The request is rejected if it doesn't have a JSON `Content-Type` header, or if the body isn't
+/// valid JSON, in which case the rejection includes the line and column of the error.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{extract::JsonValue, routing::post, Json, Router};
+/// use serde_json::Value;
+///
+/// async fn strip_nulls(JsonValue(mut value): JsonValue) -> Json<Value> {
+///     if let Value::Object(map) = &mut value {
+///         map.retain(|_, value| !value.is_null());
+///     }
+///     Json(value)
+/// }
+///
+/// let app = Router::new().route("/", post(strip_nulls));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub struct JsonValue(pub serde_json::Value);
+
+#[async_trait]
+impl<S> FromRequest<S> for JsonValue
+where
+    S: Send + Sync,
+{
+    type Rejection = JsonValueRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !json_content_type(req.headers()) {
+            return Err(MissingJsonContentType.into());
+        }
+
+        let bytes = Bytes::from_request(req, state).await?;
+        let value = serde_json::from_slice(&bytes).map_err(JsonSyntaxError::from_err)?;
+
+        Ok(Self(value))
+    }
+}
+
+axum_core::__impl_deref!(JsonValue: serde_json::Value);
+
 impl<T> IntoResponse for Json<T>
 where
     T: Serialize,
@@ -232,6 +284,37 @@ mod tests {
         assert_eq!(body, "bar");
     }
 
+    #[crate::test]
+    async fn json_value() {
+        let app = Router::new().route(
+            "/",
+            post(|JsonValue(value): JsonValue| async move { value.to_string() }),
+        );
+        let client = TestClient::new(app);
+
+        let res = client.post("/").json(&json!({ "foo": [1, null] })).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, r#"{"foo":[1,null]}"#);
+
+        let res = client.post("/").json(&json!("scalar")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, r#""scalar""#);
+
+        let res = client
+            .post("/")
+            .header("content-type", "application/json")
+            .body("{\n  \"foo\": }")
+            .await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.text().await,
+            "Failed to parse the request body as JSON: expected value at line 2 column 10"
+        );
+
+        let res = client.post("/").body("{}").await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
     #[crate::test]
     async fn consume_body_to_json_requires_json_content_type() {
         #[derive(Debug, Deserialize)]
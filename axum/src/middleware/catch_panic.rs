@@ -0,0 +1,501 @@
+#[cfg(feature = "matched-path")]
+use crate::extract::MatchedPath;
+use crate::response::{IntoResponse, Response};
+use http::{header::HeaderName, HeaderValue, Method, Request, StatusCode, Uri};
+use pin_project_lite::pin_project;
+use std::{
+    any::{type_name, Any},
+    fmt,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// The type of a panic payload, as passed to the handler of [`CatchPanic`].
+pub type PanicPayload = Box<dyn Any + Send + 'static>;
+
+/// Layer that applies the [`CatchPanic`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{http::HeaderName, middleware::CatchPanicLayer, routing::get, Router};
+///
+/// let app = Router::new()
+///     .route("/users/:id", get(|| async { panic!("oh no") }))
+///     // panics are logged with the matched path `/users/:id`
+///     .layer(
+///         CatchPanicLayer::new().with_request_id_header(HeaderName::from_static("x-request-id")),
+///     );
+/// # let _: Router = app;
+/// ```
+pub struct CatchPanicLayer<F = fn(PanicPayload) -> Response> {
+    handler: F,
+    request_id_header: Option<HeaderName>,
+}
+
+impl CatchPanicLayer {
+    /// Create a new `CatchPanicLayer` that converts panics into empty
+    /// `500 Internal Server Error` responses.
+    pub fn new() -> Self {
+        Self {
+            handler: default_panic_handler,
+            request_id_header: None,
+        }
+    }
+}
+
+impl Default for CatchPanicLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> CatchPanicLayer<F> {
+    /// Use a custom function to convert panics into responses.
+    ///
+    /// See [`CatchPanic::with_handler`] for more details.
+    pub fn with_handler<F2>(self, handler: F2) -> CatchPanicLayer<F2>
+    where
+        F2: Fn(PanicPayload) -> Response,
+    {
+        CatchPanicLayer {
+            handler,
+            request_id_header: self.request_id_header,
+        }
+    }
+
+    /// Correlate panics with a request id read from the given request header.
+    ///
+    /// See [`CatchPanic::with_request_id_header`] for more details.
+    pub fn with_request_id_header(mut self, header: HeaderName) -> Self {
+        self.request_id_header = Some(header);
+        self
+    }
+}
+
+impl<F> Clone for CatchPanicLayer<F>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            request_id_header: self.request_id_header.clone(),
+        }
+    }
+}
+
+impl<F> fmt::Debug for CatchPanicLayer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchPanicLayer")
+            .field("handler", &format_args!("{}", type_name::<F>()))
+            .field("request_id_header", &self.request_id_header)
+            .finish()
+    }
+}
+
+impl<S, F> Layer<S> for CatchPanicLayer<F>
+where
+    F: Clone,
+{
+    type Service = CatchPanic<S, F>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CatchPanic {
+            inner,
+            handler: self.handler.clone(),
+            request_id_header: self.request_id_header.clone(),
+        }
+    }
+}
+
+/// Middleware that catches panics in the inner service and converts them into responses.
+///
+/// By default a panic results in an empty `500 Internal Server Error` response. Use
+/// [`CatchPanic::with_handler`] to customize the response.
+///
+/// This is meant as a last-resort boundary. Without it a panicking handler aborts the task
+/// serving the connection and the client doesn't get a response.
+///
+/// With the `tracing` feature enabled, each panic is logged as an error event including the
+/// panic message, the method and path of the request and, if available, the [`MatchedPath`].
+/// Use [`CatchPanic::with_request_id_header`] to also include a request id so the response can
+/// be correlated with the logs.
+///
+/// The [`MatchedPath`] is only available if the middleware is added with [`Router::layer`] or
+/// [`Router::route_layer`], since it's inserted by [`Router`] when a route matches. Wrapping the
+/// whole router also catches panics in the router itself and its fallback, but doesn't log the
+/// matched path.
+///
+/// Created with [`CatchPanicLayer`] or [`ServiceExt::catch_panic`](crate::ServiceExt::catch_panic).
+///
+/// [`MatchedPath`]: crate::extract::MatchedPath
+/// [`Router`]: crate::Router
+/// [`Router::layer`]: crate::Router::layer
+/// [`Router::route_layer`]: crate::Router::route_layer
+///
+/// # Example
+///
+/// ```
+/// use axum::{extract::Request, routing::get, Router, ServiceExt};
+///
+/// let app = Router::new().route("/", get(|| async { panic!("oh no") }));
+///
+/// // requests to `/` will now get a `500 Internal Server Error` response
+/// let app = ServiceExt::<Request>::catch_panic(app);
+/// # let _ = app;
+/// ```
+pub struct CatchPanic<S, F = fn(PanicPayload) -> Response> {
+    inner: S,
+    handler: F,
+    request_id_header: Option<HeaderName>,
+}
+
+impl<S> CatchPanic<S> {
+    pub(crate) fn new(inner: S) -> Self {
+        Self {
+            inner,
+            handler: default_panic_handler,
+            request_id_header: None,
+        }
+    }
+}
+
+impl<S, F> CatchPanic<S, F> {
+    /// Use a custom function to convert panics into responses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axum::{
+    ///     extract::Request,
+    ///     http::StatusCode,
+    ///     middleware::PanicPayload,
+    ///     response::{IntoResponse, Response},
+    ///     routing::get,
+    ///     Router, ServiceExt,
+    /// };
+    ///
+    /// fn handle_panic(payload: PanicPayload) -> Response {
+    ///     let message = payload
+    ///         .downcast_ref::<&str>()
+    ///         .copied()
+    ///         .or_else(|| payload.downcast_ref::<String>().map(|s| s.as_str()))
+    ///         .unwrap_or("unknown panic");
+    ///
+    ///     eprintln!("handler panicked: {message}");
+    ///
+    ///     (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong").into_response()
+    /// }
+    ///
+    /// let app = Router::new().route("/", get(|| async { panic!("oh no") }));
+    /// let app = ServiceExt::<Request>::catch_panic(app).with_handler(handle_panic);
+    /// # let _ = app;
+    /// ```
+    pub fn with_handler<F2>(self, handler: F2) -> CatchPanic<S, F2>
+    where
+        F2: Fn(PanicPayload) -> Response,
+    {
+        CatchPanic {
+            inner: self.inner,
+            handler,
+            request_id_header: self.request_id_header,
+        }
+    }
+
+    /// Correlate panics with a request id read from the given request header.
+    ///
+    /// The request id is included in the logged event and copied to the same header of the
+    /// response produced for the panic, so operators can find the log for a failed request.
+    /// The header is typically set by an earlier middleware such as
+    /// [`tower_http::request_id::SetRequestIdLayer`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axum::{extract::Request, http::HeaderName, routing::get, Router, ServiceExt};
+    ///
+    /// let app = Router::new().route("/", get(|| async { panic!("oh no") }));
+    /// let app = ServiceExt::<Request>::catch_panic(app)
+    ///     .with_request_id_header(HeaderName::from_static("x-request-id"));
+    /// # let _ = app;
+    /// ```
+    ///
+    /// [`tower_http::request_id::SetRequestIdLayer`]: https://docs.rs/tower-http/latest/tower_http/request_id/struct.SetRequestIdLayer.html
+    pub fn with_request_id_header(mut self, header: HeaderName) -> Self {
+        self.request_id_header = Some(header);
+        self
+    }
+}
+
+fn default_panic_handler(_: PanicPayload) -> Response {
+    StatusCode::INTERNAL_SERVER_ERROR.into_response()
+}
+
+impl<S, F> Clone for CatchPanic<S, F>
+where
+    S: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            handler: self.handler.clone(),
+            request_id_header: self.request_id_header.clone(),
+        }
+    }
+}
+
+impl<S, F> fmt::Debug for CatchPanic<S, F>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchPanic")
+            .field("inner", &self.inner)
+            .field("handler", &format_args!("{}", type_name::<F>()))
+            .field("request_id_header", &self.request_id_header)
+            .finish()
+    }
+}
+
+impl<S, F, B> Service<Request<B>> for CatchPanic<S, F>
+where
+    S: Service<Request<B>>,
+    S::Response: IntoResponse,
+    F: Fn(PanicPayload) -> Response + Clone,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, F>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let request_id = self.request_id_header.as_ref().and_then(|name| {
+            let value = req.headers().get(name)?;
+            Some((name.clone(), value.clone()))
+        });
+        let context = PanicContext {
+            method: req.method().clone(),
+            uri: req.uri().clone(),
+            #[cfg(feature = "matched-path")]
+            matched_path: req.extensions().get::<MatchedPath>().cloned(),
+            request_id,
+        };
+
+        let (future, panic) = match catch_unwind(AssertUnwindSafe(|| self.inner.call(req))) {
+            Ok(future) => (Some(future), None),
+            Err(panic) => (None, Some(panic)),
+        };
+
+        ResponseFuture {
+            future,
+            panic,
+            handler: self.handler.clone(),
+            context,
+        }
+    }
+}
+
+/// Information about the request that's logged if it panics.
+struct PanicContext {
+    // only used for logging
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    method: Method,
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    uri: Uri,
+    #[cfg(feature = "matched-path")]
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    matched_path: Option<MatchedPath>,
+    request_id: Option<(HeaderName, HeaderValue)>,
+}
+
+impl PanicContext {
+    fn handle<F>(&self, handler: &F, panic: PanicPayload) -> Response
+    where
+        F: Fn(PanicPayload) -> Response,
+    {
+        #[cfg(feature = "tracing")]
+        self.log(&panic);
+
+        let mut res = handler(panic);
+        if let Some((name, value)) = &self.request_id {
+            res.headers_mut().insert(name.clone(), value.clone());
+        }
+        res
+    }
+
+    #[cfg(feature = "tracing")]
+    fn log(&self, panic: &PanicPayload) {
+        let panic_message = panic
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+
+        #[cfg(feature = "matched-path")]
+        let matched_path = self.matched_path.as_ref().map(MatchedPath::as_str);
+        #[cfg(not(feature = "matched-path"))]
+        let matched_path: Option<&str> = None;
+
+        let request_id = self
+            .request_id
+            .as_ref()
+            .and_then(|(_, value)| value.to_str().ok());
+
+        tracing::error!(
+            panic_message,
+            method = %self.method,
+            path = self.uri.path(),
+            matched_path,
+            request_id,
+            "request panicked",
+        );
+    }
+}
+
+pin_project! {
+    /// Response future for [`CatchPanic`].
+    pub struct ResponseFuture<Fut, F> {
+        #[pin]
+        future: Option<Fut>,
+        panic: Option<PanicPayload>,
+        handler: F,
+        context: PanicContext,
+    }
+}
+
+impl<Fut, F, T, E> Future for ResponseFuture<Fut, F>
+where
+    Fut: Future<Output = Result<T, E>>,
+    T: IntoResponse,
+    F: Fn(PanicPayload) -> Response,
+{
+    type Output = Result<Response, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        // the inner service panicked in `call`
+        if let Some(panic) = this.panic.take() {
+            return Poll::Ready(Ok(this.context.handle(this.handler, panic)));
+        }
+
+        let future = this
+            .future
+            .as_pin_mut()
+            .expect("future polled after completion");
+
+        match catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(Poll::Ready(result)) => Poll::Ready(result.map(IntoResponse::into_response)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(panic) => Poll::Ready(Ok(this.context.handle(this.handler, panic))),
+        }
+    }
+}
+
+impl<Fut, F> fmt::Debug for ResponseFuture<Fut, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseFuture").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        extract::Request,
+        routing::get,
+        test_helpers::{
+            tracing_helpers::{capture_tracing, TracingEvent},
+            *,
+        },
+        Router, ServiceExt,
+    };
+    use serde::Deserialize;
+
+    #[crate::test]
+    async fn panics_become_500() {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .route("/panic", get(|| async { panic!("oh no") }));
+        let client = TestClient::new(ServiceExt::<Request>::catch_panic(app));
+
+        let res = client.get("/panic").await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        // the server is still running
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "ok");
+    }
+
+    #[crate::test]
+    async fn custom_handler() {
+        let app = Router::new().route("/", get(|| async { panic!("oh no") }));
+        let app = ServiceExt::<Request>::catch_panic(app).with_handler(|payload: PanicPayload| {
+            let message = payload.downcast_ref::<&str>().copied().unwrap_or_default();
+            (StatusCode::SERVICE_UNAVAILABLE, message.to_owned()).into_response()
+        });
+        let client = TestClient::new(app);
+
+        let res = client.get("/").await;
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(res.text().await, "oh no");
+    }
+
+    #[crate::test]
+    async fn logs_panics_with_request_id() {
+        #[derive(Deserialize, Eq, PartialEq, Debug)]
+        #[serde(deny_unknown_fields)]
+        struct PanicEvent {
+            message: String,
+            panic_message: String,
+            method: String,
+            path: String,
+            matched_path: String,
+            request_id: String,
+        }
+
+        let events = capture_tracing::<PanicEvent, _, _>(|| async {
+            let app = Router::new()
+                .route("/users/:id", get(|| async { panic!("oh no") }))
+                .layer(
+                    CatchPanicLayer::new()
+                        .with_request_id_header(HeaderName::from_static("x-request-id")),
+                );
+            let client = TestClient::new(app);
+
+            let res = client
+                .get("/users/1")
+                .header("x-request-id", "abc123")
+                .await;
+            assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+            assert_eq!(res.headers()["x-request-id"], "abc123");
+        })
+        .await;
+
+        assert_eq!(
+            events,
+            Vec::from([TracingEvent {
+                fields: PanicEvent {
+                    message: "request panicked".to_owned(),
+                    panic_message: "oh no".to_owned(),
+                    method: "GET".to_owned(),
+                    path: "/users/1".to_owned(),
+                    matched_path: "/users/:id".to_owned(),
+                    request_id: "abc123".to_owned(),
+                },
+                target: "axum::middleware::catch_panic".to_owned(),
+                level: "ERROR".to_owned(),
+            }]),
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use http::{Response, StatusCode};
+use pin_project_lite::pin_project;
+use std::{
+    any::type_name,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`MapStatus`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{http::StatusCode, middleware::MapStatusLayer, routing::get, Router};
+///
+/// // a legacy handler that uses a non-standard status for invalid input
+/// async fn legacy() -> StatusCode {
+///     StatusCode::IM_A_TEAPOT
+/// }
+///
+/// let app = Router::new()
+///     .route("/", get(legacy))
+///     .layer(MapStatusLayer::new(|status| {
+///         if status == StatusCode::IM_A_TEAPOT {
+///             StatusCode::BAD_REQUEST
+///         } else {
+///             status
+///         }
+///     }));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone, Copy)]
+pub struct MapStatusLayer<F> {
+    f: F,
+}
+
+impl<F> MapStatusLayer<F> {
+    /// Create a new `MapStatusLayer` that maps the status with `f`.
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<F> fmt::Debug for MapStatusLayer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapStatusLayer")
+            .field("f", &format_args!("{}", type_name::<F>()))
+            .finish()
+    }
+}
+
+impl<S, F> Layer<S> for MapStatusLayer<F>
+where
+    F: Clone,
+{
+    type Service = MapStatus<S, F>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MapStatus::new(inner, self.f.clone())
+    }
+}
+
+/// Middleware that rewrites the status of responses with a function.
+///
+/// Only the status is changed. The headers and body of the response are passed through
+/// untouched, so for example a body describing an error is kept when its status is changed from
+/// `200 OK` to a `4xx` status.
+///
+/// Created with [`MapStatusLayer`] or [`ServiceExt::map_status`](crate::ServiceExt::map_status).
+pub struct MapStatus<S, F> {
+    inner: S,
+    f: F,
+}
+
+impl<S, F> MapStatus<S, F> {
+    pub(crate) fn new(inner: S, f: F) -> Self {
+        Self { inner, f }
+    }
+}
+
+impl<S, F> Clone for MapStatus<S, F>
+where
+    S: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<S, F> fmt::Debug for MapStatus<S, F>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapStatus")
+            .field("inner", &self.inner)
+            .field("f", &format_args!("{}", type_name::<F>()))
+            .finish()
+    }
+}
+
+impl<S, F, R, ResBody> Service<R> for MapStatus<S, F>
+where
+    S: Service<R, Response = Response<ResBody>>,
+    F: Fn(StatusCode) -> StatusCode + Clone,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, F>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        ResponseFuture {
+            future: self.inner.call(req),
+            f: self.f.clone(),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`MapStatus`].
+    pub struct ResponseFuture<Fut, F> {
+        #[pin]
+        future: Fut,
+        f: F,
+    }
+}
+
+impl<Fut, F, ResBody, E> Future for ResponseFuture<Fut, F>
+where
+    Fut: Future<Output = Result<Response<ResBody>, E>>,
+    F: Fn(StatusCode) -> StatusCode,
+{
+    type Output = Result<Response<ResBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut res = std::task::ready!(this.future.poll(cx))?;
+
+        let status = (this.f)(res.status());
+        *res.status_mut() = status;
+
+        Poll::Ready(Ok(res))
+    }
+}
+
+impl<Fut, F> fmt::Debug for ResponseFuture<Fut, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseFuture").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::*, Router};
+
+    #[crate::test]
+    async fn maps_status_and_keeps_body() {
+        let app = Router::new()
+            .route(
+                "/teapot",
+                get(|| async { (StatusCode::IM_A_TEAPOT, "invalid") }),
+            )
+            .route("/ok", get(|| async { "ok" }))
+            .layer(MapStatusLayer::new(|status| {
+                if status == StatusCode::IM_A_TEAPOT {
+                    StatusCode::BAD_REQUEST
+                } else {
+                    status
+                }
+            }));
+        let client = TestClient::new(app);
+
+        let res = client.get("/teapot").await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(res.text().await, "invalid");
+
+        let res = client.get("/ok").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "ok");
+    }
+}
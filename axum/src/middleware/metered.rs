@@ -0,0 +1,220 @@
+use crate::extract::MatchedPath;
+use http::{Request, Response, StatusCode};
+use pin_project_lite::pin_project;
+use std::{
+    any::type_name,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that applies the [`Metered`] middleware.
+///
+/// # Example
+///
+/// ```
+/// use axum::{extract::MatchedPath, middleware::MeteredLayer, routing::get, Router};
+/// use axum::http::StatusCode;
+/// use std::time::Duration;
+///
+/// fn record(path: &MatchedPath, latency: Duration, status: StatusCode) {
+///     // record `latency` in a histogram labeled with `path` and `status`...
+/// }
+///
+/// let app = Router::new()
+///     .route("/users/:id", get(|| async {}))
+///     .layer(MeteredLayer::new(record));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone, Copy)]
+pub struct MeteredLayer<F> {
+    callback: F,
+}
+
+impl<F> MeteredLayer<F> {
+    /// Create a new `MeteredLayer` that reports to `callback`.
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+impl<F> fmt::Debug for MeteredLayer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MeteredLayer")
+            .field("callback", &format_args!("{}", type_name::<F>()))
+            .finish()
+    }
+}
+
+impl<S, F> Layer<S> for MeteredLayer<F>
+where
+    F: Clone,
+{
+    type Service = Metered<S, F>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Metered::new(inner, self.callback.clone())
+    }
+}
+
+/// Middleware that measures the latency of each request and reports it along with the route and
+/// the response status.
+///
+/// The latency is measured from calling the inner service until its response future resolves, so
+/// it doesn't include streaming the response body. It's reported to a callback with the
+/// signature `fn(&MatchedPath, Duration, StatusCode)`, for example to record it in a histogram.
+///
+/// The route is read from the [`MatchedPath`] request extension, which is inserted by [`Router`]
+/// when a route matches. So the middleware must be added with [`Router::layer`] or
+/// [`Router::route_layer`] and not wrap the whole router. Requests without a [`MatchedPath`], and
+/// requests where the inner service fails, are not reported.
+///
+/// Created with [`MeteredLayer`] or [`ServiceExt::metered`](crate::ServiceExt::metered).
+///
+/// [`Router`]: crate::Router
+/// [`Router::layer`]: crate::Router::layer
+/// [`Router::route_layer`]: crate::Router::route_layer
+pub struct Metered<S, F> {
+    inner: S,
+    callback: F,
+}
+
+impl<S, F> Metered<S, F> {
+    pub(crate) fn new(inner: S, callback: F) -> Self {
+        Self { inner, callback }
+    }
+}
+
+impl<S, F> Clone for Metered<S, F>
+where
+    S: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            callback: self.callback.clone(),
+        }
+    }
+}
+
+impl<S, F> fmt::Debug for Metered<S, F>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metered")
+            .field("inner", &self.inner)
+            .field("callback", &format_args!("{}", type_name::<F>()))
+            .finish()
+    }
+}
+
+impl<S, F, B, ResBody> Service<Request<B>> for Metered<S, F>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+    F: Fn(&MatchedPath, Duration, StatusCode) + Clone,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, F>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let matched_path = req.extensions().get::<MatchedPath>().cloned();
+        let start = Instant::now();
+
+        ResponseFuture {
+            future: self.inner.call(req),
+            start,
+            matched_path,
+            callback: self.callback.clone(),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`Metered`].
+    pub struct ResponseFuture<Fut, F> {
+        #[pin]
+        future: Fut,
+        start: Instant,
+        matched_path: Option<MatchedPath>,
+        callback: F,
+    }
+}
+
+impl<Fut, F, ResBody, E> Future for ResponseFuture<Fut, F>
+where
+    Fut: Future<Output = Result<Response<ResBody>, E>>,
+    F: Fn(&MatchedPath, Duration, StatusCode),
+{
+    type Output = Result<Response<ResBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = std::task::ready!(this.future.poll(cx));
+
+        if let (Ok(res), Some(matched_path)) = (&result, this.matched_path.take()) {
+            (this.callback)(&matched_path, this.start.elapsed(), res.status());
+        }
+
+        Poll::Ready(result)
+    }
+}
+
+impl<Fut, F> fmt::Debug for ResponseFuture<Fut, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseFuture").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{routing::get, test_helpers::*, Router};
+    use std::sync::{Arc, Mutex};
+
+    #[crate::test]
+    async fn reports_matched_path_latency_and_status() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+
+        let app = Router::new()
+            .route(
+                "/users/:id",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    StatusCode::CREATED
+                }),
+            )
+            .layer(MeteredLayer::new({
+                let reports = Arc::clone(&reports);
+                move |path: &MatchedPath, latency: Duration, status: StatusCode| {
+                    let report = (path.as_str().to_owned(), latency, status);
+                    reports.lock().unwrap().push(report);
+                }
+            }));
+        let client = TestClient::new(app);
+
+        let res = client.get("/users/1").await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        // unmatched requests aren't reported
+        let res = client.get("/not-found").await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 1);
+        let (path, latency, status) = &reports[0];
+        assert_eq!(path, "/users/:id");
+        assert!(*latency >= Duration::from_millis(10));
+        assert_eq!(*status, StatusCode::CREATED);
+    }
+}
@@ -2,11 +2,17 @@
 //!
 #![doc = include_str!("../docs/middleware.md")]
 
+mod catch_panic;
 mod from_extractor;
 mod from_fn;
 mod map_request;
 mod map_response;
+mod map_status;
+#[cfg(feature = "matched-path")]
+mod metered;
+mod prefix;
 
+pub use self::catch_panic::{CatchPanic, CatchPanicLayer, PanicPayload};
 pub use self::from_extractor::{
     from_extractor, from_extractor_with_state, FromExtractor, FromExtractorLayer,
 };
@@ -17,13 +23,22 @@ pub use self::map_request::{
 pub use self::map_response::{
     map_response, map_response_with_state, MapResponse, MapResponseLayer,
 };
+pub use self::map_status::{MapStatus, MapStatusLayer};
+#[cfg(feature = "matched-path")]
+pub use self::metered::{Metered, MeteredLayer};
+pub use self::prefix::{AddPrefix, StripPrefix};
 pub use crate::extension::AddExtension;
 
 pub mod future {
     //! Future types.
 
+    pub use super::catch_panic::ResponseFuture as CatchPanicResponseFuture;
     pub use super::from_extractor::ResponseFuture as FromExtractorResponseFuture;
     pub use super::from_fn::ResponseFuture as FromFnResponseFuture;
     pub use super::map_request::ResponseFuture as MapRequestResponseFuture;
     pub use super::map_response::ResponseFuture as MapResponseResponseFuture;
+    pub use super::map_status::ResponseFuture as MapStatusResponseFuture;
+    #[cfg(feature = "matched-path")]
+    pub use super::metered::ResponseFuture as MeteredResponseFuture;
+    pub use super::prefix::ResponseFuture as StripPrefixResponseFuture;
 }
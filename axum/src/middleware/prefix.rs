@@ -0,0 +1,296 @@
+use crate::{
+    response::{IntoResponse, Response},
+    routing::strip_prefix::strip_prefix,
+};
+use http::{uri::PathAndQuery, Request, StatusCode, Uri};
+use pin_project_lite::pin_project;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_service::Service;
+
+/// Middleware that removes a prefix from the path of requests before calling the inner service.
+///
+/// This mounts a service under a prefix, like [`Router::nest_service`] does, without using a
+/// [`Router`]. The prefix is matched segment by segment, so `/api` matches `/api` and `/api/users`
+/// but not `/apiary`. Requests whose path doesn't start with the prefix are rejected with
+/// `404 Not Found` without calling the inner service.
+///
+/// If the `original-uri` feature is enabled the URI of the request before the prefix is removed
+/// is available through the [`OriginalUri`] extractor.
+///
+/// Created with [`ServiceExt::strip_prefix`](crate::ServiceExt::strip_prefix).
+///
+/// # Example
+///
+/// ```
+/// use axum::{extract::Request, routing::get, Router, ServiceExt};
+///
+/// let users = Router::new().route("/users", get(|| async { /* ... */ }));
+///
+/// // `GET /api/users` is routed to `/users`
+/// let app = ServiceExt::<Request>::strip_prefix(users, "/api");
+/// # async {
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+/// axum::serve(listener, ServiceExt::<Request>::into_make_service(app)).await.unwrap();
+/// # };
+/// ```
+///
+/// [`Router::nest_service`]: crate::Router::nest_service
+/// [`Router`]: crate::Router
+/// [`OriginalUri`]: crate::extract::OriginalUri
+#[derive(Clone)]
+pub struct StripPrefix<S> {
+    inner: S,
+    prefix: Arc<str>,
+}
+
+impl<S> StripPrefix<S> {
+    pub(crate) fn new(inner: S, prefix: &str) -> Self {
+        assert!(prefix.starts_with('/'), "Prefixes must start with a `/`");
+        Self {
+            inner,
+            prefix: prefix.into(),
+        }
+    }
+}
+
+impl<S> fmt::Debug for StripPrefix<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StripPrefix")
+            .field("inner", &self.inner)
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+impl<S, B> Service<Request<B>> for StripPrefix<S>
+where
+    S: Service<Request<B>>,
+    S::Response: IntoResponse,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let Some(new_uri) = strip_prefix(req.uri(), &self.prefix) else {
+            return ResponseFuture { future: None };
+        };
+
+        set_original_uri(&mut req);
+        *req.uri_mut() = new_uri;
+
+        ResponseFuture {
+            future: Some(self.inner.call(req)),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`StripPrefix`].
+    pub struct ResponseFuture<Fut> {
+        #[pin]
+        future: Option<Fut>,
+    }
+}
+
+impl<Fut, T, E> Future for ResponseFuture<Fut>
+where
+    Fut: Future<Output = Result<T, E>>,
+    T: IntoResponse,
+{
+    type Output = Result<Response, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().future.as_pin_mut() {
+            Some(future) => future
+                .poll(cx)
+                .map(|result| result.map(IntoResponse::into_response)),
+            // the prefix didn't match
+            None => Poll::Ready(Ok(StatusCode::NOT_FOUND.into_response())),
+        }
+    }
+}
+
+impl<Fut> fmt::Debug for ResponseFuture<Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseFuture").finish()
+    }
+}
+
+/// Middleware that adds a prefix to the path of requests before calling the inner service.
+///
+/// This is the counterpart of [`StripPrefix`], for calling a service that expects its routes to
+/// be under a prefix.
+///
+/// If the `original-uri` feature is enabled the URI of the request before the prefix is added is
+/// available through the [`OriginalUri`] extractor.
+///
+/// Requests whose URI has no path, such as `CONNECT` requests in authority form, are passed
+/// through unchanged.
+///
+/// Created with [`ServiceExt::add_prefix`](crate::ServiceExt::add_prefix).
+///
+/// [`OriginalUri`]: crate::extract::OriginalUri
+#[derive(Clone)]
+pub struct AddPrefix<S> {
+    inner: S,
+    prefix: Arc<str>,
+}
+
+impl<S> AddPrefix<S> {
+    pub(crate) fn new(inner: S, prefix: &str) -> Self {
+        assert!(prefix.starts_with('/'), "Prefixes must start with a `/`");
+        assert!(
+            !prefix.contains('?') && prefix.parse::<PathAndQuery>().is_ok(),
+            "Prefixes must be valid URI paths"
+        );
+        Self {
+            inner,
+            prefix: prefix.trim_end_matches('/').into(),
+        }
+    }
+}
+
+impl<S> fmt::Debug for AddPrefix<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AddPrefix")
+            .field("inner", &self.inner)
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+impl<S, B> Service<Request<B>> for AddPrefix<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        if let Some(new_uri) = add_prefix(req.uri(), &self.prefix) {
+            set_original_uri(&mut req);
+            *req.uri_mut() = new_uri;
+        }
+
+        self.inner.call(req)
+    }
+}
+
+/// Returns `None` if the URI has no path to add the prefix to.
+fn add_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
+    // `Uri::path` returns an empty path for authority form URIs
+    if !uri.path().starts_with('/') {
+        return None;
+    }
+
+    let new_path_and_query = match uri.query() {
+        Some(query) => format!("{prefix}{}?{query}", uri.path()),
+        None => format!("{prefix}{}", uri.path()),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(new_path_and_query.parse().ok()?);
+
+    Uri::from_parts(parts).ok()
+}
+
+fn set_original_uri<B>(req: &mut Request<B>) {
+    #[cfg(feature = "original-uri")]
+    {
+        use crate::extract::OriginalUri;
+
+        if req.extensions().get::<OriginalUri>().is_none() {
+            let original_uri = OriginalUri(req.uri().clone());
+            req.extensions_mut().insert(original_uri);
+        }
+    }
+
+    #[cfg(not(feature = "original-uri"))]
+    let _ = req;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        extract::{OriginalUri, Request},
+        routing::get,
+        test_helpers::*,
+        Router, ServiceExt,
+    };
+    use http::{Method, StatusCode, Uri};
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt as _};
+
+    async fn handler(uri: Uri, OriginalUri(original_uri): OriginalUri) -> String {
+        format!("{uri} {original_uri}")
+    }
+
+    #[crate::test]
+    async fn strip_prefix() {
+        let app = Router::new().route("/users", get(handler));
+        let client = TestClient::new(ServiceExt::<Request>::strip_prefix(app, "/api"));
+
+        let res = client.get("/api/users?page=2").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "/users?page=2 /api/users?page=2");
+
+        let res = client.get("/apiary/users").await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let res = client.get("/users").await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[crate::test]
+    async fn add_prefix() {
+        let app = Router::new().route("/api/users", get(handler));
+        let client = TestClient::new(ServiceExt::<Request>::add_prefix(app, "/api"));
+
+        let res = client.get("/users?page=2").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.text().await, "/api/users?page=2 /users?page=2");
+    }
+
+    #[crate::test]
+    async fn add_prefix_authority_form() {
+        let svc = service_fn(|req: Request| async move { Ok::<_, Infallible>(req.uri().clone()) });
+        let svc = ServiceExt::<Request>::add_prefix(svc, "/api");
+
+        let req = Request::builder()
+            .method(Method::CONNECT)
+            .uri("example.com:443")
+            .body(crate::body::Body::empty())
+            .unwrap();
+        let uri = svc.oneshot(req).await.unwrap();
+        assert_eq!(uri, "example.com:443");
+    }
+
+    #[test]
+    #[should_panic(expected = "Prefixes must be valid URI paths")]
+    fn add_invalid_prefix() {
+        let app = Router::<()>::new();
+        let _ = ServiceExt::<Request>::add_prefix(app, "/a b");
+    }
+}
@@ -31,7 +31,7 @@ mod method_filter;
 mod not_found;
 pub(crate) mod path_router;
 mod route;
-mod strip_prefix;
+pub(crate) mod strip_prefix;
 pub(crate) mod url_params;
 
 #[cfg(test)]
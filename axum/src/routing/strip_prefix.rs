@@ -44,7 +44,7 @@ where
     }
 }
 
-fn strip_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
+pub(crate) fn strip_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
     let path_and_query = uri.path_and_query()?;
 
     // Check whether the prefix matches the path and if so how long the matching prefix is.
@@ -1,7 +1,10 @@
 use crate::error_handling::HandleError;
 #[cfg(feature = "tokio")]
 use crate::extract::connect_info::IntoMakeServiceWithConnectInfo;
+use crate::middleware::{AddPrefix, CatchPanic, MapStatus, StripPrefix};
 use crate::routing::IntoMakeService;
+#[cfg(feature = "matched-path")]
+use crate::{extract::MatchedPath, middleware::Metered};
 use tower_service::Service;
 
 /// Extension trait that adds additional methods to any [`Service`].
@@ -42,6 +45,73 @@ pub trait ServiceExt<R>: Service<R> + Sized {
     fn handle_error<F, T>(self, f: F) -> HandleError<Self, F, T> {
         HandleError::new(self, f)
     }
+
+    /// Convert this service into a [`CatchPanic`], that will catch panics in the service and
+    /// convert them into `500 Internal Server Error` responses.
+    ///
+    /// See [`CatchPanic`] for more details.
+    ///
+    /// [`CatchPanic`]: crate::middleware::CatchPanic
+    fn catch_panic(self) -> CatchPanic<Self> {
+        CatchPanic::new(self)
+    }
+
+    /// Convert this service into a [`MapStatus`], that will rewrite the status of each response
+    /// with `f`.
+    ///
+    /// Only the status is changed, the headers and body of the response are left untouched. See
+    /// [`MapStatus`] for more details.
+    ///
+    /// [`MapStatus`]: crate::middleware::MapStatus
+    fn map_status<F>(self, f: F) -> MapStatus<Self, F>
+    where
+        F: Fn(http::StatusCode) -> http::StatusCode + Clone,
+    {
+        MapStatus::new(self, f)
+    }
+
+    /// Convert this service into a [`Metered`], that will report the latency and status of each
+    /// request to `callback`.
+    ///
+    /// See [`Metered`] for more details.
+    ///
+    /// [`Metered`]: crate::middleware::Metered
+    #[cfg(feature = "matched-path")]
+    fn metered<F>(self, callback: F) -> Metered<Self, F>
+    where
+        F: Fn(&MatchedPath, std::time::Duration, http::StatusCode) + Clone,
+    {
+        Metered::new(self, callback)
+    }
+
+    /// Convert this service into a [`StripPrefix`], that will remove `prefix` from the path of
+    /// each request before calling this service.
+    ///
+    /// Requests whose path doesn't start with `prefix` are rejected with `404 Not Found`. See
+    /// [`StripPrefix`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix` doesn't start with a `/`.
+    ///
+    /// [`StripPrefix`]: crate::middleware::StripPrefix
+    fn strip_prefix(self, prefix: &str) -> StripPrefix<Self> {
+        StripPrefix::new(self, prefix)
+    }
+
+    /// Convert this service into an [`AddPrefix`], that will add `prefix` to the path of each
+    /// request before calling this service.
+    ///
+    /// See [`AddPrefix`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix` doesn't start with a `/` or isn't a valid URI path.
+    ///
+    /// [`AddPrefix`]: crate::middleware::AddPrefix
+    fn add_prefix(self, prefix: &str) -> AddPrefix<Self> {
+        AddPrefix::new(self, prefix)
+    }
 }
 
 impl<S, R> ServiceExt<R> for S